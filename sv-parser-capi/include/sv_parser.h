@@ -0,0 +1,154 @@
+#ifndef SV_PARSER_H
+#define SV_PARSER_H
+
+/* Generated by cbindgen from sv-parser-capi/src/lib.rs. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/* Returned by node functions if there is no such node */
+#define SV_NODE_NONE SIZE_MAX
+
+
+
+typedef enum SvSeverity {
+  SV_SEVERITY_ERROR = 0,
+  SV_SEVERITY_WARNING = 1,
+} SvSeverity;
+
+/**
+ * A syntax tree and the node table built from it
+ */
+typedef struct SvTree SvTree;
+
+/**
+ * Options of `sv_parse_file` and `sv_parse_str`
+ *
+ * NULL can be given instead of a pointer to all-false options.
+ */
+typedef struct SvParseOptions {
+  /**
+   * Skip `include directives instead of reading the included files
+   */
+  bool ignore_include;
+  /**
+   * Accept source text which ends in the middle of a description
+   */
+  bool allow_incomplete;
+  /**
+   * Keep usages of undefined macros in the text with a warning instead of
+   * failing
+   */
+  bool allow_undefined;
+  /**
+   * Remove the text between `translate_off` and `translate_on` pragma
+   * comments before parsing
+   */
+  bool strip_translate_off;
+} SvParseOptions;
+
+typedef struct SvDiagnostic {
+  enum SvSeverity severity;
+  const char *message;
+  /**
+   * Source file path, or NULL if the location is unknown
+   */
+  const char *path;
+  /**
+   * Byte offset in the source file
+   */
+  size_t offset;
+} SvDiagnostic;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parse a file.
+ *
+ * `defines` are `NAME` or `NAME=TEXT`. Returns NULL on failure, and the
+ * reason can be read through `sv_diagnostic_count` and `sv_diagnostic`.
+ */
+struct SvTree *sv_parse_file(const char *path,
+                             const char *const *defines,
+                             size_t defines_len,
+                             const char *const *include_paths,
+                             size_t include_paths_len,
+                             const struct SvParseOptions *options);
+
+/**
+ * Parse a string.
+ *
+ * `path` is used to resolve relative includes and to report origins.
+ */
+struct SvTree *sv_parse_str(const char *text,
+                            const char *path,
+                            const char *const *defines,
+                            size_t defines_len,
+                            const char *const *include_paths,
+                            size_t include_paths_len,
+                            const struct SvParseOptions *options);
+
+/**
+ * Free a tree returned by `sv_parse_file` or `sv_parse_str`.
+ */
+void sv_tree_free(struct SvTree *tree);
+
+/**
+ * Number of diagnostics reported by the last parse on the calling thread
+ */
+size_t sv_diagnostic_count(void);
+
+/**
+ * Get a diagnostic reported by the last parse on the calling thread.
+ *
+ * The returned pointer is valid until the next parse on the thread.
+ */
+const struct SvDiagnostic *sv_diagnostic(size_t index);
+
+size_t sv_tree_node_count(const struct SvTree *tree);
+
+/**
+ * Kind of the node, which is the name of the `RefNode` variant
+ */
+const char *sv_node_kind(const struct SvTree *tree, size_t node);
+
+size_t sv_node_parent(const struct SvTree *tree, size_t node);
+
+size_t sv_node_child_count(const struct SvTree *tree, size_t node);
+
+size_t sv_node_child(const struct SvTree *tree, size_t node, size_t index);
+
+/**
+ * Find the first node of `kind` at or after `node` in pre-order.
+ *
+ * All nodes of a kind can be visited by calling this again from the found
+ * node plus one until `SV_NODE_NONE` is returned.
+ */
+size_t sv_node_find(const struct SvTree *tree, size_t node, const char *kind);
+
+/**
+ * Source text of the node, same as `SyntaxTree::get_str`.
+ *
+ * The text is not NUL-terminated, and its length is written to `len`.
+ * If `trim` is true, trailing white space is excluded as `get_str_trim`.
+ * Returns NULL if the node has no token.
+ */
+const char *sv_node_text(const struct SvTree *tree, size_t node, bool trim, size_t *len);
+
+/**
+ * Source file path and byte offset of the first token of the node.
+ *
+ * Returns false if the node has no token or its origin is unknown.
+ */
+bool sv_node_origin(const struct SvTree *tree, size_t node, const char **path, size_t *offset);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* SV_PARSER_H */
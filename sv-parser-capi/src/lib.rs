@@ -0,0 +1,437 @@
+//! C API of sv-parser
+//!
+//! Nodes of a parsed tree are identified by their index in pre-order, so the
+//! root node is `0` and `sv_tree_node_count` nodes are numbered contiguously.
+//! Strings returned by the API are owned by the tree or by the diagnostics of
+//! the calling thread, and must not be freed by the caller.
+
+#![recursion_limit = "256"]
+#![allow(clippy::missing_safety_doc)]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::slice;
+use sv_parser::{
+    defines_from, parse_sv, parse_sv_str, Defines, Error, NodeEntry, NodeTable, ParseOptions,
+    SyntaxTree, Warning, PARSER_STACK_SIZE,
+};
+
+/// Returned by node functions if there is no such node
+pub const SV_NODE_NONE: usize = usize::MAX;
+
+/// A syntax tree and the node table built from it
+pub struct SvTree {
+    tree: SyntaxTree,
+    table: NodeTable,
+    kinds: Vec<CString>,
+    paths: RefCell<HashMap<PathBuf, CString>>,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SvSeverity {
+    Error = 0,
+    Warning = 1,
+}
+
+/// Options of `sv_parse_file` and `sv_parse_str`
+///
+/// NULL can be given instead of a pointer to all-false options.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SvParseOptions {
+    /// Skip `include directives instead of reading the included files
+    pub ignore_include: bool,
+    /// Accept source text which ends in the middle of a description
+    pub allow_incomplete: bool,
+    /// Keep usages of undefined macros in the text with a warning instead of
+    /// failing
+    pub allow_undefined: bool,
+    /// Remove the text between `translate_off` and `translate_on` pragma
+    /// comments before parsing
+    pub strip_translate_off: bool,
+}
+
+#[repr(C)]
+pub struct SvDiagnostic {
+    pub severity: SvSeverity,
+    pub message: *const c_char,
+    /// Source file path, or NULL if the location is unknown
+    pub path: *const c_char,
+    /// Byte offset in the source file
+    pub offset: usize,
+}
+
+struct Diagnostic {
+    severity: SvSeverity,
+    message: CString,
+    origin: Option<(CString, usize)>,
+}
+
+thread_local!(
+    static DIAGNOSTICS: RefCell<(Vec<Diagnostic>, Vec<SvDiagnostic>)> = const {
+        RefCell::new((Vec::new(), Vec::new()))
+    }
+);
+
+// -----------------------------------------------------------------------------
+
+impl SvTree {
+    fn new(tree: SyntaxTree) -> Self {
+        let table = NodeTable::new(&tree);
+        let kinds = table.kinds.iter().map(|x| c_string(x)).collect();
+        SvTree {
+            tree,
+            table,
+            kinds,
+            paths: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn node(&self, id: usize) -> Option<&NodeEntry> {
+        self.table.nodes.get(id)
+    }
+
+    fn path(&self, path: &Path) -> *const c_char {
+        let mut paths = self.paths.borrow_mut();
+        let path = paths
+            .entry(path.to_path_buf())
+            .or_insert_with(|| c_string(&path.to_string_lossy()));
+        path.as_ptr()
+    }
+}
+
+fn c_string(s: &str) -> CString {
+    CString::new(s.replace('\0', "")).unwrap()
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        None
+    } else {
+        CStr::from_ptr(s).to_str().ok()
+    }
+}
+
+unsafe fn to_strs<'a>(s: *const *const c_char, len: usize) -> Vec<&'a str> {
+    if s.is_null() {
+        return vec![];
+    }
+    slice::from_raw_parts(s, len)
+        .iter()
+        .filter_map(|x| to_str(*x))
+        .collect()
+}
+
+unsafe fn to_parse_options(options: *const SvParseOptions) -> ParseOptions {
+    let options = options.as_ref().copied().unwrap_or_default();
+    ParseOptions {
+        ignore_include: options.ignore_include,
+        allow_incomplete: options.allow_incomplete,
+        allow_undefined: options.allow_undefined,
+        strip_translate_off: options.strip_translate_off,
+        ..ParseOptions::default()
+    }
+}
+
+// Defines are given as `NAME` or `NAME=TEXT`
+fn to_defines(defines: &[&str]) -> Defines {
+    defines_from(defines.iter().map(|x| match x.split_once('=') {
+        Some((ident, text)) => (ident, Some(text)),
+        None => (*x, None),
+    }))
+}
+
+fn set_diagnostics(diagnostics: Vec<Diagnostic>) {
+    DIAGNOSTICS.with(|x| {
+        let mut x = x.borrow_mut();
+        x.1 = diagnostics
+            .iter()
+            .map(|x| SvDiagnostic {
+                severity: x.severity,
+                message: x.message.as_ptr(),
+                path: x.origin.as_ref().map_or(ptr::null(), |x| x.0.as_ptr()),
+                offset: x.origin.as_ref().map_or(0, |x| x.1),
+            })
+            .collect();
+        x.0 = diagnostics;
+    });
+}
+
+fn error_diagnostic(error: &Error) -> Diagnostic {
+    let origin = match error {
+        Error::Parse(x) | Error::Preprocess(x) => x.clone(),
+        Error::File { path, .. } | Error::ReadUtf8(path) => Some((path.clone(), 0)),
+        Error::Include { source } => return error_diagnostic(source),
+        _ => None,
+    };
+    Diagnostic {
+        severity: SvSeverity::Error,
+        message: c_string(&format!("{}", error)),
+        origin: origin.map(|(path, pos)| (c_string(&path.to_string_lossy()), pos)),
+    }
+}
+
+fn warning_diagnostic(warning: &Warning) -> Diagnostic {
+    let origin = match warning {
+        Warning::DefineRedefined(_, x)
+        | Warning::DefineNotFound(_, x)
+        | Warning::DefineSimilar(_, _, x)
+        | Warning::UndefineNotFound(_, x)
+        | Warning::IncludeRepeated(_, x)
+        | Warning::TimescaleInherited(_, x)
+        | Warning::TimescaleMissing(_, x)
+        | Warning::TranslateOffUnterminated(x) => Some(x.clone()),
+        Warning::DefineUnused(_) => None,
+    };
+    Diagnostic {
+        severity: SvSeverity::Warning,
+        message: c_string(&format!("{}", warning)),
+        origin: origin.map(|(path, pos)| (c_string(&path.to_string_lossy()), pos)),
+    }
+}
+
+fn parse<F>(f: F) -> *mut SvTree
+where
+    F: FnOnce() -> Result<SyntaxTree, Error> + Send + 'static,
+{
+    let result = std::thread::Builder::new()
+        .stack_size(PARSER_STACK_SIZE)
+        .spawn(f)
+        .expect("thread spawn failure")
+        .join();
+    match result {
+        Ok(Ok(tree)) => {
+            set_diagnostics(tree.get_warnings().iter().map(warning_diagnostic).collect());
+            Box::into_raw(Box::new(SvTree::new(tree)))
+        }
+        Ok(Err(x)) => {
+            set_diagnostics(vec![error_diagnostic(&x)]);
+            ptr::null_mut()
+        }
+        Err(_) => {
+            set_diagnostics(vec![Diagnostic {
+                severity: SvSeverity::Error,
+                message: c_string("Parser panicked"),
+                origin: None,
+            }]);
+            ptr::null_mut()
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Parse a file.
+///
+/// `defines` are `NAME` or `NAME=TEXT`. Returns NULL on failure, and the
+/// reason can be read through `sv_diagnostic_count` and `sv_diagnostic`.
+#[no_mangle]
+pub unsafe extern "C" fn sv_parse_file(
+    path: *const c_char,
+    defines: *const *const c_char,
+    defines_len: usize,
+    include_paths: *const *const c_char,
+    include_paths_len: usize,
+    options: *const SvParseOptions,
+) -> *mut SvTree {
+    let path = match to_str(path) {
+        Some(x) => PathBuf::from(x),
+        None => {
+            set_diagnostics(vec![]);
+            return ptr::null_mut();
+        }
+    };
+    let defines = to_defines(&to_strs(defines, defines_len));
+    let include_paths: Vec<PathBuf> = to_strs(include_paths, include_paths_len)
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    let options = to_parse_options(options);
+    parse(move || parse_sv(&path, &defines, &include_paths, &options).map(|(x, _)| x))
+}
+
+/// Parse a string.
+///
+/// `path` is used to resolve relative includes and to report origins.
+#[no_mangle]
+pub unsafe extern "C" fn sv_parse_str(
+    text: *const c_char,
+    path: *const c_char,
+    defines: *const *const c_char,
+    defines_len: usize,
+    include_paths: *const *const c_char,
+    include_paths_len: usize,
+    options: *const SvParseOptions,
+) -> *mut SvTree {
+    let text = match to_str(text) {
+        Some(x) => String::from(x),
+        None => {
+            set_diagnostics(vec![]);
+            return ptr::null_mut();
+        }
+    };
+    let path = PathBuf::from(to_str(path).unwrap_or(""));
+    let defines = to_defines(&to_strs(defines, defines_len));
+    let include_paths: Vec<PathBuf> = to_strs(include_paths, include_paths_len)
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    let options = to_parse_options(options);
+    parse(move || parse_sv_str(&text, &path, &defines, &include_paths, &options).map(|(x, _)| x))
+}
+
+/// Free a tree returned by `sv_parse_file` or `sv_parse_str`.
+#[no_mangle]
+pub unsafe extern "C" fn sv_tree_free(tree: *mut SvTree) {
+    if !tree.is_null() {
+        drop(Box::from_raw(tree));
+    }
+}
+
+/// Number of diagnostics reported by the last parse on the calling thread
+#[no_mangle]
+pub extern "C" fn sv_diagnostic_count() -> usize {
+    DIAGNOSTICS.with(|x| x.borrow().1.len())
+}
+
+/// Get a diagnostic reported by the last parse on the calling thread.
+///
+/// The returned pointer is valid until the next parse on the thread.
+#[no_mangle]
+pub extern "C" fn sv_diagnostic(index: usize) -> *const SvDiagnostic {
+    DIAGNOSTICS.with(|x| match x.borrow().1.get(index) {
+        Some(x) => x as *const SvDiagnostic,
+        None => ptr::null(),
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sv_tree_node_count(tree: *const SvTree) -> usize {
+    tree.as_ref().map_or(0, |x| x.table.nodes.len())
+}
+
+/// Kind of the node, which is the name of the `RefNode` variant
+#[no_mangle]
+pub unsafe extern "C" fn sv_node_kind(tree: *const SvTree, node: usize) -> *const c_char {
+    match tree.as_ref().and_then(|x| x.node(node).map(|y| (x, y))) {
+        Some((tree, node)) => tree.kinds[node.kind].as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sv_node_parent(tree: *const SvTree, node: usize) -> usize {
+    match tree.as_ref().and_then(|x| x.node(node)) {
+        Some(node) => node.parent.unwrap_or(SV_NODE_NONE),
+        None => SV_NODE_NONE,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sv_node_child_count(tree: *const SvTree, node: usize) -> usize {
+    match tree.as_ref().and_then(|x| x.node(node)) {
+        Some(node) => node.children.len(),
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sv_node_child(tree: *const SvTree, node: usize, index: usize) -> usize {
+    match tree.as_ref().and_then(|x| x.node(node)) {
+        Some(node) => node.children.get(index).copied().unwrap_or(SV_NODE_NONE),
+        None => SV_NODE_NONE,
+    }
+}
+
+/// Find the first node of `kind` at or after `node` in pre-order.
+///
+/// All nodes of a kind can be visited by calling this again from the found
+/// node plus one until `SV_NODE_NONE` is returned.
+#[no_mangle]
+pub unsafe extern "C" fn sv_node_find(
+    tree: *const SvTree,
+    node: usize,
+    kind: *const c_char,
+) -> usize {
+    let (tree, kind) = match (tree.as_ref(), to_str(kind)) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return SV_NODE_NONE,
+    };
+    let kind = match tree
+        .kinds
+        .iter()
+        .position(|x| x.as_bytes() == kind.as_bytes())
+    {
+        Some(x) => x,
+        None => return SV_NODE_NONE,
+    };
+    for (i, x) in tree.table.nodes.iter().enumerate().skip(node) {
+        if x.kind == kind {
+            return i;
+        }
+    }
+    SV_NODE_NONE
+}
+
+/// Source text of the node, same as `SyntaxTree::get_str`.
+///
+/// The text is not NUL-terminated, and its length is written to `len`.
+/// If `trim` is true, trailing white space is excluded as `get_str_trim`.
+/// Returns NULL if the node has no token.
+#[no_mangle]
+pub unsafe extern "C" fn sv_node_text(
+    tree: *const SvTree,
+    node: usize,
+    trim: bool,
+    len: *mut usize,
+) -> *const c_char {
+    let (tree, node) = match tree.as_ref().and_then(|x| x.node(node).map(|y| (x, y))) {
+        Some(x) => x,
+        None => return ptr::null(),
+    };
+    let locate = if trim { node.locate_trim } else { node.locate };
+    match locate {
+        Some(x) => {
+            let text = tree.tree.get_str(&x).unwrap();
+            if !len.is_null() {
+                *len = text.len();
+            }
+            text.as_ptr() as *const c_char
+        }
+        None => ptr::null(),
+    }
+}
+
+/// Source file path and byte offset of the first token of the node.
+///
+/// Returns false if the node has no token or its origin is unknown.
+#[no_mangle]
+pub unsafe extern "C" fn sv_node_origin(
+    tree: *const SvTree,
+    node: usize,
+    path: *mut *const c_char,
+    offset: *mut usize,
+) -> bool {
+    let (tree, node) = match tree.as_ref().and_then(|x| x.node(node).map(|y| (x, y))) {
+        Some(x) => x,
+        None => return false,
+    };
+    let origin = node.locate.and_then(|x| tree.tree.get_origin(&x));
+    match origin {
+        Some((origin_path, origin_offset)) => {
+            if !path.is_null() {
+                *path = tree.path(origin_path);
+            }
+            if !offset.is_null() {
+                *offset = origin_offset;
+            }
+            true
+        }
+        None => false,
+    }
+}
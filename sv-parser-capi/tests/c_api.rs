@@ -0,0 +1,34 @@
+// Build and run tests/test.c against the static library.
+#![cfg(unix)]
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn test_c_program() {
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // The library is built next to the test binary in target/<profile>/deps
+    let exe = env::current_exe().unwrap();
+    let deps = exe.parent().unwrap();
+    let program = deps.join("c_api_test");
+
+    let cc = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    let status = Command::new(cc)
+        .arg(manifest.join("tests/test.c"))
+        .arg("-I")
+        .arg(manifest.join("include"))
+        .arg(deps.join("libsv_parser_capi.a"))
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
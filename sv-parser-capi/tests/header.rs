@@ -0,0 +1,26 @@
+// Check that include/sv_parser.h is up to date with src/lib.rs.
+//
+// Regenerate the header by
+//   cbindgen --config cbindgen.toml --output include/sv_parser.h
+
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn test_header() {
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(manifest.join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(&manifest)
+        .with_config(config)
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+
+    let committed = fs::read_to_string(manifest.join("include/sv_parser.h")).unwrap();
+    assert!(
+        String::from_utf8(generated).unwrap() == committed,
+        "include/sv_parser.h is out of date; regenerate it with cbindgen"
+    );
+}
@@ -0,0 +1,90 @@
+#include <stdio.h>
+#include <string.h>
+#include "sv_parser.h"
+
+#define CHECK(x)                                                   \
+  do {                                                             \
+    if (!(x)) {                                                    \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,       \
+              __LINE__, #x);                                       \
+      return 1;                                                    \
+    }                                                              \
+  } while (0)
+
+static int text_equals(const SvTree *tree, size_t node, const char *expected) {
+  size_t len = 0;
+  const char *text = sv_node_text(tree, node, true, &len);
+  return text != NULL && len == strlen(expected) && strncmp(text, expected, len) == 0;
+}
+
+int main(void) {
+  const char *src =
+      "module A #(parameter W = `WIDTH) (input logic [W-1:0] a);\n"
+      "  B u_b (.a(a));\n"
+      "endmodule\n";
+  const char *defines[] = {"WIDTH=8"};
+
+  SvTree *tree = sv_parse_str(src, "a.sv", defines, 1, NULL, 0, NULL);
+  CHECK(tree != NULL);
+  CHECK(sv_diagnostic_count() == 0);
+  CHECK(sv_node_parent(tree, 0) == SV_NODE_NONE);
+  CHECK(strcmp(sv_node_kind(tree, 0), "SourceText") == 0);
+
+  size_t node = sv_node_find(tree, 0, "ModuleIdentifier");
+  CHECK(node != SV_NODE_NONE);
+  CHECK(text_equals(tree, node, "A"));
+
+  const char *path = NULL;
+  size_t offset = 0;
+  CHECK(sv_node_origin(tree, node, &path, &offset));
+  CHECK(strcmp(path, "a.sv") == 0);
+  CHECK(offset == 7);
+
+  node = sv_node_find(tree, node + 1, "ModuleIdentifier");
+  CHECK(node != SV_NODE_NONE);
+  CHECK(text_equals(tree, node, "B"));
+  CHECK(sv_node_find(tree, node + 1, "ModuleIdentifier") == SV_NODE_NONE);
+
+  node = sv_node_find(tree, 0, "ParamAssignment");
+  CHECK(node != SV_NODE_NONE);
+  CHECK(text_equals(tree, node, "W = 8"));
+  CHECK(sv_node_child_count(tree, node) > 0);
+  size_t child = sv_node_child(tree, node, 0);
+  CHECK(sv_node_parent(tree, child) == node);
+  CHECK(sv_node_child(tree, node, sv_node_child_count(tree, node)) == SV_NODE_NONE);
+
+  sv_tree_free(tree);
+
+  tree = sv_parse_str("module A;\n  assign = b;\nendmodule\n", "b.sv", NULL, 0, NULL, 0, NULL);
+  CHECK(tree == NULL);
+  CHECK(sv_diagnostic_count() == 1);
+  const SvDiagnostic *diagnostic = sv_diagnostic(0);
+  CHECK(diagnostic->severity == SV_SEVERITY_ERROR);
+  CHECK(diagnostic->path != NULL && strcmp(diagnostic->path, "b.sv") == 0);
+  CHECK(strncmp(diagnostic->message, "Parse error", 11) == 0);
+  CHECK(sv_diagnostic(1) == NULL);
+
+  const char *undefined = "module A;\n  assign a = `UNDEFINED;\nendmodule\n";
+  CHECK(sv_parse_str(undefined, "c.sv", NULL, 0, NULL, 0, NULL) == NULL);
+  SvParseOptions options = {0};
+  options.allow_undefined = true;
+  tree = sv_parse_str(undefined, "c.sv", NULL, 0, NULL, 0, &options);
+  CHECK(tree != NULL);
+  CHECK(sv_diagnostic_count() == 1);
+  CHECK(sv_diagnostic(0)->severity == SV_SEVERITY_WARNING);
+  CHECK(sv_node_find(tree, 0, "TextMacroUsage") != SV_NODE_NONE);
+  sv_tree_free(tree);
+
+  const char *translate_off =
+      "module A;\n  // synopsys translate_off\n  initial $display(\"a\");\n"
+      "  // synopsys translate_on\nendmodule\n";
+  options.allow_undefined = false;
+  options.strip_translate_off = true;
+  tree = sv_parse_str(translate_off, "d.sv", NULL, 0, NULL, 0, &options);
+  CHECK(tree != NULL);
+  CHECK(sv_node_find(tree, 0, "SystemTfCall") == SV_NODE_NONE);
+  sv_tree_free(tree);
+
+  printf("ok\n");
+  return 0;
+}
@@ -0,0 +1,73 @@
+use crate::input::Input;
+use std::fs;
+use sv_parser::{
+    parse_sv_pp_diagnostic, to_json_lines, to_sarif, Defines, Diagnostic, Location, Warning,
+};
+
+pub fn run(input: &Input, format: &str) -> Result<bool, String> {
+    let mut diagnostics = Vec::new();
+    let mut success = true;
+    // Defines given by the command line or file lists are reported as unused
+    // only if no file references them.
+    let mut unused: Vec<&String> = input.defines.keys().collect();
+    unused.sort();
+    let options = input.parse_options();
+    input.preprocess(false, |path, ret| {
+        let ret = match ret {
+            Ok(text) => parse_sv_pp_diagnostic(text, Defines::new(), &options),
+            Err(x) => {
+                // Nothing holds the text of a file which failed to be
+                // preprocessed.
+                let mut diagnostic = Diagnostic::from(&x);
+                if let Some(ref mut location) = diagnostic.location {
+                    if let Ok(source) = fs::read_to_string(&location.path) {
+                        location.resolve(&source);
+                    }
+                }
+                Err(Box::new(diagnostic))
+            }
+        };
+        match ret {
+            Ok((tree, _)) => {
+                for warning in tree.get_warnings() {
+                    if let Warning::DefineUnused(_) = warning {
+                        continue;
+                    }
+                    let mut diagnostic = Diagnostic::from(warning);
+                    diagnostic.resolve(|x| tree.get_source(x));
+                    if diagnostic.location.is_none() {
+                        diagnostic.location = Some(Location::new(path, None));
+                    }
+                    diagnostics.push(diagnostic);
+                }
+                unused.retain(|x| {
+                    tree.get_warnings()
+                        .contains(&Warning::DefineUnused(String::from(x.as_str())))
+                });
+            }
+            Err(mut diagnostic) => {
+                success = false;
+                if diagnostic.location.is_none() {
+                    diagnostic.location = Some(Location::new(path, None));
+                }
+                diagnostics.push(*diagnostic);
+            }
+        }
+    });
+    if success {
+        for x in unused {
+            diagnostics.push(Diagnostic::from(&Warning::DefineUnused(x.clone())));
+        }
+    }
+
+    match format {
+        "jsonl" => print!("{}", to_json_lines(&diagnostics)),
+        "sarif" => print!("{}", to_sarif(&diagnostics)),
+        _ => {
+            for x in &diagnostics {
+                println!("{}", x);
+            }
+        }
+    }
+    Ok(success)
+}
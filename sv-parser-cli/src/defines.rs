@@ -0,0 +1,34 @@
+use crate::input::Input;
+
+pub fn run(input: &Input) -> Result<bool, String> {
+    let mut success = true;
+    let defines = input.parse(|path, ret| {
+        if let Err(x) = ret {
+            success = false;
+            eprintln!("{}: error: {}", path.display(), x);
+        }
+    });
+
+    let mut defines: Vec<_> = defines.into_values().flatten().collect();
+    defines.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    for define in defines {
+        let mut line = format!("`define {}", define.identifier);
+        if !define.arguments.is_empty() {
+            let args: Vec<_> = define
+                .arguments
+                .iter()
+                .map(|(name, default)| match default {
+                    Some(x) => format!("{}={}", name, x),
+                    None => name.clone(),
+                })
+                .collect();
+            line.push_str(&format!("({})", args.join(", ")));
+        }
+        if let Some(text) = define.text {
+            line.push(' ');
+            line.push_str(&text.text);
+        }
+        println!("{}", line);
+    }
+    Ok(success)
+}
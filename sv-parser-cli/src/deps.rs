@@ -0,0 +1,80 @@
+use crate::input::Input;
+use crate::modules::design_element;
+use sv_parser::{unwrap_locate, unwrap_node, NodeEvent, RefNode, SyntaxTree};
+
+pub fn run(input: &Input, format: &str) -> Result<bool, String> {
+    let mut deps: Vec<(String, Vec<String>)> = Vec::new();
+    let mut success = true;
+    input.parse(|path, ret| match ret {
+        Ok(tree) => deps.extend(get_deps(&tree)),
+        Err(x) => {
+            success = false;
+            eprintln!("{}: error: {}", path.display(), x);
+        }
+    });
+
+    if format == "dot" {
+        println!("digraph deps {{");
+        for (name, deps) in &deps {
+            println!("    \"{}\";", name);
+            for dep in deps {
+                println!("    \"{}\" -> \"{}\";", name, dep);
+            }
+        }
+        println!("}}");
+    } else {
+        for (name, deps) in &deps {
+            for dep in deps {
+                println!("{} -> {}", name, dep);
+            }
+        }
+    }
+    Ok(success)
+}
+
+/// Instantiated modules, interfaces, programs, checkers, primitives and
+/// referenced packages of each design element
+fn get_deps(tree: &SyntaxTree) -> Vec<(String, Vec<String>)> {
+    let mut ret: Vec<(String, Vec<String>)> = Vec::new();
+    // Indices of open design elements in `ret`
+    let mut open: Vec<usize> = Vec::new();
+    for event in tree.into_iter().event() {
+        match event {
+            NodeEvent::Enter(x) => {
+                if let Some(element) = design_element(tree, &x) {
+                    open.push(ret.len());
+                    ret.push((element.name, Vec::new()));
+                } else if let Some(i) = open.last() {
+                    if let Some(dep) = dependency(tree, &x) {
+                        let deps = &mut ret[*i].1;
+                        if !deps.contains(&dep) {
+                            deps.push(dep);
+                        }
+                    }
+                }
+            }
+            NodeEvent::Leave(x) => {
+                if design_element(tree, &x).is_some() {
+                    open.pop();
+                }
+            }
+        }
+    }
+    ret
+}
+
+fn dependency(tree: &SyntaxTree, node: &RefNode) -> Option<String> {
+    let id = match node {
+        RefNode::ModuleInstantiation(x) => RefNode::from(&x.nodes.0),
+        RefNode::InterfaceInstantiation(x) => RefNode::from(&x.nodes.0),
+        RefNode::ProgramInstantiation(x) => RefNode::from(&x.nodes.0),
+        RefNode::CheckerInstantiation(x) => RefNode::from(&x.nodes.0.nodes.1),
+        RefNode::UdpInstantiation(x) => RefNode::from(&x.nodes.0),
+        RefNode::PackageImportItem(_) | RefNode::PackageScopePackage(_) => {
+            unwrap_node!(node.clone(), PackageIdentifier)?
+        }
+        _ => return None,
+    };
+    let locate = unwrap_locate!(id)?;
+    tree.get_str(locate).map(String::from)
+}
@@ -0,0 +1,146 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Contents of a `.f` file list
+///
+/// The following entries are recognized, separated by white space.
+/// `//` and `#` start comments, and `$VAR` or `${VAR}` are replaced by
+/// environment variables.
+///
+/// * source files
+/// * `+incdir+DIR[+DIR...]`, `-I DIR` and `-IDIR`
+/// * `+define+NAME[=TEXT][+NAME...]`, `-D NAME[=TEXT]` and `-DNAME[=TEXT]`
+/// * `-f FILE`: a nested file list whose paths are relative to the current directory
+/// * `-F FILE`: a nested file list whose paths are relative to the file list itself
+///
+/// Other options like `-v FILE`, `-y DIR` and `+libext+` are ignored.
+#[derive(Debug, Default, PartialEq)]
+pub struct Filelist {
+    pub files: Vec<PathBuf>,
+    pub includes: Vec<PathBuf>,
+    pub defines: Vec<String>,
+}
+
+impl Filelist {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let mut ret = Filelist::default();
+        ret.load_inner(path, None, 0)?;
+        Ok(ret)
+    }
+
+    fn load_inner(&mut self, path: &Path, base: Option<&Path>, depth: usize) -> Result<(), String> {
+        if depth > 64 {
+            return Err(format!("file list nested too deeply: {:?}", path));
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|x| format!("failed to read file list {:?}: {}", path, x))?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        let mut words = words(&text).into_iter();
+        while let Some(word) = words.next() {
+            let resolve = |x: &str| match base {
+                Some(base) => base.join(x),
+                None => PathBuf::from(x),
+            };
+            if let Some(x) = word.strip_prefix("+incdir+") {
+                self.includes
+                    .extend(x.split('+').filter(|x| !x.is_empty()).map(resolve));
+            } else if let Some(x) = word.strip_prefix("+define+") {
+                self.defines
+                    .extend(x.split('+').filter(|x| !x.is_empty()).map(String::from));
+            } else if ["-I", "-D", "-f", "-F", "-v", "-y"].contains(&word.as_str()) {
+                let arg = words
+                    .next()
+                    .ok_or_else(|| format!("{} requires an argument in {:?}", word, path))?;
+                match word.as_str() {
+                    "-I" => self.includes.push(resolve(&arg)),
+                    "-D" => self.defines.push(arg),
+                    "-f" => self.load_inner(&resolve(&arg), base, depth + 1)?,
+                    "-F" => {
+                        let nested = dir.join(&arg);
+                        let nested_dir = nested.parent().unwrap_or_else(|| Path::new(""));
+                        self.load_inner(&nested, Some(nested_dir), depth + 1)?
+                    }
+                    // Library files and directories of other tools
+                    _ => (),
+                }
+            } else if let Some(x) = word.strip_prefix("-I") {
+                self.includes.push(resolve(x));
+            } else if let Some(x) = word.strip_prefix("-D") {
+                self.defines.push(String::from(x));
+            } else if !word.starts_with('-') && !word.starts_with('+') {
+                self.files.push(resolve(&word));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Split text into words without comments, and expand environment variables
+fn words(text: &str) -> Vec<String> {
+    let mut ret = Vec::new();
+    for line in text.lines() {
+        let line = match line.find("//") {
+            Some(x) => &line[..x],
+            None => line,
+        };
+        let line = match line.find('#') {
+            Some(x) => &line[..x],
+            None => line,
+        };
+        ret.extend(line.split_whitespace().map(expand_env));
+    }
+    ret
+}
+
+fn expand_env(word: &str) -> String {
+    let mut ret = String::new();
+    let mut rest = word;
+    while let Some(pos) = rest.find('$') {
+        ret.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+        let (name, next) = if let Some(x) = rest.strip_prefix('{') {
+            match x.find('}') {
+                Some(end) => (&x[..end], &x[end + 1..]),
+                None => (x, ""),
+            }
+        } else {
+            let end = rest
+                .find(|x: char| !x.is_ascii_alphanumeric() && x != '_')
+                .unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+        ret.push_str(&env::var(name).unwrap_or_default());
+        rest = next;
+    }
+    ret.push_str(rest);
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("testcases/filelist");
+        env::set_var("SV_PARSER_FILELIST_TEST", "rtl");
+
+        let filelist = Filelist::load(&dir.join("top.f")).unwrap();
+        assert_eq!(
+            filelist.files,
+            vec![PathBuf::from("rtl/a.sv"), dir.join("sub").join("b.sv")]
+        );
+        assert_eq!(
+            filelist.includes,
+            vec![
+                PathBuf::from("inc1"),
+                PathBuf::from("inc2"),
+                PathBuf::from("inc3"),
+                dir.join("sub").join("inc4"),
+            ]
+        );
+        assert_eq!(filelist.defines, vec!["A", "B=1", "C"]);
+    }
+}
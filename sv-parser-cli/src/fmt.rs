@@ -0,0 +1,39 @@
+use crate::input::Input;
+use std::fs;
+use std::path::Path;
+use sv_parser_fmt::{format, Config};
+
+/// Configuration file looked up in the current directory
+const CONFIG: &str = "sv-parser-fmt.toml";
+
+pub fn run(input: &Input, check: bool, config: Option<&Path>) -> Result<bool, String> {
+    let config = match config {
+        Some(x) => Config::load(x)?,
+        None if Path::new(CONFIG).exists() => Config::load(CONFIG)?,
+        None => Config::default(),
+    };
+
+    let mut success = true;
+    for path in &input.files {
+        let formatted = match format(path, &input.defines, &config) {
+            Ok(x) => x,
+            Err(x) => {
+                success = false;
+                eprintln!("{}: error: {}", path.display(), x);
+                continue;
+            }
+        };
+        let original =
+            fs::read_to_string(path).map_err(|x| format!("{}: {}", path.display(), x))?;
+        if formatted == original {
+            continue;
+        }
+        if check {
+            success = false;
+            println!("{}", path.display());
+        } else {
+            fs::write(path, formatted).map_err(|x| format!("{}: {}", path.display(), x))?;
+        }
+    }
+    Ok(success)
+}
@@ -0,0 +1,152 @@
+use crate::filelist::Filelist;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+use sv_parser::{
+    defines_from, parse_sv, preprocess, Defines, Error, ParseOptions, PreprocessOptions,
+    PreprocessedText, SyntaxTree,
+};
+
+#[derive(StructOpt)]
+pub struct InputOpt {
+    /// Source files
+    pub files: Vec<PathBuf>,
+
+    /// File list, which can contain source files, +incdir+, +define+ and nested -f
+    #[structopt(short = "f", long = "filelist", multiple = true, number_of_values = 1)]
+    pub filelists: Vec<PathBuf>,
+
+    /// Include path
+    #[structopt(short = "I", long = "include", multiple = true, number_of_values = 1)]
+    pub includes: Vec<PathBuf>,
+
+    /// Define, e.g. `-D NAME` or `-D NAME=TEXT`
+    #[structopt(short = "D", long = "define", multiple = true, number_of_values = 1)]
+    pub defines: Vec<String>,
+
+    /// Allow incomplete source code
+    #[structopt(long = "incomplete")]
+    pub incomplete: bool,
+
+    /// Ignore `include directives
+    #[structopt(long = "ignore-include")]
+    pub ignore_include: bool,
+
+    /// Keep undefined macros as text with a warning instead of an error
+    #[structopt(long = "allow-undefined")]
+    pub allow_undefined: bool,
+
+    /// Remove regions between translate_off and translate_on pragma comments
+    #[structopt(long = "strip-translate-off")]
+    pub strip_translate_off: bool,
+}
+
+/// Source files and options resolved from command line and file lists
+pub struct Input {
+    pub files: Vec<PathBuf>,
+    pub includes: Vec<PathBuf>,
+    pub defines: Defines,
+    pub incomplete: bool,
+    pub ignore_include: bool,
+    pub allow_undefined: bool,
+    pub strip_translate_off: bool,
+}
+
+impl InputOpt {
+    pub fn resolve(&self) -> Result<Input, String> {
+        let mut files = Vec::new();
+        let mut includes = Vec::new();
+        let mut defines = Vec::new();
+        for path in &self.filelists {
+            let filelist = Filelist::load(path)?;
+            files.extend(filelist.files);
+            includes.extend(filelist.includes);
+            defines.extend(filelist.defines);
+        }
+        files.extend(self.files.iter().cloned());
+        includes.extend(self.includes.iter().cloned());
+        defines.extend(self.defines.iter().cloned());
+
+        if files.is_empty() {
+            return Err(String::from("no source file is given"));
+        }
+
+        Ok(Input {
+            files,
+            includes,
+            defines: to_defines(&defines),
+            incomplete: self.incomplete,
+            ignore_include: self.ignore_include,
+            allow_undefined: self.allow_undefined,
+            strip_translate_off: self.strip_translate_off,
+        })
+    }
+}
+
+// Defines are given as `NAME` or `NAME=TEXT`, where TEXT may be quoted
+fn to_defines(defines: &[String]) -> Defines {
+    defines_from(defines.iter().map(|x| match x.split_once('=') {
+        Some((ident, text)) => {
+            let text = enquote::unescape(text, None).unwrap_or_else(|_| String::from(text));
+            (ident, Some(text))
+        }
+        None => (x.as_str(), None),
+    }))
+}
+
+impl Input {
+    pub fn parse_options(&self) -> ParseOptions {
+        ParseOptions {
+            ignore_include: self.ignore_include,
+            allow_incomplete: self.incomplete,
+            allow_undefined: self.allow_undefined,
+            strip_translate_off: self.strip_translate_off,
+            ..ParseOptions::default()
+        }
+    }
+
+    /// Parse files in order. Defines are carried over to the following files.
+    /// Returns the defines after the last file.
+    pub fn parse<F>(&self, mut f: F) -> Defines
+    where
+        F: FnMut(&Path, Result<SyntaxTree, Error>),
+    {
+        let mut defines = self.defines.clone();
+        let options = self.parse_options();
+        for path in &self.files {
+            let ret = parse_sv(path, &defines, &self.includes, &options);
+            match ret {
+                Ok((tree, new_defines)) => {
+                    defines = new_defines;
+                    f(path, Ok(tree));
+                }
+                Err(x) => f(path, Err(x)),
+            }
+        }
+        defines
+    }
+
+    /// Preprocess files in order like `parse`.
+    pub fn preprocess<F>(&self, strip_comments: bool, mut f: F) -> Defines
+    where
+        F: FnMut(&Path, Result<PreprocessedText, Error>),
+    {
+        let mut defines = self.defines.clone();
+        let options = PreprocessOptions {
+            strip_comments,
+            ignore_include: self.ignore_include,
+            allow_undefined: self.allow_undefined,
+            strip_translate_off: self.strip_translate_off,
+        };
+        for path in &self.files {
+            let ret = preprocess(path, &defines, &self.includes, &options);
+            match ret {
+                Ok((text, new_defines)) => {
+                    defines = new_defines;
+                    f(path, Ok(text));
+                }
+                Err(x) => f(path, Err(x)),
+            }
+        }
+        defines
+    }
+}
@@ -0,0 +1,38 @@
+use crate::input::Input;
+
+/// Print an IP-XACT component of the module with all files as its file set
+pub fn run(
+    input: &Input,
+    module: &str,
+    vendor: &str,
+    library: &str,
+    version: &str,
+) -> Result<bool, String> {
+    let mut success = true;
+    let mut summary = None;
+    let mut includes = Vec::new();
+    input.parse(|path, ret| match ret {
+        Ok(tree) => {
+            for x in tree.get_includes() {
+                if !includes.contains(x) {
+                    includes.push(x.clone());
+                }
+            }
+            for x in tree.get_design_unit_summaries() {
+                if x.kind == "module" && x.name == module {
+                    summary = Some(x);
+                }
+            }
+        }
+        Err(x) => {
+            success = false;
+            eprintln!("{}: error: {}", path.display(), x);
+        }
+    });
+    let summary = summary.ok_or_else(|| format!("module not found: {}", module))?;
+    print!(
+        "{}",
+        summary.to_ipxact(vendor, library, version, &input.files, &includes)
+    );
+    Ok(success)
+}
@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Converter from byte offsets of source files to line and column numbers
+#[derive(Default)]
+pub struct Locations {
+    // Byte offsets of line heads of each file
+    line_heads: HashMap<PathBuf, Vec<usize>>,
+}
+
+impl Locations {
+    /// Get 1-origin line and column numbers of `offset` in the file
+    pub fn get(&mut self, path: &Path, offset: usize) -> Option<(usize, usize)> {
+        if !self.line_heads.contains_key(path) {
+            let text = fs::read(path).ok()?;
+            let mut heads = vec![0];
+            for (i, x) in text.iter().enumerate() {
+                if *x == b'\n' {
+                    heads.push(i + 1);
+                }
+            }
+            self.line_heads.insert(path.to_path_buf(), heads);
+        }
+        let heads = &self.line_heads[path];
+        let line = match heads.binary_search(&offset) {
+            Ok(x) => x,
+            Err(x) => x - 1,
+        };
+        Some((line + 1, offset - heads[line] + 1))
+    }
+
+    /// Format a location as `path:line:column`
+    pub fn format(&mut self, path: &Path, offset: usize) -> String {
+        match self.get(path, offset) {
+            Some((line, column)) => format!("{}:{}:{}", path.display(), line, column),
+            None => format!("{}", path.display()),
+        }
+    }
+}
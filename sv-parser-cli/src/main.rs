@@ -0,0 +1,228 @@
+mod check;
+mod defines;
+mod deps;
+mod filelist;
+mod fmt;
+mod input;
+mod ipxact;
+mod location;
+mod modules;
+mod pickle;
+mod pp;
+mod query;
+mod stub;
+mod summary;
+mod tree;
+
+use input::InputOpt;
+use std::path::PathBuf;
+use std::process;
+use structopt::{clap::AppSettings, StructOpt};
+
+#[derive(StructOpt)]
+#[structopt(name = "sv-parser", setting = AppSettings::SubcommandRequiredElseHelp)]
+enum Opt {
+    /// Parse files and report diagnostics
+    Check {
+        #[structopt(flatten)]
+        input: InputOpt,
+
+        /// Output format
+        #[structopt(long = "format", default_value = "text", possible_values = &["text", "jsonl", "sarif"])]
+        format: String,
+    },
+
+    /// Show preprocessed text with `line directives pointing to the original files
+    Pp {
+        #[structopt(flatten)]
+        input: InputOpt,
+
+        /// Don't emit `line directives
+        #[structopt(long = "no-line")]
+        no_line: bool,
+
+        /// Strip comments
+        #[structopt(long = "strip-comments")]
+        strip_comments: bool,
+    },
+
+    /// Show syntax trees
+    Tree {
+        #[structopt(flatten)]
+        input: InputOpt,
+
+        /// Output format
+        #[structopt(long = "format", default_value = "text", possible_values = &["text", "json"])]
+        format: String,
+    },
+
+    /// List design elements
+    Modules(InputOpt),
+
+    /// Show dependencies between design elements
+    Deps {
+        #[structopt(flatten)]
+        input: InputOpt,
+
+        /// Output format
+        #[structopt(long = "format", default_value = "text", possible_values = &["text", "dot"])]
+        format: String,
+    },
+
+    /// Show defines after all files
+    Defines(InputOpt),
+
+    /// Format source files in place
+    Fmt {
+        #[structopt(flatten)]
+        input: InputOpt,
+
+        /// List files which are not formatted instead of writing them
+        #[structopt(long = "check")]
+        check: bool,
+
+        /// Configuration file [default: sv-parser-fmt.toml if it exists]
+        #[structopt(long = "config")]
+        config: Option<PathBuf>,
+    },
+
+    /// Show nodes selected by a query, e.g. `AlwaysConstruct:has(EdgeIdentifier)`
+    Query {
+        /// Query in the syntax of `sv_parser::Query`
+        query: String,
+
+        #[structopt(flatten)]
+        input: InputOpt,
+    },
+
+    /// Show parameters, ports, modports and instances of modules, interfaces and programs as JSON
+    Summary(InputOpt),
+
+    /// Show black-box stubs of modules, which have only headers and port declarations
+    Stub {
+        #[structopt(flatten)]
+        input: InputOpt,
+
+        /// Show a wrapper `<MODULE>_wrapper` instantiating the module instead
+        #[structopt(long = "wrapper")]
+        wrapper: Option<String>,
+    },
+
+    /// Show an IP-XACT (IEEE 1685-2014) component description of a module
+    Ipxact {
+        /// Module name
+        module: String,
+
+        #[structopt(flatten)]
+        input: InputOpt,
+
+        /// Vendor of the component
+        #[structopt(long = "vendor")]
+        vendor: String,
+
+        /// Library of the component
+        #[structopt(long = "library")]
+        library: String,
+
+        /// Version of the component
+        #[structopt(long = "version", default_value = "1.0")]
+        version: String,
+    },
+
+    /// Show reachable design units merged into a single file in dependency order
+    Pickle {
+        #[structopt(flatten)]
+        input: InputOpt,
+
+        /// Top design unit; all design units are shown if not given
+        #[structopt(long = "top", multiple = true, number_of_values = 1)]
+        top: Vec<String>,
+
+        /// Expand text macros
+        #[structopt(long = "expand-macros")]
+        expand_macros: bool,
+
+        /// Inline included files
+        #[structopt(long = "inline-includes")]
+        inline_includes: bool,
+
+        /// Prefix added to the names of design units
+        #[structopt(long = "prefix", default_value = "")]
+        prefix: String,
+
+        /// Suffix added to the names of design units
+        #[structopt(long = "suffix", default_value = "")]
+        suffix: String,
+
+        /// Don't emit `line directives
+        #[structopt(long = "no-line")]
+        no_line: bool,
+    },
+}
+
+fn run(opt: Opt) -> Result<bool, String> {
+    match opt {
+        Opt::Check { input, format } => check::run(&input.resolve()?, &format),
+        Opt::Pp {
+            input,
+            no_line,
+            strip_comments,
+        } => pp::run(&input.resolve()?, !no_line, strip_comments),
+        Opt::Tree { input, format } => tree::run(&input.resolve()?, &format),
+        Opt::Modules(input) => modules::run(&input.resolve()?),
+        Opt::Deps { input, format } => deps::run(&input.resolve()?, &format),
+        Opt::Defines(input) => defines::run(&input.resolve()?),
+        Opt::Fmt {
+            input,
+            check,
+            config,
+        } => fmt::run(&input.resolve()?, check, config.as_deref()),
+        Opt::Query { query, input } => query::run(&input.resolve()?, &query),
+        Opt::Summary(input) => summary::run(&input.resolve()?),
+        Opt::Stub { input, wrapper } => stub::run(&input.resolve()?, wrapper.as_deref()),
+        Opt::Ipxact {
+            module,
+            input,
+            vendor,
+            library,
+            version,
+        } => ipxact::run(&input.resolve()?, &module, &vendor, &library, &version),
+        Opt::Pickle {
+            input,
+            top,
+            expand_macros,
+            inline_includes,
+            prefix,
+            suffix,
+            no_line,
+        } => {
+            let options = sv_parser::PickleOptions {
+                expand_macros,
+                inline_includes,
+                prefix,
+                suffix,
+                line_directive: !no_line,
+            };
+            pickle::run(&input.resolve()?, &top, &options)
+        }
+    }
+}
+
+fn main() {
+    let opt = Opt::from_args();
+
+    let builder = std::thread::Builder::new().stack_size(sv_parser::PARSER_STACK_SIZE);
+
+    let child = builder
+        .spawn(move || match run(opt) {
+            Ok(true) => 0,
+            Ok(false) => 1,
+            Err(x) => {
+                eprintln!("Error: {}", x);
+                2
+            }
+        })
+        .expect("thread spawn failure");
+
+    process::exit(child.join().unwrap_or(2));
+}
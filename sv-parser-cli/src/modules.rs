@@ -0,0 +1,68 @@
+use crate::input::Input;
+use crate::location::Locations;
+use std::path::PathBuf;
+use sv_parser::{unwrap_locate, unwrap_node, RefNode, SyntaxTree};
+
+pub fn run(input: &Input) -> Result<bool, String> {
+    let mut locations = Locations::default();
+    let mut success = true;
+    input.parse(|path, ret| match ret {
+        Ok(tree) => {
+            for node in &tree {
+                if let Some(x) = design_element(&tree, &node) {
+                    let location = match x.origin {
+                        Some((path, pos)) => locations.format(&path, pos),
+                        None => format!("{}", path.display()),
+                    };
+                    println!("{} {} {}", x.kind, x.name, location);
+                }
+            }
+        }
+        Err(x) => {
+            success = false;
+            eprintln!("{}: error: {}", path.display(), x);
+        }
+    });
+    Ok(success)
+}
+
+pub struct DesignElement {
+    /// Keyword of the declaration, e.g. `module`
+    pub kind: &'static str,
+    pub name: String,
+    /// Location of the identifier
+    pub origin: Option<(PathBuf, usize)>,
+}
+
+pub fn design_element(tree: &SyntaxTree, node: &RefNode) -> Option<DesignElement> {
+    let kind = match node {
+        RefNode::ModuleDeclaration(_) => "module",
+        RefNode::InterfaceDeclaration(_) => "interface",
+        RefNode::ProgramDeclaration(_) => "program",
+        RefNode::PackageDeclaration(_) => "package",
+        RefNode::UdpDeclaration(_) => "primitive",
+        RefNode::ConfigDeclaration(_) => "config",
+        RefNode::CheckerDeclaration(_) => "checker",
+        _ => return None,
+    };
+    let locate = unwrap_node!(
+        node.clone(),
+        ModuleIdentifier,
+        InterfaceIdentifier,
+        ProgramIdentifier,
+        PackageIdentifier,
+        UdpIdentifier,
+        ConfigIdentifier,
+        CheckerIdentifier
+    )
+    .and_then(|x| unwrap_locate!(x))?;
+    let name = tree.get_str(locate)?;
+    let origin = tree
+        .get_origin(locate)
+        .map(|(path, pos)| (path.clone(), pos));
+    Some(DesignElement {
+        kind,
+        name: String::from(name),
+        origin,
+    })
+}
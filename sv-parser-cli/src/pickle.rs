@@ -0,0 +1,19 @@
+use crate::input::Input;
+use sv_parser::{pickle, PickleOptions};
+
+/// Print the design units reachable from `tops` merged into a single source
+pub fn run(input: &Input, tops: &[String], options: &PickleOptions) -> Result<bool, String> {
+    let mut success = true;
+    let mut trees = Vec::new();
+    input.parse(|path, ret| match ret {
+        Ok(tree) => trees.push(tree),
+        Err(x) => {
+            success = false;
+            eprintln!("{}: error: {}", path.display(), x);
+        }
+    });
+    let tops: Vec<_> = tops.iter().map(|x| x.as_str()).collect();
+    let text = pickle(&trees, &tops, options).map_err(|x| x.to_string())?;
+    print!("{}", text);
+    Ok(success)
+}
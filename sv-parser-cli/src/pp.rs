@@ -0,0 +1,41 @@
+use crate::input::Input;
+use crate::location::Locations;
+use std::path::PathBuf;
+
+pub fn run(input: &Input, line_directive: bool, strip_comments: bool) -> Result<bool, String> {
+    let mut locations = Locations::default();
+    let mut success = true;
+    input.preprocess(strip_comments, |path, ret| match ret {
+        Ok(text) => {
+            if !line_directive {
+                print!("{}", text.text());
+                return;
+            }
+            // IEEE1800-2017 Clause 22.12
+            // `line directives are emitted where the next line doesn't
+            // follow the previous line in the original file.
+            let mut next: Option<(PathBuf, usize)> = None;
+            let mut offset = 0;
+            for line in text.text().split_inclusive('\n') {
+                let origin = text.origin(offset).and_then(|(path, pos)| {
+                    locations
+                        .get(path, pos)
+                        .map(|(line, _)| (path.clone(), line))
+                });
+                if let Some((ref path, line)) = origin {
+                    if next.as_ref() != Some(&(path.clone(), line)) {
+                        println!("`line {} \"{}\" 0", line, path.display());
+                    }
+                }
+                print!("{}", line);
+                next = origin.map(|(path, line)| (path, line + 1));
+                offset += line.len();
+            }
+        }
+        Err(x) => {
+            success = false;
+            eprintln!("{}: error: {}", path.display(), x);
+        }
+    });
+    Ok(success)
+}
@@ -0,0 +1,43 @@
+use crate::input::Input;
+use crate::location::Locations;
+use sv_parser::{unwrap_locate, Query, RefNode, SyntaxTree};
+
+/// Print nodes selected by the query, and return whether any node is selected
+pub fn run(input: &Input, query: &str) -> Result<bool, String> {
+    let query = Query::new(query).map_err(|x| x.to_string())?;
+    let mut locations = Locations::default();
+    let mut success = true;
+    let mut found = false;
+    input.parse(|path, ret| match ret {
+        Ok(tree) => {
+            for x in tree.query(&query) {
+                found = true;
+                let location = match origin(&tree, &x.node) {
+                    Some((path, pos)) => locations.format(&path, pos),
+                    None => format!("{}", path.display()),
+                };
+                println!("{}: {} {}", location, x.node, text(&tree, &x.node));
+                for (name, node) in &x.captures {
+                    println!("  @{} {}", name, text(&tree, node));
+                }
+            }
+        }
+        Err(x) => {
+            success = false;
+            eprintln!("{}: error: {}", path.display(), x);
+        }
+    });
+    Ok(success && found)
+}
+
+fn origin(tree: &SyntaxTree, node: &RefNode) -> Option<(std::path::PathBuf, usize)> {
+    let locate = unwrap_locate!(node.clone())?;
+    tree.get_origin(locate)
+        .map(|(path, pos)| (path.clone(), pos))
+}
+
+// Text of the node on a line
+fn text(tree: &SyntaxTree, node: &RefNode) -> String {
+    let text = tree.get_str_trim(vec![node.clone()]).unwrap_or("");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
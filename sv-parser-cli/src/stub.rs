@@ -0,0 +1,34 @@
+use crate::input::Input;
+use sv_parser::DirectiveState;
+
+/// Print black-box stubs of all modules, or a wrapper of `wrapper` if given
+pub fn run(input: &Input, wrapper: Option<&str>) -> Result<bool, String> {
+    let mut success = true;
+    let mut found = false;
+    // `timescale is carried over to the following files
+    let mut state = DirectiveState::default();
+    input.parse(|path, ret| match ret {
+        Ok(tree) => {
+            match wrapper {
+                Some(module) => {
+                    let name = format!("{}_wrapper", module);
+                    let instance = format!("u_{}", module);
+                    if let Some(x) = tree.generate_wrapper(module, &name, &instance, &state) {
+                        print!("{}", x);
+                        found = true;
+                    }
+                }
+                None => print!("{}", tree.generate_stubs(&state)),
+            }
+            state = tree.get_directive_states(&state).last;
+        }
+        Err(x) => {
+            success = false;
+            eprintln!("{}: error: {}", path.display(), x);
+        }
+    });
+    match wrapper {
+        Some(module) if !found => Err(format!("module not found: {}", module)),
+        _ => Ok(success),
+    }
+}
@@ -0,0 +1,17 @@
+use crate::input::Input;
+use sv_parser::summaries_to_json;
+
+/// Print summaries of modules, interfaces and programs in all files as a JSON array
+pub fn run(input: &Input) -> Result<bool, String> {
+    let mut success = true;
+    let mut summaries = Vec::new();
+    input.parse(|path, ret| match ret {
+        Ok(tree) => summaries.append(&mut tree.get_design_unit_summaries()),
+        Err(x) => {
+            success = false;
+            eprintln!("{}: error: {}", path.display(), x);
+        }
+    });
+    println!("{}", summaries_to_json(&summaries));
+    Ok(success)
+}
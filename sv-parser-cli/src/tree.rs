@@ -0,0 +1,60 @@
+use crate::input::Input;
+use serde_json::{json, Map, Value};
+use sv_parser::{NodeEvent, RefNode, SyntaxTree};
+
+pub fn run(input: &Input, format: &str) -> Result<bool, String> {
+    let mut success = true;
+    let mut trees = Vec::new();
+    input.parse(|path, ret| match ret {
+        Ok(tree) => {
+            if format == "json" {
+                trees.push(json!({
+                    "path": path.to_string_lossy(),
+                    "tree": to_json(&tree),
+                }));
+            } else {
+                print!("{}", tree);
+            }
+        }
+        Err(x) => {
+            success = false;
+            eprintln!("{}: error: {}", path.display(), x);
+        }
+    });
+    if format == "json" {
+        println!("{}", Value::Array(trees));
+    }
+    Ok(success)
+}
+
+/// Convert a syntax tree to nested objects without white space.
+///
+/// Each node is `{"kind": ..., "children": [...]}`, and each token is
+/// `{"kind": "Locate", "text": ..., "line": ...}`.
+fn to_json(tree: &SyntaxTree) -> Value {
+    // The tree is converted without recursion because it can be very deep.
+    let mut stack: Vec<Vec<Value>> = vec![Vec::new()];
+    let mut skip = 0;
+    for event in tree.into_iter().event() {
+        match event {
+            NodeEvent::Enter(RefNode::WhiteSpace(_)) => skip += 1,
+            NodeEvent::Leave(RefNode::WhiteSpace(_)) => skip -= 1,
+            _ if skip > 0 => (),
+            NodeEvent::Enter(_) => stack.push(Vec::new()),
+            NodeEvent::Leave(x) => {
+                let children = stack.pop().unwrap();
+                let mut node = Map::new();
+                node.insert(String::from("kind"), Value::from(x.to_string()));
+                if let RefNode::Locate(locate) = x {
+                    let text = tree.get_str(locate).unwrap_or("");
+                    node.insert(String::from("text"), Value::from(text));
+                    node.insert(String::from("line"), Value::from(locate.line));
+                } else {
+                    node.insert(String::from("children"), Value::Array(children));
+                }
+                stack.last_mut().unwrap().push(Value::Object(node));
+            }
+        }
+    }
+    stack.pop().unwrap().pop().unwrap_or(Value::Null)
+}
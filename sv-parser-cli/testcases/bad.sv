@@ -0,0 +1,3 @@
+module bad;
+`include "bad.svh"
+endmodule
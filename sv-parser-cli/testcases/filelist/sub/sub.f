@@ -0,0 +1 @@
+b.sv -Iinc4
@@ -0,0 +1,5 @@
+// comment
++incdir+inc1+inc2 -I inc3
++define+A+B=1 -DC # comment
+${SV_PARSER_FILELIST_TEST}/a.sv -v lib.v
+-F sub/sub.f
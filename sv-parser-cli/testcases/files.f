@@ -0,0 +1,5 @@
+// test
++incdir+inc
++define+WIDTH=8
+top.sv
+sub.sv
@@ -0,0 +1,5 @@
+module fmt;
+  initial begin
+    x = f(a, b);
+  end
+endmodule
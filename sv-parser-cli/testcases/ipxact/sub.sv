@@ -0,0 +1,2 @@
+module sub #(parameter W = 8) (input [W-1:0] a, output logic b);
+endmodule
@@ -0,0 +1,4 @@
+`timescale 1ns/1ps
+module sub #(parameter W = 8) (input [W-1:0] a);
+  assign b = a;
+endmodule
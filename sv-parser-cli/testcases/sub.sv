@@ -0,0 +1,3 @@
+`define SUB
+module sub;
+endmodule
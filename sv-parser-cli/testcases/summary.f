@@ -0,0 +1,4 @@
++incdir+inc
++define+WIDTH=8
+top.sv
+summary/sub.sv
@@ -0,0 +1,6 @@
+`include "pkg.svh"
+module top;
+  import pkg::*;
+  sub u0 ();
+  sub u1 ();
+endmodule
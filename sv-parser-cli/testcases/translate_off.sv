@@ -0,0 +1,5 @@
+module translate_off;
+  // synopsys translate_off
+  initial $display("simulation only");
+  // synopsys translate_on
+endmodule
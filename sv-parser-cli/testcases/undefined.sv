@@ -0,0 +1,3 @@
+module undefined;
+  assign a = `UNDEFINED;
+endmodule
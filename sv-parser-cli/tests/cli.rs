@@ -0,0 +1,259 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn testdir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("testcases")
+}
+
+fn run(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sv-parser"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn check() {
+    let dir = testdir();
+    let ret = run(&dir, &["check", "-f", "files.f"]);
+    assert!(ret.status.success());
+    assert_eq!(stdout(&ret), "");
+
+    // WIDTH is not defined without the file list
+    let ret = run(&dir, &["check", "-I", "inc", "top.sv"]);
+    assert_eq!(ret.status.code(), Some(1));
+    assert!(stdout(&ret).contains("error:"));
+
+    let ret = run(&dir, &["check", "-I", "inc", "-D", "WIDTH=8", "top.sv"]);
+    assert!(ret.status.success());
+
+    let ret = run(&dir, &["check", "undefined.sv"]);
+    assert_eq!(ret.status.code(), Some(1));
+    let ret = run(&dir, &["check", "--allow-undefined", "undefined.sv"]);
+    assert!(ret.status.success());
+    assert_eq!(stdout(&ret), "undefined.sv:2:14: warning: Define not found: UNDEFINED\n");
+
+    let ret = run(&dir, &["check", "--format", "jsonl", "-I", "inc", "top.sv"]);
+    assert_eq!(ret.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_str(&stdout(&ret)).unwrap();
+    assert_eq!(json["severity"], "error");
+    assert_eq!(json["code"], "define-not-found");
+
+    let ret = run(&dir, &["check", "--format", "jsonl", "-I", "inc", "bad.sv"]);
+    assert_eq!(ret.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_str(&stdout(&ret)).unwrap();
+    assert_eq!(json["code"], "parse");
+    assert_eq!(json["location"]["line"], 2);
+    assert_eq!(json["related"][0]["message"], "included from here");
+    assert_eq!(json["related"][0]["location"]["path"], "bad.sv");
+    assert_eq!(json["related"][0]["location"]["line"], 2);
+
+    let ret = run(&dir, &["check", "--format", "sarif", "-I", "inc", "bad.sv"]);
+    let sarif: serde_json::Value = serde_json::from_str(&stdout(&ret)).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!(result["ruleId"], "parse");
+    assert_eq!(result["level"], "error");
+    assert_eq!(
+        result["relatedLocations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "bad.sv"
+    );
+
+    let ret = run(&dir, &["check", "-D", "UNUSED", "-f", "files.f"]);
+    assert!(ret.status.success());
+    assert_eq!(stdout(&ret), "warning: Define never referenced: UNUSED\n");
+}
+
+#[test]
+fn pp() {
+    let dir = testdir();
+    let ret = run(&dir, &["pp", "-f", "files.f"]);
+    assert!(ret.status.success());
+    let out = stdout(&ret);
+    assert!(out.starts_with("`line 1 \"inc/pkg.svh\" 0\npackage pkg;\n  localparam W = 8;\n"));
+    assert!(out.contains("`line 2 \"top.sv\" 0\nmodule top;\n"));
+
+    let ret = run(&dir, &["pp", "--no-line", "-f", "files.f"]);
+    assert!(!stdout(&ret).contains("`line"));
+
+    let ret = run(&dir, &["pp", "--no-line", "--strip-translate-off", "translate_off.sv"]);
+    assert!(ret.status.success());
+    assert_eq!(stdout(&ret), "module translate_off;\n  endmodule\n");
+}
+
+#[test]
+fn tree() {
+    let dir = testdir();
+    let ret = run(&dir, &["tree", "sub.sv"]);
+    assert!(ret.status.success());
+    assert!(stdout(&ret).contains("ModuleIdentifier\n"));
+
+    let ret = run(&dir, &["tree", "--format", "json", "sub.sv"]);
+    assert!(ret.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&ret)).unwrap();
+    assert_eq!(json[0]["path"], "sub.sv");
+    assert_eq!(json[0]["tree"]["kind"], "SourceText");
+}
+
+#[test]
+fn modules() {
+    let dir = testdir();
+    let ret = run(&dir, &["modules", "-f", "files.f"]);
+    assert!(ret.status.success());
+    assert_eq!(
+        stdout(&ret),
+        "package pkg inc/pkg.svh:1:9\nmodule top top.sv:2:8\nmodule sub sub.sv:2:8\n"
+    );
+}
+
+#[test]
+fn deps() {
+    let dir = testdir();
+    let ret = run(&dir, &["deps", "-f", "files.f"]);
+    assert!(ret.status.success());
+    assert_eq!(stdout(&ret), "top -> pkg\ntop -> sub\n");
+
+    let ret = run(&dir, &["deps", "--format", "dot", "-f", "files.f"]);
+    assert!(stdout(&ret).contains("    \"top\" -> \"sub\";\n"));
+}
+
+#[test]
+fn defines() {
+    let dir = testdir();
+    let ret = run(&dir, &["defines", "-f", "files.f"]);
+    assert!(ret.status.success());
+    let out = stdout(&ret);
+    assert!(out.contains("`define SUB\n"));
+    assert!(out.contains("`define WIDTH 8\n"));
+}
+
+#[test]
+fn fmt() {
+    let dir = testdir();
+    let ret = run(&dir, &["fmt", "--check", "fmt.sv", "fmt_formatted.sv", "sub.sv"]);
+    assert_eq!(ret.status.code(), Some(1));
+    assert_eq!(stdout(&ret), "fmt.sv\n");
+
+    let args = ["fmt", "--check", "--config", "fmt_indent4.toml", "fmt_formatted.sv"];
+    let ret = run(&dir, &args);
+    assert_eq!(ret.status.code(), Some(1));
+
+    // Formatting in place needs a writable copy
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    fs::copy(dir.join("fmt.sv"), tmp.join("fmt.sv")).unwrap();
+    let ret = run(tmp, &["fmt", "fmt.sv"]);
+    assert!(ret.status.success());
+    assert_eq!(
+        fs::read_to_string(tmp.join("fmt.sv")).unwrap(),
+        fs::read_to_string(dir.join("fmt_formatted.sv")).unwrap()
+    );
+}
+
+#[test]
+fn query() {
+    let dir = testdir();
+    let ret = run(&dir, &["query", "HierarchicalInstance@inst", "-f", "files.f"]);
+    assert!(ret.status.success());
+    assert_eq!(
+        stdout(&ret),
+        "top.sv:4:7: HierarchicalInstance u0 ()\n  @inst u0 ()\n\
+         top.sv:5:7: HierarchicalInstance u1 ()\n  @inst u1 ()\n"
+    );
+
+    let ret = run(&dir, &["query", "ModuleDeclaration > ModuleIdentifier", "sub.sv"]);
+    assert_eq!(ret.status.code(), Some(1));
+    assert_eq!(stdout(&ret), "");
+
+    let ret = run(&dir, &["query", "Modul", "sub.sv"]);
+    assert_eq!(ret.status.code(), Some(2));
+}
+
+#[test]
+fn summary() {
+    let dir = testdir();
+    let ret = run(&dir, &["summary", "-f", "summary.f"]);
+    assert!(ret.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&ret)).unwrap();
+    assert_eq!(json[0]["name"], "top");
+    assert_eq!(json[0]["location"]["line"], 2);
+    assert_eq!(json[0]["instances"][1]["module"], "sub");
+    assert_eq!(json[0]["instances"][1]["name"], "u1");
+    assert_eq!(json[1]["name"], "sub");
+    assert_eq!(json[1]["parameters"][0]["default"], "8");
+    assert_eq!(json[1]["ports"][0]["direction"], "input");
+    assert_eq!(json[1]["ports"][0]["packed_dimensions"][0], "[W-1:0]");
+    assert_eq!(json[1]["ports"][1]["data_type"], "logic");
+    assert_eq!(json[1]["ports"][1]["unpacked_dimensions"][0], "[2]");
+}
+
+#[test]
+fn ipxact() {
+    let dir = testdir();
+    let args = ["ipxact", "sub", "--vendor", "example.com", "--library", "ip", "-f", "ipxact.f"];
+    let ret = run(&dir, &args);
+    assert!(ret.status.success());
+    let xml = stdout(&ret);
+    assert!(xml.contains("<ipxact:vendor>example.com</ipxact:vendor>"));
+    assert!(xml.contains("<ipxact:version>1.0</ipxact:version>"));
+    assert!(xml.contains("<ipxact:name>W</ipxact:name>\n            <ipxact:value>8</ipxact:value>"));
+    assert!(xml.contains("<ipxact:left>7</ipxact:left>"));
+    assert!(xml.contains("<ipxact:name>top.sv</ipxact:name>"));
+    assert!(xml.contains("pkg.svh</ipxact:name>\n        <ipxact:fileType>systemVerilogSource</ipxact:fileType>\n        <ipxact:isIncludeFile>true</ipxact:isIncludeFile>"));
+
+    let args = ["ipxact", "none", "--vendor", "example.com", "--library", "ip", "-f", "files.f"];
+    let ret = run(&dir, &args);
+    assert!(!ret.status.success());
+}
+
+#[test]
+fn stub() {
+    let dir = testdir();
+    let ret = run(&dir, &["stub", "stub/sub.sv"]);
+    assert!(ret.status.success());
+    assert_eq!(
+        stdout(&ret),
+        "`timescale 1ns/1ps\nmodule sub #(\n  parameter W = 8\n) (\n  input [W - 1:0] a\n);\nendmodule\n"
+    );
+
+    let ret = run(&dir, &["stub", "--wrapper", "sub", "stub/sub.sv"]);
+    assert!(ret.status.success());
+    assert!(stdout(&ret).contains("module sub_wrapper #("));
+    assert!(stdout(&ret).contains("  sub #(\n    .W(W)\n  ) u_sub (\n    .a(a)\n  );\n"));
+
+    let ret = run(&dir, &["stub", "--wrapper", "none", "stub/sub.sv"]);
+    assert!(!ret.status.success());
+}
+
+#[test]
+fn pickle() {
+    let dir = testdir();
+    let args = ["pickle", "--top", "top", "--prefix", "p_", "--no-line", "-f", "files.f", "unused.sv"];
+    let ret = run(&dir, &args);
+    assert!(ret.status.success());
+    assert_eq!(
+        stdout(&ret),
+        "`include \"pkg.svh\"\n`define SUB\nmodule p_sub;\nendmodule\n\
+         module p_top;\n  import pkg::*;\n  p_sub u0 ();\n  p_sub u1 ();\nendmodule\n"
+    );
+
+    let args = ["pickle", "--top", "top", "--expand-macros", "--inline-includes", "-f", "files.f"];
+    let ret = run(&dir, &args);
+    assert!(ret.status.success());
+    let text = stdout(&ret);
+    assert!(text.contains("`line 1 \"inc/pkg.svh\" 0\npackage pkg;\n  localparam W = 8;\nendpackage\n"));
+    assert!(text.find("module sub;").unwrap() < text.find("module top;").unwrap());
+
+    let args = ["pickle", "--top", "top", "--inline-includes", "--no-line", "-f", "files.f"];
+    let ret = run(&dir, &args);
+    assert!(ret.status.success());
+    assert!(stdout(&ret).contains("  localparam W = `WIDTH;\n"));
+
+    let ret = run(&dir, &["pickle", "--top", "none", "-f", "files.f"]);
+    assert!(!ret.status.success());
+}
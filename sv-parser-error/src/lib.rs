@@ -45,3 +45,35 @@ pub enum Error {
     #[error("Include line can't have other items")]
     IncludeLine,
 }
+
+// -----------------------------------------------------------------------------
+
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum Warning {
+    #[error("Define redefined with different text: {0}")]
+    DefineRedefined(String, (PathBuf, usize)),
+
+    #[error("Define not found: {0}")]
+    DefineNotFound(String, (PathBuf, usize)),
+
+    #[error("Define not found: {0} (similar define: {1})")]
+    DefineSimilar(String, String, (PathBuf, usize)),
+
+    #[error("Define never referenced: {0}")]
+    DefineUnused(String),
+
+    #[error("Undefine of macro not defined: {0}")]
+    UndefineNotFound(String, (PathBuf, usize)),
+
+    #[error("File included again without include guard: {0:?}")]
+    IncludeRepeated(PathBuf, (PathBuf, usize)),
+
+    #[error("Timescale inherited from a previous file: {0}")]
+    TimescaleInherited(String, (PathBuf, usize)),
+
+    #[error("Timescale not specified while other design elements have it: {0}")]
+    TimescaleMissing(String, (PathBuf, usize)),
+
+    #[error("translate_off region not closed by translate_on")]
+    TranslateOffUnterminated((PathBuf, usize)),
+}
@@ -0,0 +1,50 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Formatter configuration, usually loaded from `sv-parser-fmt.toml`
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Number of spaces per indentation level
+    pub indent_width: usize,
+    /// Align named port connections and named parameter assignments
+    pub align: bool,
+    /// Placement of `begin` of statements and generate blocks
+    pub begin_placement: BeginPlacement,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BeginPlacement {
+    /// Keep `begin` where it is
+    Preserve,
+    /// Move `begin` at the start of a line to the end of the previous line
+    SameLine,
+    /// Move `begin` after other tokens to the next line
+    NextLine,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            indent_width: 2,
+            align: true,
+            begin_placement: BeginPlacement::Preserve,
+        }
+    }
+}
+
+impl Config {
+    /// Parse TOML text, where omitted keys take the default values
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    /// Load a TOML file
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<Self, String> {
+        let path = path.as_ref();
+        let s = fs::read_to_string(path).map_err(|x| format!("{}: {}", path.display(), x))?;
+        Config::from_toml(&s).map_err(|x| format!("{}: {}", path.display(), x))
+    }
+}
@@ -0,0 +1,511 @@
+//! Source code formatter of SystemVerilog based on the concrete syntax tree.
+//!
+//! Only whitespace is changed: lines are re-indented by the syntactic
+//! structure, spacing between tokens on a line is normalized, named port
+//! connections are aligned, and `begin` is moved if configured. Comments,
+//! compiler directives and code in inactive `ifdef branches are preserved.
+
+mod config;
+mod token;
+
+pub use config::*;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::hash::BuildHasher;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use sv_parser::{parse_sv_str, Defines, Error, ParseOptions};
+use token::{Token, Tokens};
+
+/// Format a file
+pub fn format<T: AsRef<Path>, V: BuildHasher>(
+    path: T,
+    pre_defines: &Defines<V>,
+    config: &Config,
+) -> Result<String, Error> {
+    let mut f = File::open(path.as_ref()).map_err(|x| Error::File {
+        source: x,
+        path: PathBuf::from(path.as_ref()),
+    })?;
+    let mut s = String::new();
+    if f.read_to_string(&mut s).is_err() {
+        return Err(Error::ReadUtf8(PathBuf::from(path.as_ref())));
+    }
+    format_str(&s, path, pre_defines, config)
+}
+
+/// Format source code; `path` is used to tell the source code from included
+/// files and macro expansions
+pub fn format_str<T: AsRef<Path>, V: BuildHasher>(
+    s: &str,
+    path: T,
+    pre_defines: &Defines<V>,
+    config: &Config,
+) -> Result<String, Error> {
+    let path = path.as_ref();
+    // Included files are not needed to recognize the structure because
+    // undefined macros are left as placeholders.
+    let include_paths: [PathBuf; 0] = [];
+    let options = ParseOptions {
+        ignore_include: true,
+        allow_undefined: true,
+        ..ParseOptions::default()
+    };
+    let (tree, _) = parse_sv_str(s, path, pre_defines, &include_paths, &options)?;
+
+    let lines = line_starts(s);
+    let tokens = Tokens::new(&tree, path, &lines);
+    let formatter = Formatter {
+        src: s,
+        lines: &lines,
+        tokens: &tokens,
+        config,
+    };
+    Ok(formatter.run())
+}
+
+fn line_starts(s: &str) -> Vec<usize> {
+    let mut ret = vec![0];
+    ret.extend(s.match_indices('\n').map(|(i, _)| i + 1));
+    ret
+}
+
+fn is_blank(s: &str) -> bool {
+    s.chars().all(|x| x == ' ' || x == '\t')
+}
+
+struct Formatter<'a, 'b> {
+    src: &'a str,
+    lines: &'a [usize],
+    tokens: &'a Tokens<'b>,
+    config: &'a Config,
+}
+
+impl<'a, 'b> Formatter<'a, 'b> {
+    fn run(&self) -> String {
+        let tokens = &self.tokens.tokens;
+
+        // Tokens written in the source as is, in the order of offsets
+        let mut local: Vec<(usize, usize)> = tokens
+            .iter()
+            .enumerate()
+            .filter_map(|(i, x)| x.offset.map(|y| (y, i)))
+            .collect();
+        local.sort_unstable();
+        local.dedup_by_key(|x| x.0);
+
+        // The first token produced at each offset of the source
+        let mut anchors: Vec<(usize, usize)> = Vec::new();
+        let mut anchor_map = HashMap::new();
+        for (i, x) in tokens.iter().enumerate() {
+            if let Some(anchor) = x.anchor {
+                anchor_map.entry(anchor).or_insert_with(|| {
+                    anchors.push((anchor, i));
+                    i
+                });
+            }
+        }
+        anchors.sort_unstable();
+
+        let newline = if self.src.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        // Edits moving `begin` across lines, which take precedence over the
+        // other edits
+        let mut moves: BTreeMap<usize, (usize, String)> = BTreeMap::new();
+        let mut gaps: BTreeMap<usize, (usize, String)> = BTreeMap::new();
+        for w in local.windows(2) {
+            let (a, b) = (&tokens[w[0].1], &tokens[w[1].1]);
+            let (beg, end) = (w[0].0 + a.text.len(), w[1].0);
+            if beg > end {
+                continue;
+            }
+            let gap = &self.src[beg..end];
+            if is_blank(gap) {
+                if self.config.begin_placement == BeginPlacement::NextLine
+                    && b.is_keyword("begin")
+                    && !(a.symbol && a.text == ":")
+                    && !a.is_keyword("begin")
+                    && !a.is_keyword("fork")
+                {
+                    let indent = " ".repeat(b.depth * self.config.indent_width);
+                    moves.insert(beg, (end, format!("{}{}", newline, indent)));
+                } else {
+                    gaps.insert(beg, (end, String::from(spacing(a, b, gap))));
+                }
+            } else if self.config.begin_placement == BeginPlacement::SameLine
+                && b.is_keyword("begin")
+                && gap.trim().is_empty()
+                && gap.matches('\n').count() == 1
+                && !(a.symbol && a.text == ";")
+                && !(a.keyword && a.is_closing())
+                && !a.is_keyword("begin")
+                && !a.is_keyword("fork")
+            {
+                moves.insert(beg, (end, String::from(" ")));
+            }
+        }
+
+        if self.config.align {
+            self.align(&mut gaps);
+        }
+
+        let mut edits: Vec<(usize, usize, String)> = Vec::new();
+        // Shift of indentation of each comment
+        let mut comment_shifts: HashMap<usize, isize> = HashMap::new();
+        for (n, start) in self.lines.iter().enumerate() {
+            let start = *start;
+            let mut end = self
+                .lines
+                .get(n + 1)
+                .map_or(self.src.len(), |x| x - 1)
+                .max(start);
+            if self.src[..end].ends_with('\r') && end > start {
+                end -= 1;
+            }
+            let line = &self.src[start..end];
+            let indent_len = line.len() - line.trim_start_matches(&[' ', '\t'][..]).len();
+            let p = start + indent_len;
+            if p == end {
+                if end > start {
+                    edits.push((start, end, String::new()));
+                }
+                continue;
+            }
+
+            // Lines in the middle of multi-line tokens are kept as is
+            if inside(&local, p, |i| tokens[i].text.len()) {
+                continue;
+            }
+
+            let indent = if let Some(comment) = self.comment_containing(p) {
+                if comment == p {
+                    let indent = self.comment_indent(p, &anchors);
+                    comment_shifts.insert(comment, indent as isize - indent_len as isize);
+                    Some(indent)
+                } else {
+                    comment_shifts
+                        .get(&comment)
+                        .map(|x| (indent_len as isize + x).max(0) as usize)
+                }
+            } else {
+                anchor_map.get(&p).map(|i| self.indent(&tokens[*i]))
+            };
+            if let Some(indent) = indent {
+                if indent != indent_len || line[..indent_len].contains('\t') {
+                    edits.push((start, p, " ".repeat(indent)));
+                }
+            }
+
+            let q = start + line.trim_end_matches(&[' ', '\t'][..]).len();
+            if q < end
+                && !self.src[..q].ends_with('\\')
+                && !inside(&local, end, |i| tokens[i].text.len())
+            {
+                edits.push((q, end, String::new()));
+            }
+
+            for (beg, (end, x)) in gaps.range(p..q) {
+                if &self.src[*beg..*end] != x {
+                    edits.push((*beg, *end, x.clone()));
+                }
+            }
+        }
+
+        edits.retain(|(beg, end, _)| {
+            // Insertions at the end of moved ranges are dropped too
+            let bound = if beg == end { *beg } else { *end };
+            !matches!(moves.range(..bound).next_back(), Some((_, (y, _))) if beg < y || beg == end && beg == y)
+        });
+        edits.extend(moves.into_iter().map(|(beg, (end, x))| (beg, end, x)));
+        edits.sort_by_key(|x| (x.0, x.1));
+
+        let mut ret = String::new();
+        let mut pos = 0;
+        for (beg, end, x) in edits {
+            if beg < pos {
+                continue;
+            }
+            ret.push_str(&self.src[pos..beg]);
+            ret.push_str(&x);
+            pos = end;
+        }
+        ret.push_str(&self.src[pos..]);
+        ret
+    }
+
+    fn indent(&self, token: &Token) -> usize {
+        let depth = if token.unit {
+            token.depth
+        } else {
+            token.depth + 1
+        };
+        depth * self.config.indent_width
+    }
+
+    // A comment at the start of a line is indented like the next token, or
+    // like contents of a block if the next token closes it.
+    fn comment_indent(&self, pos: usize, anchors: &[(usize, usize)]) -> usize {
+        let i = anchors.partition_point(|x| x.0 <= pos);
+        match anchors.get(i) {
+            Some((_, i)) => {
+                let token = &self.tokens.tokens[*i];
+                if token.is_closing() {
+                    (token.depth + 1) * self.config.indent_width
+                } else {
+                    self.indent(token)
+                }
+            }
+            None => 0,
+        }
+    }
+
+    fn comment_containing(&self, pos: usize) -> Option<usize> {
+        let comments = &self.tokens.comments;
+        let i = comments.partition_point(|x| x.0 <= pos);
+        if i > 0 && pos < comments[i - 1].1 {
+            Some(comments[i - 1].0)
+        } else {
+            None
+        }
+    }
+
+    // Named connections placed one per line are aligned at `(`.
+    fn align(&self, gaps: &mut BTreeMap<usize, (usize, String)>) {
+        let tokens = &self.tokens.tokens;
+        'group: for group in &self.tokens.align_groups {
+            if group.len() < 2 {
+                continue;
+            }
+            let mut items = Vec::new();
+            for (dot, id, paren) in group {
+                let (dot, id, paren) = match (tokens.get(*dot), tokens.get(*id), tokens.get(*paren))
+                {
+                    (Some(dot), Some(id), Some(paren)) => (dot, id, paren),
+                    _ => continue 'group,
+                };
+                let (dot_pos, id_pos, paren_pos) = match (dot.offset, id.offset, paren.offset) {
+                    (Some(x), Some(y), Some(z)) => (x, y, z),
+                    _ => continue 'group,
+                };
+                let line_start = self.src[..dot_pos].rfind('\n').map_or(0, |x| x + 1);
+                let id_end = id_pos + id.text.len();
+                if !is_blank(&self.src[line_start..dot_pos])
+                    || paren.text != "("
+                    || id_end > paren_pos
+                    || !is_blank(&self.src[id_end..paren_pos])
+                {
+                    continue 'group;
+                }
+                items.push((id.text.chars().count(), id_end, paren_pos));
+            }
+            let max = items.iter().map(|x| x.0).max().unwrap_or(0);
+            // `(` of the longest names are kept next to the names if any
+            let base = if items
+                .iter()
+                .any(|(len, beg, end)| *len == max && beg != end)
+            {
+                1
+            } else {
+                0
+            };
+            for (len, beg, end) in items {
+                gaps.insert(beg, (end, " ".repeat(max - len + base)));
+            }
+        }
+    }
+}
+
+// Whether `pos` is in the middle of any of sorted ranges
+fn inside<F: Fn(usize) -> usize>(ranges: &[(usize, usize)], pos: usize, len: F) -> bool {
+    let i = ranges.partition_point(|x| x.0 < pos);
+    i > 0 && pos < ranges[i - 1].0 + len(ranges[i - 1].1)
+}
+
+fn spacing(a: &Token, b: &Token, gap: &str) -> &'static str {
+    let keep = if gap.is_empty() { "" } else { " " };
+    // Escaped identifiers are terminated by whitespace
+    if a.text.starts_with('\\') {
+        return keep;
+    }
+    if a.symbol && a.text == "," {
+        return " ";
+    }
+    let open = a.symbol && matches!(a.text, "(" | "[");
+    let close = b.symbol && matches!(b.text, ")" | "]" | "," | ";");
+    // `( *` and `* )` must not become attribute delimiters
+    let attribute = a.text.ends_with('(') && b.text.starts_with('*')
+        || a.text.ends_with('*') && b.text.starts_with(')');
+    if (open || close) && !attribute {
+        ""
+    } else {
+        keep
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn fmt(s: &str, config: &Config) -> String {
+        let defines = HashMap::new();
+        format_str(s, "test.sv", &defines, config).unwrap()
+    }
+
+    #[test]
+    fn test_indent() {
+        let src = r##"module top #(parameter W = 8,
+parameter D = 2) (
+    input  logic clk,
+      input logic [W-1:0] d,
+        output logic [W-1:0] q
+);
+// comment
+always_ff @(posedge clk) begin
+if (d == 0) begin
+q <= 0;
+end else begin
+    /* block
+       comment */
+q <= d;
+end
+end
+  always_comb
+      case (d)
+    0: x = 1;
+        default: begin
+  x = 0;
+  end
+endcase
+endmodule
+"##;
+        let expected = r##"module top #(parameter W = 8,
+  parameter D = 2) (
+  input logic clk,
+  input logic [W-1:0] d,
+  output logic [W-1:0] q
+);
+  // comment
+  always_ff @(posedge clk) begin
+    if (d == 0) begin
+      q <= 0;
+    end else begin
+      /* block
+         comment */
+      q <= d;
+    end
+  end
+  always_comb
+    case (d)
+      0: x = 1;
+      default: begin
+        x = 0;
+      end
+    endcase
+endmodule
+"##;
+        let config = Config::default();
+        assert_eq!(fmt(src, &config), expected);
+        assert_eq!(fmt(expected, &config), expected);
+    }
+
+    #[test]
+    fn test_spacing() {
+        let src = "module top;\nassign a = f( b ,c  ) ;\n`define A(x)  x   \nendmodule   \n";
+        let expected = "module top;\n  assign a = f(b, c);\n`define A(x)  x\nendmodule\n";
+        assert_eq!(fmt(src, &Config::default()), expected);
+    }
+
+    #[test]
+    fn test_align() {
+        let src = r##"module top;
+sub #(
+.W(8),
+.DEPTH (2)
+) u0 (
+.clk(clk),
+.data_in   (d),
+.q(q)
+);
+endmodule
+"##;
+        let expected = r##"module top;
+  sub #(
+    .W     (8),
+    .DEPTH (2)
+  ) u0 (
+    .clk     (clk),
+    .data_in (d),
+    .q       (q)
+  );
+endmodule
+"##;
+        assert_eq!(fmt(src, &Config::default()), expected);
+        assert_eq!(fmt(expected, &Config::default()), expected);
+
+        let config = Config {
+            align: false,
+            ..Config::default()
+        };
+        assert!(fmt(src, &config).contains("    .data_in (d),\n"));
+    }
+
+    #[test]
+    fn test_begin_placement() {
+        let src = "module top;\ninitial\nbegin\nif (a)\nbegin\nb = 1;\nend\nend\nendmodule\n";
+        let same_line = "module top;\n  initial begin\n    if (a) begin\n      b = 1;\n    end\n  end\nendmodule\n";
+        let next_line = "module top;\n  initial\n  begin\n    if (a)\n    begin\n      b = 1;\n    end\n  end\nendmodule\n";
+
+        let config = Config::from_toml("begin_placement = \"same_line\"").unwrap();
+        assert_eq!(fmt(src, &config), same_line);
+        assert_eq!(fmt(next_line, &config), same_line);
+
+        let config = Config::from_toml("begin_placement = \"next_line\"").unwrap();
+        assert_eq!(fmt(src, &config), next_line);
+        assert_eq!(fmt(same_line, &config), next_line);
+    }
+
+    #[test]
+    fn test_config() {
+        let config = Config::from_toml("indent_width = 4\nalign = false\n").unwrap();
+        assert_eq!(config.indent_width, 4);
+        assert!(!config.align);
+        assert_eq!(config.begin_placement, BeginPlacement::Preserve);
+        assert!(Config::from_toml("indent = 4").is_err());
+        assert!(Config::from_toml("begin_placement = \"below\"").is_err());
+
+        let src = "module top;\ninitial begin\nx = 1;\nend\nendmodule\n";
+        let expected = "module top;\n    initial begin\n        x = 1;\n    end\nendmodule\n";
+        assert_eq!(fmt(src, &config), expected);
+    }
+
+    #[test]
+    fn test_directive() {
+        let src = r##"module top;
+`ifdef A
+  wire   a;
+`else
+wire b;
+`endif
+`FOO(x)
+assign b = `BAR;
+endmodule
+"##;
+        let expected = r##"module top;
+`ifdef A
+  wire   a;
+`else
+  wire b;
+`endif
+  `FOO(x)
+  assign b = `BAR;
+endmodule
+"##;
+        assert_eq!(fmt(src, &Config::default()), expected);
+    }
+}
@@ -0,0 +1,270 @@
+use std::path::Path;
+use sv_parser::{Expansion, Locate, NodeEvent, RefNode, StatementItem, SyntaxTree};
+
+/// A token of the syntax tree with its layout in the formatted source
+#[derive(Clone, Debug)]
+pub(crate) struct Token<'a> {
+    pub text: &'a str,
+    /// Offset in the formatted source, `None` if the token comes from
+    /// another file or a macro expansion
+    pub offset: Option<usize>,
+    /// Offset in the formatted source which produced the token; the token
+    /// itself or the text macro usage which expanded to it
+    pub anchor: Option<usize>,
+    pub keyword: bool,
+    pub symbol: bool,
+    /// Indentation level of the innermost construct containing the token
+    pub depth: usize,
+    /// Whether a line starting with the token isn't a continuation line
+    pub unit: bool,
+}
+
+impl<'a> Token<'a> {
+    /// `end*`, `join*` and closing brackets
+    pub fn is_closing(&self) -> bool {
+        if self.keyword {
+            self.text.starts_with("end") || self.text.starts_with("join")
+        } else {
+            self.symbol && matches!(self.text, ")" | "]" | "}")
+        }
+    }
+
+    pub fn is_keyword(&self, text: &str) -> bool {
+        self.keyword && self.text == text
+    }
+}
+
+/// Named port connections or named parameter assignments of an
+/// instantiation as indices of `.`, the identifier and `(`
+pub(crate) type AlignGroup = Vec<(usize, usize, usize)>;
+
+pub(crate) struct Tokens<'a> {
+    pub tokens: Vec<Token<'a>>,
+    /// Ranges of comments in the formatted source
+    pub comments: Vec<(usize, usize)>,
+    pub align_groups: Vec<AlignGroup>,
+}
+
+struct Entry {
+    body: bool,
+    block: bool,
+    keyword: bool,
+    symbol: bool,
+    first: Option<usize>,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(tree: &'a SyntaxTree, path: &Path, lines: &[usize]) -> Self {
+        let line_of = |x: usize| lines.partition_point(|y| *y <= x);
+
+        let mut tokens: Vec<Token> = Vec::new();
+        let mut comments = Vec::new();
+        let mut align_groups = Vec::new();
+        let mut open_groups: Vec<AlignGroup> = Vec::new();
+
+        // Tokens which are the first token of their lines
+        let mut line_first: Vec<bool> = Vec::new();
+        let mut last_line = None;
+
+        let mut stack: Vec<Entry> = Vec::new();
+        let mut whitespace = 0;
+        for event in tree.into_iter().event() {
+            match event {
+                NodeEvent::Enter(RefNode::WhiteSpace(_)) => whitespace += 1,
+                NodeEvent::Leave(RefNode::WhiteSpace(_)) => whitespace -= 1,
+                NodeEvent::Enter(RefNode::Comment(x)) => {
+                    if let Some(offset) = local_offset(tree, path, &x.nodes.0) {
+                        comments.push((offset, offset + x.nodes.0.len));
+                    }
+                }
+                _ if whitespace > 0 => (),
+                NodeEvent::Enter(RefNode::Locate(x)) => {
+                    let idx = tokens.len();
+                    let offset = local_offset(tree, path, x);
+                    let anchor = offset.or_else(|| usage_offset(tree, path, x));
+
+                    let first = match anchor {
+                        Some(anchor) => {
+                            let line = line_of(anchor);
+                            let ret = last_line != Some(line);
+                            last_line = Some(line);
+                            ret
+                        }
+                        None => false,
+                    };
+                    line_first.push(first);
+
+                    for entry in stack.iter_mut().rev() {
+                        if entry.first.is_some() {
+                            break;
+                        }
+                        entry.first = Some(idx);
+                    }
+
+                    // Nested constructs starting at the same token are
+                    // indented once, and constructs starting in the middle of
+                    // a line aren't indented.
+                    let mut depth = 0;
+                    let mut last = None;
+                    for entry in &stack {
+                        if entry.body && line_first[entry.first.unwrap()] && last != entry.first {
+                            depth += 1;
+                            last = entry.first;
+                        }
+                    }
+
+                    let parent = stack.last();
+                    let mut token = Token {
+                        text: tree.get_str(x).unwrap_or(""),
+                        offset,
+                        anchor,
+                        keyword: matches!(parent, Some(x) if x.keyword),
+                        symbol: matches!(parent, Some(x) if x.symbol),
+                        depth: depth.max(1) - 1,
+                        unit: false,
+                    };
+                    token.unit = token.is_closing()
+                        || token.is_keyword("begin")
+                        || token.is_keyword("fork")
+                        || token.is_keyword("else")
+                        || stack
+                            .iter()
+                            .any(|x| (x.body || x.block) && x.first == Some(idx));
+                    tokens.push(token);
+                }
+                NodeEvent::Leave(RefNode::Locate(_)) => (),
+                NodeEvent::Enter(x) => {
+                    match x {
+                        RefNode::ListOfPortConnectionsNamed(_)
+                        | RefNode::ListOfParameterAssignmentsNamed(_) => {
+                            open_groups.push(Vec::new());
+                        }
+                        // Connections with attributes are not aligned
+                        RefNode::NamedPortConnectionIdentifier(y)
+                            if y.nodes.0.is_empty() && y.nodes.3.is_some() =>
+                        {
+                            let i = tokens.len();
+                            if let Some(group) = open_groups.last_mut() {
+                                group.push((i, i + 1, i + 2));
+                            }
+                        }
+                        RefNode::NamedParameterAssignment(_) => {
+                            let i = tokens.len();
+                            if let Some(group) = open_groups.last_mut() {
+                                group.push((i, i + 1, i + 2));
+                            }
+                        }
+                        _ => (),
+                    }
+                    stack.push(Entry {
+                        body: is_body(&x),
+                        block: is_block(&x),
+                        keyword: matches!(x, RefNode::Keyword(_)),
+                        symbol: matches!(x, RefNode::Symbol(_)),
+                        first: None,
+                    });
+                }
+                NodeEvent::Leave(x) => {
+                    if let RefNode::ListOfPortConnectionsNamed(_)
+                    | RefNode::ListOfParameterAssignmentsNamed(_) = x
+                    {
+                        align_groups.extend(open_groups.pop());
+                    }
+                    stack.pop();
+                }
+            }
+        }
+
+        Tokens {
+            tokens,
+            comments,
+            align_groups,
+        }
+    }
+}
+
+// Offset of a token written in the formatted source as is
+fn local_offset(tree: &SyntaxTree, path: &Path, locate: &Locate) -> Option<usize> {
+    if !tree.get_expansions(locate).is_empty() {
+        return None;
+    }
+    match tree.get_origin(locate) {
+        Some((origin, offset)) if origin == path => Some(offset),
+        _ => None,
+    }
+}
+
+// Offset of the text macro usage in the formatted source which expanded to
+// the token
+fn usage_offset(tree: &SyntaxTree, path: &Path, locate: &Locate) -> Option<usize> {
+    match tree.get_expansions(locate).as_slice() {
+        [Expansion::Macro(_, (origin, offset))] if origin == path => Some(*offset),
+        _ => None,
+    }
+}
+
+// Constructs whose lines are indented one level deeper than the enclosing
+// construct
+fn is_body(node: &RefNode) -> bool {
+    match node {
+        RefNode::Statement(x) => !is_block_item(&x.nodes.2),
+        RefNode::Description(_)
+        | RefNode::ModuleItem(_)
+        | RefNode::NonPortModuleItem(_)
+        | RefNode::InterfaceItem(_)
+        | RefNode::NonPortInterfaceItem(_)
+        | RefNode::ProgramItem(_)
+        | RefNode::NonPortProgramItem(_)
+        | RefNode::PackageItem(_)
+        | RefNode::ClassItem(_)
+        | RefNode::InterfaceClassItem(_)
+        | RefNode::CheckerOrGenerateItem(_)
+        | RefNode::GenerateItem(_)
+        | RefNode::BlockItemDeclaration(_)
+        | RefNode::TfItemDeclaration(_)
+        | RefNode::TfPortItem(_)
+        | RefNode::FunctionStatementOrNull(_)
+        | RefNode::CaseItem(_)
+        | RefNode::CaseInsideItem(_)
+        | RefNode::CasePatternItem(_)
+        | RefNode::CaseGenerateItem(_)
+        | RefNode::RandcaseItem(_)
+        | RefNode::AnsiPortDeclaration(_)
+        | RefNode::Port(_)
+        | RefNode::ParameterPortDeclaration(_)
+        | RefNode::ParamAssignment(_)
+        | RefNode::NamedPortConnection(_)
+        | RefNode::OrderedPortConnection(_)
+        | RefNode::NamedParameterAssignment(_)
+        | RefNode::OrderedParameterAssignment(_)
+        | RefNode::ModportItem(_)
+        | RefNode::StructUnionMember(_)
+        | RefNode::EnumNameDeclaration(_)
+        | RefNode::ClockingItem(_)
+        | RefNode::ConstraintBlockItem(_)
+        | RefNode::ConstraintExpression(_)
+        | RefNode::CoverageSpecOrOption(_)
+        | RefNode::BinsOrOptions(_)
+        | RefNode::SpecifyItem(_)
+        | RefNode::UdpPortDeclaration(_)
+        | RefNode::UdpBody(_)
+        | RefNode::CombinationalEntry(_)
+        | RefNode::SequentialEntry(_)
+        | RefNode::ConfigRuleStatement(_)
+        | RefNode::AssertionItemDeclaration(_) => true,
+        _ => false,
+    }
+}
+
+// Statements of `begin`-`end` or `fork`-`join`, which are placed at the level
+// of the enclosing construct
+fn is_block(node: &RefNode) -> bool {
+    match node {
+        RefNode::Statement(x) => is_block_item(&x.nodes.2),
+        _ => false,
+    }
+}
+
+fn is_block_item(x: &StatementItem) -> bool {
+    matches!(x, StatementItem::SeqBlock(_) | StatementItem::ParBlock(_))
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::thread;
+use sv_parser_fmt::{format_str, BeginPlacement, Config};
+
+// Parsers of the testcases which are complete source texts
+const PARSERS: &[&str] = &[
+    "source_text",
+    "description",
+    "module_declaration",
+    "interface_declaration",
+    "program_declaration",
+    "checker_declaration",
+    "package_declaration",
+    "class_declaration",
+    "udp_declaration",
+    "config_declaration",
+];
+
+// Source texts of the testcases from IEEE 1800 in sv-parser-parser, which are
+// expected to be parsed successfully
+fn testcases() -> Vec<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../sv-parser-parser/src/tests.rs");
+    let text = fs::read_to_string(path).unwrap();
+    let mut ret = Vec::new();
+    for case in text.split("test!(").skip(1) {
+        let case = case.trim_start();
+        let parser = case.split(',').next().unwrap().trim();
+        if !PARSERS.contains(&parser) {
+            continue;
+        }
+        let case = case[parser.len()..].trim_start_matches(|x: char| x == ',' || x.is_whitespace());
+        if !case.starts_with("r##\"") {
+            continue;
+        }
+        let case = &case[4..];
+        let end = case.find("\"##").unwrap();
+        let rest = case[end + 3..].trim_start_matches(|x: char| x == ',' || x.is_whitespace());
+        if rest.starts_with("Ok") {
+            ret.push(String::from(&case[..end]));
+        }
+    }
+    ret
+}
+
+// Testcases which can't be formatted, because they use text macros undefined
+// in the preprocessor
+const SKIPPED: &[&str] = &["`macro(A, B, logic, C)", "`macro(A, B, logic, a())"];
+
+fn strip_whitespace(s: &str) -> String {
+    s.chars().filter(|x| !x.is_whitespace()).collect()
+}
+
+fn check(config: Config) {
+    let defines = HashMap::new();
+    let mut formatted = 0;
+    let mut skipped = Vec::new();
+    for src in testcases() {
+        let first = match format_str(&src, "test.sv", &defines, &config) {
+            Ok(x) => x,
+            Err(_) if SKIPPED.contains(&src.as_str()) => {
+                skipped.push(src);
+                continue;
+            }
+            Err(x) => panic!("{}\n{}", x, src),
+        };
+        assert_eq!(strip_whitespace(&first), strip_whitespace(&src), "{}", src);
+        let second = format_str(&first, "test.sv", &defines, &config)
+            .unwrap_or_else(|x| panic!("{}\n{}", x, first));
+        assert_eq!(second, first, "{}", src);
+        formatted += 1;
+    }
+    assert_eq!(skipped, SKIPPED);
+    assert!(formatted > 100, "{}", formatted);
+}
+
+fn run(config: Config) {
+    let child = thread::Builder::new()
+        .stack_size(20 * 1024 * 1024)
+        .spawn(move || check(config))
+        .unwrap();
+    child.join().unwrap();
+}
+
+#[test]
+fn idempotence() {
+    run(Config::default());
+}
+
+#[test]
+fn idempotence_same_line() {
+    run(Config {
+        indent_width: 4,
+        align: false,
+        begin_placement: BeginPlacement::SameLine,
+    });
+}
+
+#[test]
+fn idempotence_next_line() {
+    run(Config {
+        begin_placement: BeginPlacement::NextLine,
+        ..Config::default()
+    });
+}
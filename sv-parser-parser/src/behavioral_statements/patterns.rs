@@ -163,7 +163,7 @@ pub(crate) fn assignment_pattern_key(s: Span) -> IResult<Span, AssignmentPattern
 #[packrat_parser]
 pub(crate) fn assignment_pattern_expression(s: Span) -> IResult<Span, AssignmentPatternExpression> {
     let (s, a) = opt(assignment_pattern_expression_type)(s)?;
-    let (s, b) = assignment_pattern(s)?;
+    let (s, b) = since(LanguageVersion::Ieee1800_2005, assignment_pattern)(s)?;
     Ok((s, AssignmentPatternExpression { nodes: (a, b) }))
 }
 
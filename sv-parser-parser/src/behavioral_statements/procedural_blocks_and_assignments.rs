@@ -122,18 +122,26 @@ pub(crate) fn operator_assignment(s: Span) -> IResult<Span, OperatorAssignment>
 pub(crate) fn assignment_operator(s: Span) -> IResult<Span, AssignmentOperator> {
     alt((
         map(symbol("="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol("+="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol("-="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol("*="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol("/="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol("%="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol("&="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol("|="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol("^="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol("<<<="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol(">>>="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol("<<="), |x| AssignmentOperator { nodes: (x,) }),
-        map(symbol(">>="), |x| AssignmentOperator { nodes: (x,) }),
+        map(
+            since(
+                LanguageVersion::Ieee1800_2005,
+                alt((
+                    symbol("+="),
+                    symbol("-="),
+                    symbol("*="),
+                    symbol("/="),
+                    symbol("%="),
+                    symbol("&="),
+                    symbol("|="),
+                    symbol("^="),
+                    symbol("<<<="),
+                    symbol(">>>="),
+                    symbol("<<="),
+                    symbol(">>="),
+                )),
+            ),
+            |x| AssignmentOperator { nodes: (x,) },
+        ),
     ))(s)
 }
 
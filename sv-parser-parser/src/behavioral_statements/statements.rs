@@ -92,6 +92,14 @@ pub(crate) fn statement_item(s: Span) -> IResult<Span, StatementItem> {
         map(expect_property_statement, |x| {
             StatementItem::ExpectPropertyStatement(Box::new(x))
         }),
+        alt((
+            map(pair(macro_placeholder, opt(symbol(";"))), |x| {
+                StatementItem::TextMacroUsage(Box::new(x))
+            }),
+            map(ams(contribution_statement), |x| {
+                StatementItem::ContributionStatement(Box::new(x))
+            }),
+        )),
     ))(s)
 }
 
@@ -81,7 +81,8 @@ pub(crate) fn associative_dimension_asterisk(s: Span) -> IResult<Span, Associati
 #[tracable_parser]
 #[packrat_parser]
 pub(crate) fn variable_dimension(s: Span) -> IResult<Span, VariableDimension> {
-    alt((
+    let queue = queue_bracket(s).is_ok();
+    let (s, a) = alt((
         map(unsized_dimension, |x| {
             VariableDimension::UnsizedDimension(Box::new(x))
         }),
@@ -94,7 +95,20 @@ pub(crate) fn variable_dimension(s: Span) -> IResult<Span, VariableDimension> {
         map(queue_dimension, |x| {
             VariableDimension::QueueDimension(Box::new(x))
         }),
-    ))(s)
+    ))(s)?;
+    // Consecutive queue dimensions are new in 1800-2023.
+    if queue && queue_bracket(s).is_ok() {
+        since(LanguageVersion::Ieee1800_2023, success(()))(s)?;
+    }
+    Ok((s, a))
+}
+
+#[tracable_parser]
+pub(crate) fn queue_bracket(s: Span) -> IResult<Span, ()> {
+    let (s, _) = symbol("[")(s)?;
+    let (s, _) = symbol("$")(s)?;
+    let (s, _) = alt((symbol("]"), symbol(":")))(s)?;
+    Ok((s, ()))
 }
 
 #[tracable_parser]
@@ -33,9 +33,15 @@ pub(crate) fn function_data_type_or_implicit(s: Span) -> IResult<Span, FunctionD
 #[packrat_parser]
 pub(crate) fn function_declaration(s: Span) -> IResult<Span, FunctionDeclaration> {
     let (s, a) = keyword("function")(s)?;
-    let (s, b) = opt(lifetime)(s)?;
-    let (s, c) = function_body_declaration(s)?;
-    Ok((s, FunctionDeclaration { nodes: (a, b, c) }))
+    let (s, b) = opt(dynamic_override_specifiers)(s)?;
+    let (s, c) = opt(lifetime)(s)?;
+    let (s, d) = function_body_declaration(s)?;
+    Ok((
+        s,
+        FunctionDeclaration {
+            nodes: (a, b, c, d),
+        },
+    ))
 }
 
 #[tracable_parser]
@@ -107,13 +113,14 @@ pub(crate) fn interface_identifier_or_class_scope(
 #[packrat_parser]
 pub(crate) fn function_prototype(s: Span) -> IResult<Span, FunctionPrototype> {
     let (s, a) = keyword("function")(s)?;
-    let (s, b) = data_type_or_void(s)?;
-    let (s, c) = function_identifier(s)?;
-    let (s, d) = opt(paren(opt(tf_port_list)))(s)?;
+    let (s, b) = opt(dynamic_override_specifiers)(s)?;
+    let (s, c) = data_type_or_void(s)?;
+    let (s, d) = function_identifier(s)?;
+    let (s, e) = opt(paren(opt(tf_port_list)))(s)?;
     Ok((
         s,
         FunctionPrototype {
-            nodes: (a, b, c, d),
+            nodes: (a, b, c, d, e),
         },
     ))
 }
@@ -50,14 +50,19 @@ pub(crate) fn data_type(s: Span) -> IResult<Span, DataType> {
         map(keyword("string"), |x| DataType::String(Box::new(x))),
         map(keyword("chandle"), |x| DataType::Chandle(Box::new(x))),
         data_type_virtual,
-        map(terminated(class_type, peek(not(packed_dimension))), |x| {
-            DataType::ClassType(Box::new(x))
-        }),
-        data_type_type,
+        map(
+            since(
+                LanguageVersion::Ieee1800_2005,
+                terminated(class_type, peek(not(packed_dimension))),
+            ),
+            |x| DataType::ClassType(Box::new(x)),
+        ),
+        since(LanguageVersion::Ieee1800_2005, data_type_type),
         map(keyword("event"), |x| DataType::Chandle(Box::new(x))),
-        map(ps_covergroup_identifier, |x| {
-            DataType::PsCovergroupIdentifier(Box::new(x))
-        }),
+        map(
+            since(LanguageVersion::Ieee1800_2005, ps_covergroup_identifier),
+            |x| DataType::PsCovergroupIdentifier(Box::new(x)),
+        ),
         map(type_reference, |x| DataType::TypeReference(Box::new(x))),
     ))(s)
 }
@@ -508,6 +513,13 @@ pub(crate) fn data_type_or_void(s: Span) -> IResult<Span, DataTypeOrVoid> {
 pub(crate) fn struct_union(s: Span) -> IResult<Span, StructUnion> {
     alt((
         map(keyword("struct"), |x| StructUnion::Struct(Box::new(x))),
+        map(
+            since(
+                LanguageVersion::Ieee1800_2023,
+                pair(keyword("union"), keyword("soft")),
+            ),
+            |x| StructUnion::UnionSoft(Box::new(x)),
+        ),
         map(pair(keyword("union"), keyword("tagged")), |x| {
             StructUnion::UnionTagged(Box::new(x))
         }),
@@ -150,7 +150,10 @@ pub(crate) fn net_declaration(s: Span) -> IResult<Span, NetDeclaration> {
     alt((
         net_declaration_interconnect,
         net_declaration_net_type,
-        net_declaration_net_type_identifier,
+        since(
+            LanguageVersion::Ieee1800_2012,
+            net_declaration_net_type_identifier,
+        ),
     ))(s)
 }
 
@@ -149,6 +149,7 @@ pub(crate) fn non_zero_unsigned_number_impl(s: Span) -> IResult<Span, Locate> {
 pub(crate) fn real_number(s: Span) -> IResult<Span, RealNumber> {
     alt((
         real_number_floating,
+        ams(real_number_scale_factor),
         map(fixed_point_number, |x| {
             RealNumber::FixedPointNumber(Box::new(x))
         }),
@@ -176,6 +177,25 @@ pub(crate) fn real_number_floating(s: Span) -> IResult<Span, RealNumber> {
     ))
 }
 
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn real_number_scale_factor(s: Span) -> IResult<Span, RealNumber> {
+    let (s, a) = unsigned_number_without_ws(s)?;
+    let (s, b) = opt(pair(
+        map(tag("."), |x: Span| Symbol {
+            nodes: (into_locate(x), vec![]),
+        }),
+        unsigned_number_without_ws,
+    ))(s)?;
+    let (s, c) = scale_factor(s)?;
+    Ok((
+        s,
+        RealNumber::ScaleFactor(Box::new(RealNumberScaleFactor {
+            nodes: (a, b, c),
+        })),
+    ))
+}
+
 #[tracable_parser]
 #[packrat_parser]
 pub(crate) fn fixed_point_number(s: Span) -> IResult<Span, FixedPointNumber> {
@@ -212,6 +232,21 @@ pub(crate) fn exp(s: Span) -> IResult<Span, Exp> {
     Ok((s, Exp { nodes: (a,) }))
 }
 
+// Verilog-AMS 2.4 Clause 2.6.2, where a scale factor can't be followed by
+// characters of an identifier like `1ms`
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn scale_factor(s: Span) -> IResult<Span, ScaleFactor> {
+    let (s, a) = ws(terminated(
+        map(
+            verify(take(1usize), |x: &Span| "TGMKkmunpfa".contains(*x.fragment())),
+            into_locate,
+        ),
+        not(peek(is_a(AZ09_DOLLAR))),
+    ))(s)?;
+    Ok((s, ScaleFactor { nodes: (Symbol { nodes: a },) }))
+}
+
 #[tracable_parser]
 #[packrat_parser]
 pub(crate) fn unsigned_number_without_ws(s: Span) -> IResult<Span, UnsignedNumber> {
@@ -389,13 +424,16 @@ pub(crate) fn z_number_impl(s: Span) -> IResult<Span, Locate> {
 #[tracable_parser]
 #[packrat_parser]
 pub(crate) fn unbased_unsized_literal(s: Span) -> IResult<Span, UnbasedUnsizedLiteral> {
-    let (s, a) = alt((
-        symbol("'0"),
-        symbol("'1"),
-        symbol("'z"),
-        symbol("'x"),
-        symbol("'Z"),
-        symbol("'X"),
-    ))(s)?;
+    let (s, a) = since(
+        LanguageVersion::Ieee1800_2005,
+        alt((
+            symbol("'0"),
+            symbol("'1"),
+            symbol("'z"),
+            symbol("'x"),
+            symbol("'Z"),
+            symbol("'X"),
+        )),
+    )(s)?;
     Ok((s, UnbasedUnsizedLiteral { nodes: (a,) }))
 }
@@ -65,7 +65,10 @@ pub(crate) fn binary_operator(s: Span) -> IResult<Span, BinaryOperator> {
 #[tracable_parser]
 #[packrat_parser]
 pub(crate) fn inc_or_dec_operator(s: Span) -> IResult<Span, IncOrDecOperator> {
-    let (s, a) = alt((symbol("++"), symbol("--")))(s)?;
+    let (s, a) = since(
+        LanguageVersion::Ieee1800_2005,
+        alt((symbol("++"), symbol("--"))),
+    )(s)?;
     Ok((s, IncOrDecOperator { nodes: (a,) }))
 }
 
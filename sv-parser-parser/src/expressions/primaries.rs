@@ -223,6 +223,10 @@ pub(crate) fn primary(s: Span) -> IResult<Span, Primary> {
         map(keyword("this"), |x| Primary::This(Box::new(x))),
         map(keyword("$"), |x| Primary::Dollar(Box::new(x))),
         map(keyword("null"), |x| Primary::Null(Box::new(x))),
+        map(macro_placeholder, |x| Primary::TextMacroUsage(Box::new(x))),
+        map(ams(analog_builtin_call), |x| {
+            Primary::AnalogBuiltinCall(Box::new(x))
+        }),
     ))(s)
 }
 
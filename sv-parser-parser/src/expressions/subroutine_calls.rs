@@ -349,8 +349,16 @@ pub(crate) fn array_method_name(s: Span) -> IResult<Span, ArrayMethodName> {
         map(keyword("and"), |x| ArrayMethodName::And(Box::new(x))),
         map(keyword("or"), |x| ArrayMethodName::Or(Box::new(x))),
         map(keyword("xor"), |x| ArrayMethodName::Xor(Box::new(x))),
-        map(method_identifier, |x| {
-            ArrayMethodName::MethodIdentifier(Box::new(x))
-        }),
+        map(
+            preceded(
+                // The `map` method is new in 1800-2023.
+                alt((
+                    since(LanguageVersion::Ieee1800_2023, success(())),
+                    not(peek(terminated(tag("map"), not(is_a(AZ09_DOLLAR))))),
+                )),
+                method_identifier,
+            ),
+            |x| ArrayMethodName::MethodIdentifier(Box::new(x)),
+        ),
     ))(s)
 }
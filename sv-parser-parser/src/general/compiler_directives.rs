@@ -43,6 +43,9 @@ pub(crate) fn compiler_directive(s: Span) -> IResult<Span, CompilerDirective> {
         map(endcelldefine_compiler_directive, |x| {
             CompilerDirective::EndcelldefineDriveCompilerDirective(Box::new(x))
         }),
+        map(protected_envelope, |x| {
+            CompilerDirective::ProtectedEnvelope(Box::new(x))
+        }),
         map(pragma, |x| CompilerDirective::Pragma(Box::new(x))),
         map(line_compiler_directive, |x| {
             CompilerDirective::LineCompilerDirective(Box::new(x))
@@ -102,6 +105,9 @@ pub(crate) fn compiler_directive_without_resetall(s: Span) -> IResult<Span, Comp
         map(endcelldefine_compiler_directive, |x| {
             CompilerDirective::EndcelldefineDriveCompilerDirective(Box::new(x))
         }),
+        map(protected_envelope, |x| {
+            CompilerDirective::ProtectedEnvelope(Box::new(x))
+        }),
         map(pragma, |x| CompilerDirective::Pragma(Box::new(x))),
         map(line_compiler_directive, |x| {
             CompilerDirective::LineCompilerDirective(Box::new(x))
@@ -313,6 +319,16 @@ pub(crate) fn text_macro_usage(s: Span) -> IResult<Span, TextMacroUsage> {
     Ok((s, TextMacroUsage { nodes: (a, b, c) }))
 }
 
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn macro_placeholder(s: Span) -> IResult<Span, TextMacroUsage> {
+    if s.extra.macro_placeholder {
+        text_macro_usage(s)
+    } else {
+        Err(Err::Error(make_error(s, ErrorKind::Fix)))
+    }
+}
+
 #[tracable_parser]
 #[packrat_parser]
 pub(crate) fn list_of_actual_arguments(s: Span) -> IResult<Span, ListOfActualArguments> {
@@ -687,6 +703,60 @@ pub(crate) fn pragma(s: Span) -> IResult<Span, Pragma> {
     ))
 }
 
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn protected_envelope(s: Span) -> IResult<Span, ProtectedEnvelope> {
+    let (s, a) = protect_pragma("begin_protected")(s)?;
+    let (s, (b, c)) = many_till(protected_envelope_item, protect_pragma("end_protected"))(s)?;
+    Ok((s, ProtectedEnvelope { nodes: (a, b, c) }))
+}
+
+pub(crate) fn protect_pragma<'a>(t: &'a str) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, Pragma> {
+    move |s: Span<'a>| {
+        let text = |x: &Locate| {
+            let offset = x.offset - s.location_offset();
+            &s.fragment()[offset..offset + x.len]
+        };
+        let (s2, a) = pragma(s)?;
+        let (_, _, ref name, ref expressions) = a.nodes;
+        let found = text(&name.nodes.0.nodes.0) == "protect"
+            && expressions.as_ref().is_some_and(|x| {
+                x.contents().into_iter().any(|x| match x {
+                    PragmaExpression::PragmaKeyword(x) => text(&x.nodes.0.nodes.0) == t,
+                    _ => false,
+                })
+            });
+        if found {
+            Ok((s2, a))
+        } else {
+            Err(Err::Error(make_error(s, ErrorKind::Verify)))
+        }
+    }
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn protected_envelope_item(s: Span) -> IResult<Span, ProtectedEnvelopeItem> {
+    alt((
+        map(pragma, |x| ProtectedEnvelopeItem::Pragma(Box::new(x))),
+        map(protected_block, |x| {
+            ProtectedEnvelopeItem::ProtectedBlock(Box::new(x))
+        }),
+    ))(s)
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn protected_block(s: Span) -> IResult<Span, ProtectedBlock> {
+    let (s, a) = verify(take_until("`pragma"), |x: &Span| !x.fragment().is_empty())(s)?;
+    Ok((
+        s,
+        ProtectedBlock {
+            nodes: (into_locate(a),),
+        },
+    ))
+}
+
 #[tracable_parser]
 #[packrat_parser]
 pub(crate) fn pragma_name(s: Span) -> IResult<Span, PragmaName> {
@@ -829,6 +899,14 @@ pub(crate) fn keywords_directive(s: Span) -> IResult<Span, KeywordsDirective> {
 #[packrat_parser]
 pub(crate) fn version_specifier(s: Span) -> IResult<Span, VersionSpecifier> {
     let (s, a) = alt((
+        map(keyword("VAMS-2.4"), |x| {
+            begin_keywords("VAMS-2.4");
+            x
+        }),
+        map(keyword("1800-2023"), |x| {
+            begin_keywords("1800-2023");
+            x
+        }),
         map(keyword("1800-2017"), |x| {
             begin_keywords("1800-2017");
             x
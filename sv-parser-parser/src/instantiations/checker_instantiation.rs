@@ -5,7 +5,7 @@ use crate::*;
 #[tracable_parser]
 #[packrat_parser]
 pub(crate) fn checker_instantiation(s: Span) -> IResult<Span, CheckerInstantiation> {
-    let (s, a) = ps_checker_identifier(s)?;
+    let (s, a) = since(LanguageVersion::Ieee1800_2009, ps_checker_identifier)(s)?;
     let (s, b) = name_of_instance(s)?;
     let (s, c) = paren(opt(list_of_checker_port_connections))(s)?;
     let (s, d) = symbol(";")(s)?;
@@ -1445,6 +1445,276 @@ pub(crate) const KEYWORDS_1800_2017: &[&str] = &[
     "xor",
 ];
 
+// IEEE 1800-2023 Table B.1 reserves no keywords beyond IEEE 1800-2017.
+pub(crate) const KEYWORDS_1800_2023: &[&str] = KEYWORDS_1800_2017;
+
+pub(crate) const KEYWORDS_VAMS_2_4: &[&str] = &[
+    "always",
+    "and",
+    "assign",
+    "begin",
+    "buf",
+    "bufif0",
+    "bufif1",
+    "case",
+    "casex",
+    "casez",
+    "cmos",
+    "deassign",
+    "default",
+    "defparam",
+    "disable",
+    "edge",
+    "else",
+    "end",
+    "endcase",
+    "endfunction",
+    "endmodule",
+    "endprimitive",
+    "endspecify",
+    "endtable",
+    "endtask",
+    "event",
+    "for",
+    "force",
+    "forever",
+    "fork",
+    "function",
+    "highz0",
+    "highz1",
+    "if",
+    "ifnone",
+    "initial",
+    "inout",
+    "input",
+    "integer",
+    "join",
+    "large",
+    "macromodule",
+    "medium",
+    "module",
+    "nand",
+    "negedge",
+    "nmos",
+    "nor",
+    "not",
+    "notif0",
+    "notif1",
+    "or",
+    "output",
+    "parameter",
+    "pmos",
+    "posedge",
+    "primitive",
+    "pull0",
+    "pull1",
+    "pulldown",
+    "pullup",
+    "rcmos",
+    "real",
+    "realtime",
+    "reg",
+    "release",
+    "repeat",
+    "rnmos",
+    "rpmos",
+    "rtran",
+    "rtranif0",
+    "rtranif1",
+    "scalared",
+    "small",
+    "specify",
+    "specparam",
+    "strong0",
+    "strong1",
+    "supply0",
+    "supply1",
+    "table",
+    "task",
+    "time",
+    "tran",
+    "tranif0",
+    "tranif1",
+    "tri",
+    "tri0",
+    "tri1",
+    "triand",
+    "trior",
+    "trireg",
+    "vectored",
+    "wait",
+    "wand",
+    "weak0",
+    "weak1",
+    "while",
+    "wire",
+    "wor",
+    "xnor",
+    "xor",
+    "automatic",
+    "cell",
+    "config",
+    "design",
+    "endconfig",
+    "endgenerate",
+    "generate",
+    "genvar",
+    "incdir",
+    "include",
+    "instance",
+    "liblist",
+    "library",
+    "localparam",
+    "noshowcancelled",
+    "pulsestyle_ondetect",
+    "pulsestyle_onevent",
+    "showcancelled",
+    "signed",
+    "unsigned",
+    "use",
+    "uwire",
+    "above",
+    "abs",
+    "absdelay",
+    "absdelta",
+    "abstol",
+    "access",
+    "acos",
+    "acosh",
+    "analog",
+    "analysis",
+    "asin",
+    "asinh",
+    "atan",
+    "atan2",
+    "atanh",
+    "branch",
+    "ceil",
+    "connect",
+    "connectmodule",
+    "connectrules",
+    "continuous",
+    "cos",
+    "cosh",
+    "cross",
+    "ddt",
+    "ddt_nature",
+    "ddx",
+    "discipline",
+    "discrete",
+    "domain",
+    "driver_update",
+    "endconnectrules",
+    "enddiscipline",
+    "endnature",
+    "endparamset",
+    "exclude",
+    "exp",
+    "final_step",
+    "flicker_noise",
+    "floor",
+    "flow",
+    "from",
+    "ground",
+    "hypot",
+    "idt",
+    "idt_nature",
+    "idtmod",
+    "inf",
+    "initial_step",
+    "laplace_nd",
+    "laplace_np",
+    "laplace_zd",
+    "laplace_zp",
+    "last_crossing",
+    "limexp",
+    "ln",
+    "log",
+    "max",
+    "merged",
+    "min",
+    "nature",
+    "net_resolution",
+    "noise_table",
+    "noise_table_log",
+    "paramset",
+    "potential",
+    "pow",
+    "resolveto",
+    "sin",
+    "sinh",
+    "slew",
+    "split",
+    "sqrt",
+    "string",
+    "tan",
+    "tanh",
+    "timer",
+    "transition",
+    "units",
+    "white_noise",
+    "wreal",
+    "zi_nd",
+    "zi_np",
+    "zi_zd",
+    "zi_zp",
+];
+
+pub(crate) const ANALOG_BUILTIN_FUNCTIONS: &[&str] = &[
+    "abs",
+    "absdelay",
+    "absdelta",
+    "above",
+    "acos",
+    "acosh",
+    "analysis",
+    "asin",
+    "asinh",
+    "atan",
+    "atan2",
+    "atanh",
+    "ceil",
+    "cos",
+    "cosh",
+    "cross",
+    "ddt",
+    "ddx",
+    "exp",
+    "final_step",
+    "flicker_noise",
+    "floor",
+    "hypot",
+    "idt",
+    "idtmod",
+    "initial_step",
+    "laplace_nd",
+    "laplace_np",
+    "laplace_zd",
+    "laplace_zp",
+    "last_crossing",
+    "limexp",
+    "ln",
+    "log",
+    "max",
+    "min",
+    "noise_table",
+    "noise_table_log",
+    "pow",
+    "sin",
+    "sinh",
+    "slew",
+    "sqrt",
+    "tan",
+    "tanh",
+    "timer",
+    "transition",
+    "white_noise",
+    "zi_nd",
+    "zi_np",
+    "zi_zd",
+    "zi_zp",
+];
+
 pub(crate) const KEYWORDS_DIRECTIVE: &[&str] = &[
     "begin_keywords",
     "celldefine",
@@ -19,6 +19,7 @@ pub mod primitive_instances;
 pub mod source_text;
 pub mod specify_section;
 pub mod udp_declaration_and_instantiation;
+pub mod verilog_ams;
 pub(crate) use behavioral_statements::*;
 pub(crate) use declarations::*;
 pub(crate) use expressions::*;
@@ -29,6 +30,7 @@ pub(crate) use primitive_instances::*;
 pub(crate) use source_text::*;
 pub(crate) use specify_section::*;
 pub(crate) use udp_declaration_and_instantiation::*;
+pub(crate) use verilog_ams::*;
 
 pub(crate) use nom::branch::*;
 pub(crate) use nom::bytes::complete::*;
@@ -49,16 +51,50 @@ pub(crate) use sv_parser_syntaxtree::*;
 // -----------------------------------------------------------------------------
 
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
-pub struct SpanInfo {
+pub struct SpanInfo<'a> {
     #[cfg(feature = "trace")]
     pub tracable_info: TracableInfo,
     pub recursive_info: RecursiveInfo,
+    /// Accept text macro usages left by the preprocessor as placeholders of
+    /// expressions, statements and module items instead of white space.
+    pub macro_placeholder: bool,
+    /// Reject keywords and constructs which are not legal in the given version
+    /// outside of `begin_keywords` regions.
+    pub language_version: Option<LanguageVersion>,
+    /// Words reserved or un-reserved on top of the keywords of the version.
+    pub keyword_overrides: Option<&'a KeywordOverrides>,
+    /// Accept the analog and mixed-signal extensions of Verilog-AMS 2.4 on
+    /// top of the language version, which also reserves their keywords.
+    pub verilog_ams: bool,
 }
 
-pub type Span<'a> = nom_locate::LocatedSpan<&'a str, SpanInfo>;
+/// Language versions selectable for strict parsing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LanguageVersion {
+    Ieee1364_1995,
+    Ieee1364_2001,
+    Ieee1364_2005,
+    Ieee1800_2005,
+    Ieee1800_2009,
+    Ieee1800_2012,
+    Ieee1800_2017,
+    Ieee1800_2023,
+}
+
+/// Words reserved or un-reserved in addition to the keywords of the language version
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeywordOverrides {
+    /// Vendor extension keywords which can't be used as identifiers
+    pub reserved: Vec<String>,
+    /// Keywords which are parsed as identifiers, e.g. `soft` used as a signal name
+    /// in legacy code
+    pub unreserved: Vec<String>,
+}
+
+pub type Span<'a> = nom_locate::LocatedSpan<&'a str, SpanInfo<'a>>;
 pub type IResult<T, U> = nom::IResult<T, U, GreedyError<T, ErrorKind>>;
 
-impl HasRecursiveInfo for SpanInfo {
+impl<'a> HasRecursiveInfo for SpanInfo<'a> {
     fn get_recursive_info(&self) -> RecursiveInfo {
         self.recursive_info
     }
@@ -70,7 +106,7 @@ impl HasRecursiveInfo for SpanInfo {
 }
 
 #[cfg(feature = "trace")]
-impl HasTracableInfo for SpanInfo {
+impl<'a> HasTracableInfo for SpanInfo<'a> {
     fn get_tracable_info(&self) -> TracableInfo {
         self.tracable_info
     }
@@ -81,7 +117,7 @@ impl HasTracableInfo for SpanInfo {
     }
 }
 
-impl HasExtraState<bool> for SpanInfo {
+impl<'a> HasExtraState<bool> for SpanInfo<'a> {
     fn get_extra_state(&self) -> bool {
         in_directive()
     }
@@ -221,7 +221,7 @@ pub(crate) fn class_method_extern_constructor(s: Span) -> IResult<Span, ClassMet
 pub(crate) fn class_constructor_prototype(s: Span) -> IResult<Span, ClassConstructorPrototype> {
     let (s, a) = keyword("function")(s)?;
     let (s, b) = keyword("new")(s)?;
-    let (s, c) = opt(paren(opt(tf_port_list)))(s)?;
+    let (s, c) = opt(paren(opt(class_constructor_arg_list)))(s)?;
     let (s, d) = symbol(";")(s)?;
     Ok((
         s,
@@ -315,14 +315,14 @@ pub(crate) fn class_constructor_declaration(s: Span) -> IResult<Span, ClassConst
     let (s, a) = keyword("function")(s)?;
     let (s, b) = opt(class_scope)(s)?;
     let (s, c) = keyword("new")(s)?;
-    let (s, d) = opt(paren(opt(tf_port_list)))(s)?;
+    let (s, d) = opt(paren(opt(class_constructor_arg_list)))(s)?;
     let (s, e) = symbol(";")(s)?;
     let (s, f) = many0(block_item_declaration)(s)?;
     let (s, g) = opt(tuple((
         keyword("super"),
         symbol("."),
         keyword("new"),
-        opt(paren(list_of_arguments)),
+        opt(paren(list_of_arguments_or_default)),
         symbol(";"),
     )))(s)?;
     let (s, (h, i)) = many_till(function_statement_or_null, keyword("endfunction"))(s)?;
@@ -335,9 +335,96 @@ pub(crate) fn class_constructor_declaration(s: Span) -> IResult<Span, ClassConst
     ))
 }
 
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn class_constructor_arg_list(s: Span) -> IResult<Span, ClassConstructorArgList> {
+    let (s, a) = list(symbol(","), class_constructor_arg)(s)?;
+    Ok((s, ClassConstructorArgList { nodes: (a,) }))
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn class_constructor_arg(s: Span) -> IResult<Span, ClassConstructorArg> {
+    alt((
+        map(
+            since(LanguageVersion::Ieee1800_2023, keyword("default")),
+            |x| ClassConstructorArg::Default(Box::new(x)),
+        ),
+        map(tf_port_item, |x| ClassConstructorArg::TfPortItem(Box::new(x))),
+    ))(s)
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn list_of_arguments_or_default(s: Span) -> IResult<Span, ListOfArgumentsOrDefault> {
+    alt((
+        map(
+            since(LanguageVersion::Ieee1800_2023, keyword("default")),
+            |x| ListOfArgumentsOrDefault::Default(Box::new(x)),
+        ),
+        map(list_of_arguments, |x| {
+            ListOfArgumentsOrDefault::ListOfArguments(Box::new(x))
+        }),
+    ))(s)
+}
+
 #[tracable_parser]
 #[packrat_parser]
 pub(crate) fn new(s: Span) -> IResult<Span, New> {
     let (s, a) = keyword("new")(s)?;
     Ok((s, New { nodes: (a,) }))
 }
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn dynamic_override_specifiers(s: Span) -> IResult<Span, DynamicOverrideSpecifiers> {
+    since(
+        LanguageVersion::Ieee1800_2023,
+        alt((
+            dynamic_override_specifiers_initial_or_extends,
+            dynamic_override_specifiers_final,
+        )),
+    )(s)
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn dynamic_override_specifiers_initial_or_extends(
+    s: Span,
+) -> IResult<Span, DynamicOverrideSpecifiers> {
+    let (s, a) = initial_or_extends_specifier(s)?;
+    let (s, b) = opt(final_specifier)(s)?;
+    Ok((s, DynamicOverrideSpecifiers { nodes: (Some(a), b) }))
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn dynamic_override_specifiers_final(
+    s: Span,
+) -> IResult<Span, DynamicOverrideSpecifiers> {
+    let (s, a) = final_specifier(s)?;
+    Ok((s, DynamicOverrideSpecifiers { nodes: (None, Some(a)) }))
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn initial_or_extends_specifier(s: Span) -> IResult<Span, InitialOrExtendsSpecifier> {
+    alt((
+        map(pair(symbol(":"), keyword("initial")), |x| {
+            InitialOrExtendsSpecifier::Initial(Box::new(x))
+        }),
+        map(pair(symbol(":"), keyword("extends")), |x| {
+            InitialOrExtendsSpecifier::Extends(Box::new(x))
+        }),
+    ))(s)
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn final_specifier(s: Span) -> IResult<Span, FinalSpecifier> {
+    let (s, (a, b)) = since(
+        LanguageVersion::Ieee1800_2023,
+        pair(symbol(":"), keyword("final")),
+    )(s)?;
+    Ok((s, FinalSpecifier { nodes: (a, b) }))
+}
@@ -142,6 +142,7 @@ pub(crate) fn module_or_generate_item(s: Span) -> IResult<Span, ModuleOrGenerate
         module_or_generate_item_module_item,
         module_or_generate_item_gate,
         module_or_generate_item_udp,
+        module_or_generate_item_analog,
     ))(s)
 }
 
@@ -156,6 +157,17 @@ pub(crate) fn module_or_generate_item_parameter(s: Span) -> IResult<Span, Module
     ))
 }
 
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn module_or_generate_item_analog(s: Span) -> IResult<Span, ModuleOrGenerateItem> {
+    let (s, a) = many0(attribute_instance)(s)?;
+    let (s, b) = ams(analog_module_item)(s)?;
+    Ok((
+        s,
+        ModuleOrGenerateItem::Analog(Box::new(ModuleOrGenerateItemAnalog { nodes: (a, b) })),
+    ))
+}
+
 #[tracable_parser]
 #[packrat_parser]
 pub(crate) fn module_or_generate_item_gate(s: Span) -> IResult<Span, ModuleOrGenerateItem> {
@@ -288,6 +300,9 @@ pub(crate) fn non_port_module_item(s: Span) -> IResult<Span, NonPortModuleItem>
         map(timeunits_declaration, |x| {
             NonPortModuleItem::TimeunitsDeclaration(Box::new(x))
         }),
+        map(pair(macro_placeholder, opt(symbol(";"))), |x| {
+            NonPortModuleItem::TextMacroUsage(Box::new(x))
+        }),
     ))(s)
 }
 
@@ -22,6 +22,37 @@ macro_rules! test {
     };
 }
 
+macro_rules! strict_test {
+    ( $v:expr, $x:expr, $y:expr, $z:pat ) => {
+        nom_packrat::init!();
+        let info = SpanInfo {
+            language_version: Some($v),
+            ..SpanInfo::default()
+        };
+        let ret = all_consuming($x)(Span::new_extra($y, info));
+        if let $z = ret {
+        } else {
+            assert!(false, "{:?}", ret)
+        }
+    };
+}
+
+macro_rules! ams_test {
+    ( $v:expr, $x:expr, $y:expr, $z:pat ) => {
+        nom_packrat::init!();
+        let info = SpanInfo {
+            language_version: $v,
+            verilog_ams: true,
+            ..SpanInfo::default()
+        };
+        let ret = all_consuming($x)(Span::new_extra($y, info));
+        if let $z = ret {
+        } else {
+            assert!(false, "{:?}", ret)
+        }
+    };
+}
+
 macro_rules! error_test {
     ( $x:expr, $y:expr, $p:expr ) => {
         nom_packrat::init!();
@@ -15925,6 +15956,477 @@ mod spec {
     }
 }
 
+mod spec_1800_2023 {
+    use super::*;
+
+    #[test]
+    fn clause5() {
+        test!(
+            string_literal,
+            "\"\"\"Humpty Dumpty sat on a \"wall\".\"\"\"",
+            Ok((_, _))
+        );
+        test!(
+            string_literal,
+            "\"\"\"Humpty Dumpty\nhad a great \"fall\".\"\"\"",
+            Ok((_, _))
+        );
+        test!(
+            many1(module_item),
+            r##"initial $display("""Humpty Dumpty sat on a wall. \
+Humpty Dumpty had a great fall.""");"##,
+            Ok((_, _))
+        );
+    }
+
+    #[test]
+    fn clause7() {
+        test!(
+            many1(module_item),
+            r##"typedef union soft packed {
+                  bit [7:0] a;
+                  bit [3:0] b;
+                } u_t;"##,
+            Ok((_, _))
+        );
+        test!(
+            many1(module_item),
+            r##"int q [$][$];"##,
+            Ok((_, _))
+        );
+        test!(
+            many1(module_item),
+            r##"initial begin
+                  int A[] = '{1, 2, 3};
+                  int B[];
+                  B = A.map(a) with (a * 2);
+                end"##,
+            Ok((_, _))
+        );
+    }
+
+    #[test]
+    fn clause8() {
+        test!(
+            source_text,
+            r##"class :final Leaf extends Base;
+                endclass"##,
+            Ok((_, _))
+        );
+        test!(
+            source_text,
+            r##"class Base;
+                  virtual function :initial void f();
+                  endfunction
+                  virtual task :initial :final t();
+                  endtask
+                endclass
+                class Derived extends Base;
+                  virtual function :extends void f();
+                  endfunction
+                  function :final int g();
+                  endfunction
+                endclass"##,
+            Ok((_, _))
+        );
+        test!(
+            source_text,
+            r##"virtual class Base;
+                  pure virtual function :initial void f();
+                  extern virtual task :extends :final t();
+                endclass"##,
+            Ok((_, _))
+        );
+        test!(
+            source_text,
+            r##"interface class IC;
+                  pure virtual function :initial int get();
+                endclass
+                class C implements IC;
+                  virtual function :extends int get();
+                    return 0;
+                  endfunction
+                endclass"##,
+            Ok((_, _))
+        );
+        test!(
+            source_text,
+            r##"virtual class Base;
+                  function new(int a = 1, string b = "b");
+                  endfunction
+                  pure virtual function void f();
+                endclass
+                class Derived extends Base(default);
+                  extern function new(default);
+                  virtual function void f();
+                  endfunction
+                endclass
+                function Derived::new(default, int c = 0);
+                  super.new(default);
+                endfunction"##,
+            Ok((_, _))
+        );
+        test!(
+            source_text,
+            r##"interface class Base #(type T = int);
+                  pure virtual function T get();
+                endclass
+                class Outer #(type T = int);
+                  typedef Base#(T) Inner;
+                endclass
+                interface class IC extends Outer#(bit)::Inner, pkg::Other;
+                endclass
+                class C implements Outer#(byte)::Inner;
+                  virtual function byte get();
+                    return 0;
+                  endfunction
+                endclass"##,
+            Ok((_, _))
+        );
+    }
+
+    #[test]
+    fn clause13() {
+        test!(
+            source_text,
+            r##"module m;
+                  task automatic wait_and_set(ref static logic sig);
+                    #10 sig = 1;
+                  endtask
+                  function automatic int peek(const ref static int data[]);
+                    return data[0];
+                  endfunction
+                endmodule"##,
+            Ok((_, _))
+        );
+    }
+
+    #[test]
+    fn clause22() {
+        test!(
+            source_text,
+            r##"`begin_keywords "1800-2023"
+                module m;
+                  logic a;
+                endmodule
+                `end_keywords"##,
+            Ok((_, _))
+        );
+    }
+}
+
+mod strict {
+    use super::*;
+
+    #[test]
+    fn test_ieee1364_2001() {
+        let v = LanguageVersion::Ieee1364_2001;
+        strict_test!(
+            v,
+            source_text,
+            r##"module a(input clk, input [1:0] d, output reg [1:0] q);
+                  wire logic, bit;
+                  always @(posedge clk) q <= d;
+                  b u_b (.clk(clk));
+                endmodule"##,
+            Ok((_, _))
+        );
+        strict_test!(v, source_text, r##"module a; logic b; endmodule"##, Err(_));
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; always_ff @(posedge clk) b <= c; endmodule"##,
+            Err(_)
+        );
+        strict_test!(v, source_text, r##"interface a; endinterface"##, Err(_));
+        strict_test!(v, source_text, r##"module a; b_t c; endmodule"##, Err(_));
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; initial b += 1; endmodule"##,
+            Err(_)
+        );
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; initial b = '0; endmodule"##,
+            Err(_)
+        );
+        strict_test!(v, source_text, r##"module a; b u_b (.*); endmodule"##, Err(_));
+        strict_test!(
+            v,
+            source_text,
+            r##"`begin_keywords "1800-2017"
+                module a; logic b; always_ff @(posedge clk) b <= '0; endmodule
+                `end_keywords"##,
+            Ok((_, _))
+        );
+    }
+
+    #[test]
+    fn test_ieee1800_2005() {
+        let v = LanguageVersion::Ieee1800_2005;
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; logic b; always_ff @(posedge clk) b <= '0; endmodule"##,
+            Ok((_, _))
+        );
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; checker c; endchecker endmodule"##,
+            Err(_)
+        );
+        strict_test!(v, source_text, r##"module a; wire checker; endmodule"##, Ok((_, _)));
+    }
+
+    #[test]
+    fn test_ieee1800_2017() {
+        let v = LanguageVersion::Ieee1800_2017;
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; initial $display("""b"""); endmodule"##,
+            Err(_)
+        );
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; typedef union soft packed { bit b; } c_t; endmodule"##,
+            Err(_)
+        );
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; task automatic b(ref static logic c); endtask endmodule"##,
+            Err(_)
+        );
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; function automatic int b(const ref static int c); endfunction endmodule"##,
+            Err(_)
+        );
+        strict_test!(
+            v,
+            source_text,
+            r##"class a; virtual function :initial void b(); endfunction endclass"##,
+            Err(_)
+        );
+        strict_test!(v, source_text, r##"class :final a; endclass"##, Err(_));
+        strict_test!(
+            v,
+            source_text,
+            r##"class a extends b(default); function new(default); super.new(default); endfunction endclass"##,
+            Err(_)
+        );
+        strict_test!(
+            v,
+            source_text,
+            r##"interface class a extends b#(int)::c; endclass"##,
+            Err(_)
+        );
+        strict_test!(
+            v,
+            source_text,
+            r##"class a extends b(1); function new(int c); super.new(c); endfunction endclass"##,
+            Ok((_, _))
+        );
+        strict_test!(v, source_text, r##"module a; int b [$][$]; endmodule"##, Err(_));
+        strict_test!(v, source_text, r##"module a; int b [$:3][$]; endmodule"##, Err(_));
+        strict_test!(v, source_text, r##"module a; int b [$][3]; endmodule"##, Ok((_, _)));
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; initial c = b.map(d) with (d * 2); endmodule"##,
+            Err(_)
+        );
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; initial c = b.mapping(d) with (d * 2); endmodule"##,
+            Ok((_, _))
+        );
+        strict_test!(v, source_text, r##"module a; initial c = b.map(d); endmodule"##, Ok((_, _)));
+    }
+
+    #[test]
+    fn test_ieee1800_2023() {
+        let v = LanguageVersion::Ieee1800_2023;
+        strict_test!(
+            v,
+            source_text,
+            r##"class :final a extends b(default);
+                  function new(default);
+                    super.new(default);
+                  endfunction
+                  virtual function :extends void c(ref static logic d);
+                    $display("""e""");
+                  endfunction
+                endclass"##,
+            Ok((_, _))
+        );
+        strict_test!(
+            v,
+            source_text,
+            r##"module a; int b [$][$]; initial c = b.map(d) with (d.size()); endmodule"##,
+            Ok((_, _))
+        );
+    }
+}
+
+mod verilog_ams {
+    use super::*;
+
+    #[test]
+    fn test_disciplines_and_natures() {
+        ams_test!(
+            None,
+            source_text,
+            r##"nature Voltage
+                  units = "V";
+                  access = V;
+                  idt_nature = Flux;
+                  abstol = 1e-6;
+                endnature
+                nature Current : Voltage; access = I; endnature
+                discipline electrical
+                  potential Voltage;
+                  flow Current;
+                  domain continuous;
+                  potential.abstol = 1e-9;
+                enddiscipline"##,
+            Ok((_, _))
+        );
+    }
+
+    #[test]
+    fn test_analog_blocks() {
+        let v = Some(LanguageVersion::Ieee1364_2005);
+        ams_test!(
+            v,
+            source_text,
+            r##"module res(p, n);
+                  inout p, n;
+                  electrical p, n;
+                  ground gnd;
+                  branch (p, n) br;
+                  parameter real r = 1.0;
+                  analog begin
+                    I(p, n) <+ V(p, n) / r;
+                    I(br) <+ ddt(V(br)) + abs(V(p));
+                  end
+                  analog initial begin
+                    x = exp(2.0);
+                  end
+                endmodule"##,
+            Ok((_, _))
+        );
+        ams_test!(
+            v,
+            source_text,
+            r##"module a; analog function real f; input x; real x; f = x; endfunction endmodule"##,
+            Ok((_, _))
+        );
+        ams_test!(
+            v,
+            source_text,
+            r##"module a; always_ff @(posedge clk) b <= c; endmodule"##,
+            Err(_)
+        );
+        test!(
+            source_text,
+            r##"module a; analog begin I(p, n) <+ V(p, n); end endmodule"##,
+            Err(_)
+        );
+        test!(
+            source_text,
+            r##"`begin_keywords "VAMS-2.4"
+                module a; electrical p; analog I(p) <+ 1.0; endmodule
+                `end_keywords"##,
+            Ok((_, _))
+        );
+    }
+
+    #[test]
+    fn test_system_verilog() {
+        let v = Some(LanguageVersion::Ieee1800_2017);
+        ams_test!(
+            v,
+            source_text,
+            r##"module a(input logic clk, inout p);
+                  electrical p;
+                  always_ff @(posedge clk) b <= c;
+                  analog I(p) <+ V(p);
+                endmodule"##,
+            Ok((_, _))
+        );
+        ams_test!(
+            None,
+            source_text,
+            r##"module a; always_comb b = c; analog I(p) <+ 1.0; endmodule"##,
+            Ok((_, _))
+        );
+        ams_test!(
+            v,
+            source_text,
+            r##"module a; int analog; endmodule"##,
+            Err(_)
+        );
+        test!(source_text, r##"module a; int analog; endmodule"##, Ok((_, _)));
+    }
+
+    #[test]
+    fn test_data_types_as_disciplines() {
+        for x in &["logic", "bit", "int"] {
+            let src = format!("module a; {} b; endmodule", x);
+            let item = format!("{} b;", x);
+            ams_test!(
+                Some(LanguageVersion::Ieee1364_2005),
+                source_text,
+                &src,
+                Err(_)
+            );
+            ams_test!(
+                None,
+                module_or_generate_item,
+                &item,
+                Ok((_, ModuleOrGenerateItem::ModuleItem(_)))
+            );
+        }
+        ams_test!(
+            Some(LanguageVersion::Ieee1364_2005),
+            module_or_generate_item,
+            r##"electrical b;"##,
+            Ok((_, ModuleOrGenerateItem::Analog(_)))
+        );
+    }
+
+    #[test]
+    fn test_scale_factor() {
+        for x in &["1.5n", "10k", "2.2u", "1T", "3G", "4M", "5K", "6m", "7p", "8f", "9a"] {
+            ams_test!(None, number, x, Ok((_, Number::RealNumber(_))));
+        }
+        nom_packrat::init!();
+        let info = SpanInfo {
+            verilog_ams: true,
+            ..SpanInfo::default()
+        };
+        let ret = all_consuming(real_number)(Span::new_extra("1.5n", info));
+        assert!(matches!(ret, Ok((_, RealNumber::ScaleFactor(_)))));
+        ams_test!(None, number, "1.5nm", Err(_));
+        ams_test!(None, primary, "1ns", Ok((_, Primary::PrimaryLiteral(_))));
+        ams_test!(
+            None,
+            source_text,
+            r##"module a; analog I(p) <+ V(p) / 1.5k + 10n; endmodule"##,
+            Ok((_, _))
+        );
+        test!(number, "1.5n", Err(_));
+    }
+}
+
 mod error {
     use super::*;
 
@@ -1,4 +1,5 @@
 use crate::*;
+use std::collections::HashMap;
 
 // -----------------------------------------------------------------------------
 
@@ -72,6 +73,9 @@ pub(crate) fn symbol_exact<'a>(t: &'a str) -> impl FnMut(Span<'a>) -> IResult<Sp
 #[cfg(not(feature = "trace"))]
 pub(crate) fn keyword<'a>(t: &'a str) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, Keyword> {
     move |s: Span<'a>| {
+        if !is_available_keyword(&s, t) {
+            return Err(Err::Error(make_error(s, ErrorKind::Verify)));
+        }
         let (s, x) = map(
             ws(alt((
                 all_consuming(map(tag(t), into_locate)),
@@ -88,6 +92,9 @@ pub(crate) fn keyword<'a>(t: &'a str) -> impl FnMut(Span<'a>) -> IResult<Span<'a
     move |s: Span<'a>| {
         let (depth, s) = nom_tracable::forward_trace(s, &format!("keyword(\"{}\")", t));
         let body = || {
+            if !is_available_keyword(&s, t) {
+                return Err(Err::Error(make_error(s, ErrorKind::Verify)));
+            }
             let (s, x) = map(
                 ws(alt((
                     all_consuming(map(tag(t), into_locate)),
@@ -324,7 +331,13 @@ pub(crate) fn white_space(s: Span) -> IResult<Span, WhiteSpace> {
                 WhiteSpace::Comment(Box::new(x))
             }),
             map(
-                preceded(peek(char('`')), compiler_directive_without_resetall),
+                verify(
+                    preceded(peek(char('`')), compiler_directive_without_resetall),
+                    |x: &CompilerDirective| {
+                        !(s.extra.macro_placeholder
+                            && matches!(x, CompilerDirective::TextMacroUsage(_)))
+                    },
+                ),
                 |x| WhiteSpace::CompilerDirective(Box::new(x)),
             ),
         ))(s)
@@ -365,6 +378,8 @@ pub(crate) enum Version {
     Ieee1800_2009,
     Ieee1800_2012,
     Ieee1800_2017,
+    Ieee1800_2023,
+    VerilogAms2_4,
     Directive,
 }
 
@@ -386,6 +401,8 @@ pub(crate) fn begin_keywords(version: &str) {
         "1800-2009" => current_version.borrow_mut().push(Version::Ieee1800_2009),
         "1800-2012" => current_version.borrow_mut().push(Version::Ieee1800_2012),
         "1800-2017" => current_version.borrow_mut().push(Version::Ieee1800_2017),
+        "1800-2023" => current_version.borrow_mut().push(Version::Ieee1800_2023),
+        "VAMS-2.4" => current_version.borrow_mut().push(Version::VerilogAms2_4),
         "directive" => current_version.borrow_mut().push(Version::Directive),
         _ => (),
     });
@@ -424,25 +441,172 @@ pub(crate) fn concat<'a>(a: Span<'a>, b: Span<'a>) -> Option<Span<'a>> {
     }
 }
 
+// Bit of each version in the values of `KEYWORD_VERSIONS`
+fn version_bit(version: Version) -> u16 {
+    1 << version as u16
+}
+
+fn keyword_tables() -> [(Version, &'static [&'static str]); 11] {
+    [
+        (Version::Ieee1364_1995, KEYWORDS_1364_1995),
+        (Version::Ieee1364_2001, KEYWORDS_1364_2001),
+        (Version::Ieee1364_2001Noconfig, KEYWORDS_1364_2001_NOCONFIG),
+        (Version::Ieee1364_2005, KEYWORDS_1364_2005),
+        (Version::Ieee1800_2005, KEYWORDS_1800_2005),
+        (Version::Ieee1800_2009, KEYWORDS_1800_2009),
+        (Version::Ieee1800_2012, KEYWORDS_1800_2012),
+        (Version::Ieee1800_2017, KEYWORDS_1800_2017),
+        (Version::Ieee1800_2023, KEYWORDS_1800_2023),
+        (Version::VerilogAms2_4, KEYWORDS_VAMS_2_4),
+        (Version::Directive, KEYWORDS_DIRECTIVE),
+    ]
+}
+
+/// Whether `s` is reserved as a keyword by any version of the language
+pub fn is_reserved_word(s: &str) -> bool {
+    keyword_tables()
+        .iter()
+        .any(|(version, keywords)| !matches!(version, Version::Directive) && keywords.contains(&s))
+}
+
+thread_local!(
+    // Keyword to the set of versions reserving it
+    static KEYWORD_VERSIONS: HashMap<&'static str, u16> = {
+        let mut ret = HashMap::new();
+        for (version, keywords) in keyword_tables().iter() {
+            for k in keywords.iter() {
+                *ret.entry(*k).or_insert(0) |= version_bit(*version);
+            }
+        }
+        ret
+    }
+);
+
+// Whether `t` is reserved or un-reserved by `SpanInfo::keyword_overrides`.
+// Overrides don't apply to compiler directives.
+fn overridden(s: &Span, t: &str) -> Option<bool> {
+    let overrides = s.extra.keyword_overrides?;
+    if matches!(current_version(), Some(Version::Directive)) {
+        None
+    } else if overrides.unreserved.iter().any(|x| x == t) {
+        Some(false)
+    } else if overrides.reserved.iter().any(|x| x == t) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+fn keyword_version(s: &Span) -> Version {
+    match current_version() {
+        Some(x) => x,
+        None => match s.extra.language_version {
+            Some(LanguageVersion::Ieee1364_1995) => Version::Ieee1364_1995,
+            Some(LanguageVersion::Ieee1364_2001) => Version::Ieee1364_2001,
+            Some(LanguageVersion::Ieee1364_2005) => Version::Ieee1364_2005,
+            Some(LanguageVersion::Ieee1800_2005) => Version::Ieee1800_2005,
+            Some(LanguageVersion::Ieee1800_2009) => Version::Ieee1800_2009,
+            Some(LanguageVersion::Ieee1800_2012) => Version::Ieee1800_2012,
+            Some(LanguageVersion::Ieee1800_2017) => Version::Ieee1800_2017,
+            Some(LanguageVersion::Ieee1800_2023) => Version::Ieee1800_2023,
+            None => Version::Ieee1800_2017,
+        },
+    }
+}
+
+// Whether `t` is a keyword of `version` without overrides
+fn is_reserved(t: &str, version: Version) -> bool {
+    KEYWORD_VERSIONS.with(|x| match x.get(t) {
+        Some(versions) => versions & version_bit(version) != 0,
+        None => false,
+    })
+}
+
+// Whether `t` is a keyword added by Verilog-AMS to IEEE 1364-2005
+fn is_ams_reserved(t: &str) -> bool {
+    is_reserved(t, Version::VerilogAms2_4) && !is_reserved(t, Version::Ieee1364_2005)
+}
+
 pub(crate) fn is_keyword(s: &Span) -> bool {
-    let keywords = match current_version() {
-        Some(Version::Ieee1364_1995) => KEYWORDS_1364_1995,
-        Some(Version::Ieee1364_2001) => KEYWORDS_1364_2001,
-        Some(Version::Ieee1364_2001Noconfig) => KEYWORDS_1364_2001_NOCONFIG,
-        Some(Version::Ieee1364_2005) => KEYWORDS_1364_2005,
-        Some(Version::Ieee1800_2005) => KEYWORDS_1800_2005,
-        Some(Version::Ieee1800_2009) => KEYWORDS_1800_2009,
-        Some(Version::Ieee1800_2012) => KEYWORDS_1800_2012,
-        Some(Version::Ieee1800_2017) => KEYWORDS_1800_2017,
-        Some(Version::Directive) => KEYWORDS_DIRECTIVE,
-        None => KEYWORDS_1800_2017,
-    };
-    for k in keywords {
-        if s.fragment() == k {
-            return true;
+    let t = s.fragment();
+    match overridden(s, t) {
+        Some(x) => x,
+        None => is_reserved(t, keyword_version(s)) || (is_ams_reserved(t) && is_ams(s)),
+    }
+}
+
+/// The version whose grammar is enforced at `s`.
+///
+/// This is `None` unless strict parsing was requested through
+/// `SpanInfo::language_version`. Inside `begin_keywords` regions the version
+/// of the region is enforced instead.
+pub(crate) fn strict_version(s: &Span) -> Option<LanguageVersion> {
+    s.extra.language_version?;
+    match current_version() {
+        Some(Version::Ieee1364_1995) => Some(LanguageVersion::Ieee1364_1995),
+        Some(Version::Ieee1364_2001) => Some(LanguageVersion::Ieee1364_2001),
+        Some(Version::Ieee1364_2001Noconfig) => Some(LanguageVersion::Ieee1364_2001),
+        Some(Version::Ieee1364_2005) => Some(LanguageVersion::Ieee1364_2005),
+        Some(Version::Ieee1800_2005) => Some(LanguageVersion::Ieee1800_2005),
+        Some(Version::Ieee1800_2009) => Some(LanguageVersion::Ieee1800_2009),
+        Some(Version::Ieee1800_2012) => Some(LanguageVersion::Ieee1800_2012),
+        Some(Version::Ieee1800_2017) => Some(LanguageVersion::Ieee1800_2017),
+        Some(Version::Ieee1800_2023) => Some(LanguageVersion::Ieee1800_2023),
+        // Verilog-AMS 2.4 extends IEEE 1364-2005
+        Some(Version::VerilogAms2_4) => Some(LanguageVersion::Ieee1364_2005),
+        Some(Version::Directive) => None,
+        None => s.extra.language_version,
+    }
+}
+
+/// Whether `t` can be matched as a keyword at `s`.
+///
+/// Under strict parsing, keywords reserved only by later versions are not
+/// keywords; they are ordinary identifiers.
+pub(crate) fn is_available_keyword(s: &Span, t: &str) -> bool {
+    if overridden(s, t) == Some(false) {
+        return false;
+    }
+    if strict_version(s).is_none() || !is_reserved(t, Version::Ieee1800_2023) {
+        return true;
+    }
+    is_reserved(t, keyword_version(s))
+}
+
+/// Apply `f` only if strict parsing is disabled or enforces `version` or later.
+pub(crate) fn since<'a, O, F>(
+    version: LanguageVersion,
+    mut f: F,
+) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, O>
+where
+    F: FnMut(Span<'a>) -> IResult<Span<'a>, O>,
+{
+    move |s: Span<'a>| match strict_version(&s) {
+        Some(x) if x < version => Err(Err::Error(make_error(s, ErrorKind::Verify))),
+        _ => f(s),
+    }
+}
+
+/// Whether the Verilog-AMS extension is enabled at `s`.
+///
+/// It is enabled by `SpanInfo::verilog_ams` or inside
+/// `begin_keywords "VAMS-2.4"` regions.
+pub(crate) fn is_ams(s: &Span) -> bool {
+    s.extra.verilog_ams || matches!(current_version(), Some(Version::VerilogAms2_4))
+}
+
+/// Apply `f` only if the Verilog-AMS extension is enabled.
+pub(crate) fn ams<'a, O, F>(mut f: F) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, O>
+where
+    F: FnMut(Span<'a>) -> IResult<Span<'a>, O>,
+{
+    move |s: Span<'a>| {
+        if is_ams(&s) {
+            f(s)
+        } else {
+            Err(Err::Error(make_error(s, ErrorKind::Verify)))
         }
     }
-    false
 }
 
 pub(crate) fn into_locate(s: Span) -> Locate {
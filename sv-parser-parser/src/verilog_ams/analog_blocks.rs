@@ -0,0 +1,88 @@
+use crate::*;
+
+// -----------------------------------------------------------------------------
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn analog_module_item(s: Span) -> IResult<Span, AnalogModuleItem> {
+    alt((
+        map(analog_construct, |x| {
+            AnalogModuleItem::AnalogConstruct(Box::new(x))
+        }),
+        map(analog_function_declaration, |x| {
+            AnalogModuleItem::AnalogFunctionDeclaration(Box::new(x))
+        }),
+        map(branch_declaration, |x| {
+            AnalogModuleItem::BranchDeclaration(Box::new(x))
+        }),
+        map(ground_declaration, |x| {
+            AnalogModuleItem::GroundDeclaration(Box::new(x))
+        }),
+        map(net_discipline_declaration, |x| {
+            AnalogModuleItem::NetDisciplineDeclaration(Box::new(x))
+        }),
+    ))(s)
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn analog_construct(s: Span) -> IResult<Span, AnalogConstruct> {
+    alt((
+        map(
+            triple(keyword("analog"), keyword("initial"), statement),
+            |x| AnalogConstruct::AnalogInitial(Box::new(x)),
+        ),
+        map(pair(keyword("analog"), statement), |x| {
+            AnalogConstruct::Analog(Box::new(x))
+        }),
+    ))(s)
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn analog_function_declaration(s: Span) -> IResult<Span, AnalogFunctionDeclaration> {
+    let (s, a) = keyword("analog")(s)?;
+    let (s, b) = function_declaration(s)?;
+    Ok((s, AnalogFunctionDeclaration { nodes: (a, b) }))
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn contribution_statement(s: Span) -> IResult<Span, ContributionStatement> {
+    let (s, a) = branch_probe_function_call(s)?;
+    let (s, b) = symbol("<+")(s)?;
+    let (s, c) = expression(s)?;
+    let (s, d) = symbol(";")(s)?;
+    Ok((
+        s,
+        ContributionStatement {
+            nodes: (a, b, c, d),
+        },
+    ))
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn branch_probe_function_call(s: Span) -> IResult<Span, BranchProbeFunctionCall> {
+    let (s, a) = nature_access_identifier(s)?;
+    let (s, b) = paren(list(symbol(","), net_lvalue))(s)?;
+    Ok((s, BranchProbeFunctionCall { nodes: (a, b) }))
+}
+
+#[tracable_parser]
+#[packrat_parser]
+pub(crate) fn analog_builtin_call(s: Span) -> IResult<Span, AnalogBuiltinCall> {
+    let (s, a) = analog_builtin_function(s)?;
+    let (s, b) = opt(paren(list_of_arguments))(s)?;
+    Ok((s, AnalogBuiltinCall { nodes: (a, b) }))
+}
+
+#[tracable_parser]
+pub(crate) fn analog_builtin_function(s: Span) -> IResult<Span, Keyword> {
+    for x in ANALOG_BUILTIN_FUNCTIONS {
+        if let Ok(ret) = keyword(x)(s) {
+            return Ok(ret);
+        }
+    }
+    Err(Err::Error(make_error(s, ErrorKind::Tag)))
+}
@@ -0,0 +1,4 @@
+pub mod analog_blocks;
+pub mod disciplines_and_natures;
+pub(crate) use analog_blocks::*;
+pub(crate) use disciplines_and_natures::*;
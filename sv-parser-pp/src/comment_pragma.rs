@@ -0,0 +1,127 @@
+// Tool specific directives written in comments.
+// They are not part of IEEE1800-2017, but synthesis, lint and coverage tools
+// conventionally recognize them by the leading word of the comment.
+const TOOLS: &[&str] = &[
+    "synopsys",
+    "synthesis",
+    "pragma",
+    "verilator",
+    "cadence",
+    "ambit",
+    "xilinx",
+    "altera",
+    "lint",
+    "coverage",
+];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CommentPragmaKind {
+    TranslateOff,
+    TranslateOn,
+    CoverageOff,
+    CoverageOn,
+    LintOff,
+    LintOn,
+    FullCase,
+    ParallelCase,
+    Other,
+}
+
+/// A directive written in a comment, like `// synopsys translate_off`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommentPragma {
+    /// Leading word of the comment, e.g. `synopsys` or `verilator`.
+    pub tool: String,
+    /// Word following the tool, e.g. `translate_off` or `lint_off`.
+    pub directive: String,
+    /// Remaining words, e.g. `WIDTH` of `lint_off WIDTH`.
+    pub arguments: Vec<String>,
+    pub kind: CommentPragmaKind,
+}
+
+impl CommentPragma {
+    /// Parse the text of a comment including its delimiters.
+    /// Returns `None` if the comment doesn't start with a known tool name.
+    pub fn parse(comment: &str) -> Option<Self> {
+        let body = if let Some(x) = comment.strip_prefix("//") {
+            x
+        } else {
+            let x = comment.strip_prefix("/*")?;
+            x.strip_suffix("*/").unwrap_or(x)
+        };
+
+        let mut words = body.split_whitespace();
+        let tool = words.next()?;
+        if !TOOLS.contains(&tool.to_ascii_lowercase().as_str()) {
+            return None;
+        }
+        // `// coverage off` has no tool name.
+        let (tool, directive) = if tool.eq_ignore_ascii_case("coverage") {
+            ("", tool)
+        } else {
+            (tool, words.next()?)
+        };
+        let arguments: Vec<String> = words.map(String::from).collect();
+
+        let switch = |on, off| match arguments.first().map(|x| x.to_ascii_lowercase()) {
+            Some(ref x) if x == "on" => on,
+            Some(ref x) if x == "off" => off,
+            _ => CommentPragmaKind::Other,
+        };
+        let kind = match directive.to_ascii_lowercase().as_str() {
+            "translate_off" | "synthesis_off" => CommentPragmaKind::TranslateOff,
+            "translate_on" | "synthesis_on" => CommentPragmaKind::TranslateOn,
+            "translate" | "synthesis" => switch(
+                CommentPragmaKind::TranslateOn,
+                CommentPragmaKind::TranslateOff,
+            ),
+            "coverage_off" => CommentPragmaKind::CoverageOff,
+            "coverage_on" => CommentPragmaKind::CoverageOn,
+            "coverage" => switch(
+                CommentPragmaKind::CoverageOn,
+                CommentPragmaKind::CoverageOff,
+            ),
+            "lint_off" => CommentPragmaKind::LintOff,
+            "lint_on" => CommentPragmaKind::LintOn,
+            "lint_checking" => switch(CommentPragmaKind::LintOn, CommentPragmaKind::LintOff),
+            "full_case" => CommentPragmaKind::FullCase,
+            "parallel_case" => CommentPragmaKind::ParallelCase,
+            _ => CommentPragmaKind::Other,
+        };
+
+        Some(CommentPragma {
+            tool: String::from(tool),
+            directive: String::from(directive),
+            arguments,
+            kind,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let x = CommentPragma::parse("// synopsys translate_off").unwrap();
+        assert_eq!(x.tool, "synopsys");
+        assert_eq!(x.kind, CommentPragmaKind::TranslateOff);
+
+        let x = CommentPragma::parse("/* verilator lint_off WIDTH */").unwrap();
+        assert_eq!(x.tool, "verilator");
+        assert_eq!(x.directive, "lint_off");
+        assert_eq!(x.arguments, vec![String::from("WIDTH")]);
+        assert_eq!(x.kind, CommentPragmaKind::LintOff);
+
+        let x = CommentPragma::parse("// pragma coverage off").unwrap();
+        assert_eq!(x.kind, CommentPragmaKind::CoverageOff);
+
+        let x = CommentPragma::parse("// synthesis full_case parallel_case").unwrap();
+        assert_eq!(x.kind, CommentPragmaKind::FullCase);
+        assert_eq!(x.arguments, vec![String::from("parallel_case")]);
+
+        assert!(CommentPragma::parse("// synthesize the adder").is_none());
+        assert!(CommentPragma::parse("/* comment */").is_none());
+    }
+}
@@ -1,39 +1,181 @@
+use crate::comment_pragma::{CommentPragma, CommentPragmaKind};
 use crate::range::Range;
 use nom::combinator::all_consuming;
 use nom_greedyerror::error_position;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::convert::TryInto;
 use std::fs::File;
 use std::hash::BuildHasher;
 use std::io::{BufReader, Read};
 use std::path::{Path, PathBuf};
-use sv_parser_error::Error;
+use sv_parser_error::{Error, Warning};
 use sv_parser_parser::{pp_parser, Span, SpanInfo};
 use sv_parser_syntaxtree::{
-    IncludeCompilerDirective, Locate, NodeEvent, RefNode, SourceDescription, TextMacroUsage,
-    WhiteSpace,
+    IncludeCompilerDirective, Keyword, Locate, NodeEvent, RefNode, SourceDescription, Symbol,
+    TextMacroUsage, WhiteSpace,
 };
 use std::collections::hash_map::RandomState;
 
 const RECURSIVE_LIMIT: usize = 64;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct PreprocessedText {
     text: String,
     origins: BTreeMap<Range, Origin>,
+    conditionals: Vec<Conditional>,
+    macro_definitions: Vec<MacroDefinition>,
+    macro_usages: Vec<MacroUsage>,
+    macro_kills: Vec<MacroKill>,
+    includes: Vec<Include>,
+    expansions: Vec<(Range, Expansion)>,
+    warnings: Vec<Warning>,
+    // Macro identifiers referenced in the text of expanded macros, which
+    // are not listed in `macro_usages`
+    nested_references: BTreeSet<String>,
+    // Origin of the translate_off pragma whose region is still open
+    translate_off: Option<(PathBuf, usize)>,
+    // Text of each source file to get line and column numbers of origins
+    sources: BTreeMap<PathBuf, String>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Origin {
     range: Range,
     origin: Option<(PathBuf, Range)>,
 }
 
+/// A `ifdef`/`ifndef` block and the branches it selected between.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Conditional {
+    pub path: PathBuf,
+    /// Range from the leading `ifdef`/`ifndef` to the end of `endif`.
+    pub range: Range,
+    pub branches: Vec<ConditionalBranch>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ConditionalKind {
+    Ifdef,
+    Ifndef,
+    Elsif,
+    Else,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConditionalBranch {
+    pub kind: ConditionalKind,
+    /// Controlling macro identifier, `None` for `else`.
+    pub identifier: Option<String>,
+    /// Whether the identifier was defined when the branch was evaluated.
+    pub defined: bool,
+    pub taken: bool,
+    /// Range from the branch directive up to the next directive of the block.
+    pub range: Range,
+}
+
+/// A `define directive and what happened to it afterwards.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MacroDefinition {
+    pub define: Define,
+    /// Whether the macro was later removed by `undef or `undefineall.
+    pub undefined: bool,
+    /// Whether the macro was later defined again.
+    pub redefined: bool,
+}
+
+/// A text macro usage and the definition it resolved to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MacroUsage {
+    pub identifier: String,
+    pub path: PathBuf,
+    pub range: Range,
+    pub arguments: Vec<Option<String>>,
+    /// Definition active at the usage, `None` if the macro was not defined
+    /// or was predefined without a `Define`.
+    pub define: Option<Define>,
+}
+
+/// An `include directive or a text macro usage which produced a part of
+/// preprocessed text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Expansion {
+    /// Included file and the location of the `include directive
+    Include(PathBuf, (PathBuf, usize)),
+    /// Macro identifier and the location of the usage
+    Macro(String, (PathBuf, usize)),
+}
+
+// An `include and whether its expansion had any content.
+#[derive(Clone, Debug)]
+struct Include {
+    path: PathBuf,
+    // `path` resolved once to compare includes
+    canonical: PathBuf,
+    origin: (PathBuf, usize),
+    blank: bool,
+}
+
+// Removal of a macro whose definition may live in an including file.
+#[derive(Clone, Debug)]
+enum MacroKill {
+    Undefine(String),
+    Redefine(String),
+    UndefineAll,
+}
+
+impl Conditional {
+    fn new<T: AsRef<Path>>(path: T, begin: &Symbol, end: &Keyword) -> Self {
+        let end = end.nodes.0.offset + end.nodes.0.len;
+        Conditional {
+            path: PathBuf::from(path.as_ref()),
+            range: Range::new(begin.nodes.0.offset, end),
+            branches: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        kind: ConditionalKind,
+        identifier: Option<String>,
+        defined: bool,
+        taken: bool,
+        begin: &Symbol,
+    ) {
+        let begin = begin.nodes.0.offset;
+        if let Some(last) = self.branches.last_mut() {
+            last.range.end = begin;
+        }
+        self.branches.push(ConditionalBranch {
+            kind,
+            identifier,
+            defined,
+            taken,
+            range: Range::new(begin, begin),
+        });
+    }
+
+    fn close(&mut self, end: &Symbol) {
+        if let Some(last) = self.branches.last_mut() {
+            last.range.end = end.nodes.0.offset;
+        }
+    }
+}
+
 impl PreprocessedText {
     fn new() -> Self {
         PreprocessedText {
             text: String::new(),
             origins: BTreeMap::new(),
+            conditionals: Vec::new(),
+            macro_definitions: Vec::new(),
+            macro_usages: Vec::new(),
+            macro_kills: Vec::new(),
+            includes: Vec::new(),
+            expansions: Vec::new(),
+            warnings: Vec::new(),
+            nested_references: BTreeSet::new(),
+            translate_off: None,
+            sources: BTreeMap::new(),
         }
     }
 
@@ -53,6 +195,29 @@ impl PreprocessedText {
         self.origins.insert(range, origin);
     }
 
+    fn push_macro_expansion(
+        &mut self,
+        s: &str,
+        origin: Option<(PathBuf, Range)>,
+        usage: &MacroUsage,
+    ) {
+        let base = self.text.len();
+        self.push(s, origin);
+        let expansion = Expansion::Macro(
+            usage.identifier.clone(),
+            (usage.path.clone(), usage.range.begin),
+        );
+        self.expansions
+            .push((Range::new(base, self.text.len()), expansion));
+    }
+
+    // Called before merging the included text
+    fn push_include_expansion(&mut self, path: &Path, origin: &(PathBuf, usize), len: usize) {
+        let base = self.text.len();
+        let expansion = Expansion::Include(PathBuf::from(path), origin.clone());
+        self.expansions.push((Range::new(base, base + len), expansion));
+    }
+
     fn merge(&mut self, other: PreprocessedText) {
         let base = self.text.len();
         self.text.push_str(&other.text);
@@ -61,12 +226,158 @@ impl PreprocessedText {
             origin.range.offset(base);
             self.origins.insert(range, origin);
         }
+        self.conditionals.extend(other.conditionals);
+        self.macro_usages.extend(other.macro_usages);
+        // Kills not resolved in the included file refer to macros defined
+        // before the `include, so they are applied before appending its definitions.
+        for kill in other.macro_kills {
+            self.kill_macro(kill);
+        }
+        self.macro_definitions.extend(other.macro_definitions);
+        // Repeats inside the included file were already reported by it, so
+        // only the first include of each file is checked against this file.
+        let count = self.includes.len();
+        for (i, include) in other.includes.iter().enumerate() {
+            let first = !other.includes[..i]
+                .iter()
+                .any(|x| x.canonical == include.canonical);
+            if first {
+                self.check_include(include, count);
+            }
+        }
+        self.includes.extend(other.includes);
+        for (mut range, expansion) in other.expansions {
+            range.offset(base);
+            self.expansions.push((range, expansion));
+        }
+        self.warnings.extend(other.warnings);
+        self.nested_references.extend(other.nested_references);
+        // A translate_off region left open by an included file continues
+        // after the `include directive.
+        self.translate_off = other.translate_off;
+        for (path, source) in other.sources {
+            self.sources.entry(path).or_insert(source);
+        }
+    }
+
+    fn push_include(&mut self, include: Include) {
+        self.check_include(&include, self.includes.len());
+        self.includes.push(include);
+    }
+
+    // Check `include against the first `count` includes
+    fn check_include(&mut self, include: &Include, count: usize) {
+        // IEEE1800-2017 Clause 22.4 allows including a file more than once,
+        // but without an include guard its contents are duplicated.
+        let repeated = self.includes[..count]
+            .iter()
+            .any(|x| x.canonical == include.canonical);
+        if !include.blank && repeated {
+            self.warnings.push(Warning::IncludeRepeated(
+                include.path.clone(),
+                include.origin.clone(),
+            ));
+        }
+    }
+
+    fn check_undefined(&mut self, id: &str, defines: &Defines, origin: (PathBuf, usize)) {
+        if let Some(similar) = similar_define(id, defines) {
+            self.warnings
+                .push(Warning::DefineSimilar(String::from(id), similar, origin));
+        }
+    }
+
+    fn kill_macro(&mut self, kill: MacroKill) {
+        let mut found = false;
+        for x in self.macro_definitions.iter_mut().rev() {
+            if x.undefined || x.redefined {
+                continue;
+            }
+            match kill {
+                MacroKill::Undefine(ref id) if *id == x.define.identifier => {
+                    x.undefined = true;
+                    found = true;
+                    break;
+                }
+                MacroKill::Redefine(ref id) if *id == x.define.identifier => {
+                    x.redefined = true;
+                    found = true;
+                    break;
+                }
+                MacroKill::UndefineAll => {
+                    x.undefined = true;
+                }
+                _ => (),
+            }
+        }
+        if !found {
+            self.macro_kills.push(kill);
+        }
     }
 
     pub fn text(&self) -> &str {
         &self.text
     }
 
+    /// Get all conditional blocks evaluated during preprocess, including
+    /// those in included files, in the order they were encountered.
+    /// Blocks nested inside a skipped branch are not evaluated and not listed.
+    pub fn conditionals(&self) -> &[Conditional] {
+        &self.conditionals
+    }
+
+    /// Get all `define directives processed, including those in included files.
+    pub fn macro_definitions(&self) -> &[MacroDefinition] {
+        &self.macro_definitions
+    }
+
+    /// Get all text macro usages expanded, including those in included files.
+    /// Usages inside the text of other macros are not listed.
+    pub fn macro_usages(&self) -> &[MacroUsage] {
+        &self.macro_usages
+    }
+
+    /// Get paths of all included files, including those included from other
+    /// included files, without duplicates in the order they were encountered.
+    pub fn includes(&self) -> Vec<&PathBuf> {
+        let mut ret: Vec<&PathBuf> = Vec::new();
+        for x in &self.includes {
+            if !ret.contains(&&x.path) {
+                ret.push(&x.path);
+            }
+        }
+        ret
+    }
+
+    /// Get non-fatal diagnostics found during preprocess.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Get the text of a source file preprocessed directly or through an
+    /// `include directive, which offsets of origins point into.
+    pub fn source(&self, path: &Path) -> Option<&str> {
+        self.sources.get(path).map(|x| x.as_str())
+    }
+
+    /// Get `include directives and text macro usages which produced `pos`,
+    /// from the innermost.
+    pub fn expansions(&self, pos: usize) -> Vec<&Expansion> {
+        self.expansions
+            .iter()
+            .rev()
+            .filter(|(range, _)| range.begin <= pos && pos < range.end)
+            .map(|(_, x)| x)
+            .collect()
+    }
+
+    /// Get all `include directives and text macro usages with the ranges of
+    /// preprocessed text they produced. Usages inside the text of other
+    /// macros are not listed.
+    pub fn expansion_ranges(&self) -> &[(Range, Expansion)] {
+        &self.expansions
+    }
+
     pub fn origin(&self, pos: usize) -> Option<(&PathBuf, usize)> {
         let origin = self.origins.get(&Range::new(pos, pos + 1));
         if let Some(origin) = origin {
@@ -82,13 +393,26 @@ impl PreprocessedText {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Define {
     pub identifier: String,
     pub arguments: Vec<(String, Option<String>)>,
     pub text: Option<DefineText>,
+    /// Location of the `define directive, `None` for predefined macros.
+    pub origin: Option<(PathBuf, Range)>,
 }
 
+// Definitions are compared without their locations.
+impl PartialEq for Define {
+    fn eq(&self, other: &Self) -> bool {
+        self.identifier == other.identifier
+            && self.arguments == other.arguments
+            && self.text.as_ref().map(|x| &x.text) == other.text.as_ref().map(|x| &x.text)
+    }
+}
+
+impl Eq for Define {}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DefineText {
     pub text: String,
@@ -105,6 +429,7 @@ impl Define {
             identifier: ident,
             arguments: args,
             text,
+            origin: None,
         }
     }
 }
@@ -117,19 +442,32 @@ impl DefineText {
 
 pub type Defines<V=RandomState> = HashMap<String, Option<Define>, V>;
 
+/// Options of `preprocess` and `preprocess_str`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PreprocessOptions {
+    /// Remove comments and the whitespace around them
+    pub strip_comments: bool,
+    /// Keep `include directives instead of reading the included files
+    pub ignore_include: bool,
+    /// Keep usages of undefined macros as text with a warning instead of
+    /// failing with `Error::DefineNotFound`
+    pub allow_undefined: bool,
+    /// Remove the text between `translate_off` and `translate_on` pragma
+    /// comments
+    pub strip_translate_off: bool,
+}
+
 pub fn preprocess<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
     path: T,
     pre_defines: &Defines<V>,
     include_paths: &[U],
-    strip_comments: bool,
-    ignore_include: bool,
+    options: &PreprocessOptions,
 ) -> Result<(PreprocessedText, Defines), Error> {
     preprocess_inner(
         path,
         pre_defines,
         include_paths,
-        strip_comments,
-        ignore_include,
+        options,
         0, // include_depth
     )
 }
@@ -138,8 +476,7 @@ fn preprocess_inner<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
     path: T,
     pre_defines: &Defines<V>,
     include_paths: &[U],
-    strip_comments: bool,
-    ignore_include: bool,
+    options: &PreprocessOptions,
     include_depth: usize,
 ) -> Result<(PreprocessedText, Defines), Error> {
 
@@ -158,8 +495,7 @@ fn preprocess_inner<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
             path,
             pre_defines,
             include_paths,
-            ignore_include,
-            strip_comments,
+            options,
             0, // resolve_depth
             include_depth,
         )
@@ -199,8 +535,7 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
     path: T,
     pre_defines: &Defines<V>,
     include_paths: &[U],
-    ignore_include: bool,
-    strip_comments: bool,
+    options: &PreprocessOptions,
     resolve_depth: usize,
     include_depth: usize,
 ) -> Result<(PreprocessedText, Defines), Error> {
@@ -248,6 +583,7 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
             identifier: k.to_string(),
             arguments: Vec::new(),
             text: Some(DefineText {text: v.to_string(), origin: None}),
+            origin: None,
         };
         defines.insert(k.to_string(), Some(define));
     }
@@ -276,6 +612,10 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
     })?;
 
     let mut ret = PreprocessedText::new();
+    // The text of a macro is not a source file.
+    if resolve_depth == 0 {
+        ret.sources.insert(PathBuf::from(path.as_ref()), String::from(s));
+    }
 
     for n in pp_text.into_iter().event() {
         match n.clone() {
@@ -294,6 +634,29 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
             continue;
         }
 
+        // Synthesis tools ignore the text between translate_off and
+        // translate_on comment pragmas. The text is handled as one stream, so
+        // an `include inside a region is skipped and a region opened by an
+        // included file extends into the including file.
+        if options.strip_translate_off {
+            if let NodeEvent::Enter(RefNode::Comment(x)) = &n {
+                let locate: Locate = (*x).try_into().unwrap();
+                match CommentPragma::parse(locate.str(s)).map(|x| x.kind) {
+                    Some(CommentPragmaKind::TranslateOff) if ret.translate_off.is_none() => {
+                        ret.translate_off = Some((PathBuf::from(path.as_ref()), locate.offset));
+                    }
+                    Some(CommentPragmaKind::TranslateOn) if ret.translate_off.is_some() => {
+                        ret.translate_off = None;
+                        continue;
+                    }
+                    _ => (),
+                }
+            }
+            if ret.translate_off.is_some() {
+                continue;
+            }
+        }
+
         match n.clone() {
             NodeEvent::Enter(RefNode::SourceDescriptionNotDirective(x)) => {
                 let locate: Locate = x.try_into().unwrap();
@@ -415,6 +778,16 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
             NodeEvent::Leave(RefNode::Pragma(_)) => {
                 skip_whitespace = false;
             }
+            NodeEvent::Enter(RefNode::ProtectedEnvelope(x)) => {
+                // IEEE1800-2017 Clause 34
+                // The encrypted blocks are passed through as is, and are
+                // skipped as a compiler directive by the parser.
+                let locate: Locate = x.try_into().unwrap();
+                let range = Range::new(locate.offset, locate.offset + locate.len);
+                ret.push(locate.str(&s), Some((path.as_ref(), range)));
+                skip_nodes.push(RefNode::ProtectedEnvelope(x));
+                skip = true;
+            }
             NodeEvent::Enter(RefNode::LineCompilerDirective(x)) => {
                 let locate: Locate = x.try_into().unwrap();
                 let range = Range::new(locate.offset, locate.offset + locate.len);
@@ -445,9 +818,17 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
             NodeEvent::Enter(RefNode::UndefineCompilerDirective(x)) => {
                 let (_, _, ref name) = x.nodes;
                 let id = identifier((&name.nodes.0).into(), &s).unwrap();
-                defines.remove(&id);
-
                 let locate: Locate = x.try_into().unwrap();
+
+                // IEEE1800-2017 Clause 22.5.2
+                // An attempt to undefine a text macro that was not previously
+                // defined using a `define compiler directive can issue a warning.
+                if defines.remove(&id).is_none() {
+                    let origin = (PathBuf::from(path.as_ref()), locate.offset);
+                    ret.warnings.push(Warning::UndefineNotFound(id.clone(), origin));
+                }
+                ret.kill_macro(MacroKill::Undefine(id));
+
                 let range = Range::new(locate.offset, locate.offset + locate.len);
                 ret.push(locate.str(&s), Some((path.as_ref(), range)));
                 skip_whitespace = true;
@@ -457,6 +838,7 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
             }
             NodeEvent::Enter(RefNode::UndefineallCompilerDirective(x)) => {
                 defines.clear();
+                ret.kill_macro(MacroKill::UndefineAll);
 
                 let locate: Locate = x.try_into().unwrap();
                 let range = Range::new(locate.offset, locate.offset + locate.len);
@@ -466,89 +848,23 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
             NodeEvent::Leave(RefNode::UndefineallCompilerDirective(_)) => {
                 skip_whitespace = false;
             }
-            NodeEvent::Enter(RefNode::IfdefDirective(x)) => {
-                let (_, ref keyword, ref ifid, ref ifbody, ref elsif, ref elsebody, _, _) = x.nodes;
-                skip_nodes.push(keyword.into());
-                skip_nodes.push(ifid.into());
-
-                let ifid = identifier(ifid.into(), &s).unwrap();
-                let mut hit = false;
-                if defines.contains_key(&ifid) || is_predefined_text_macro(&ifid) {
-                    hit = true;
-                } else {
-                    skip_nodes.push(ifbody.into());
-                }
-
-                for x in elsif {
-                    let (_, ref keyword, ref elsifid, ref elsifbody) = x;
-                    skip_nodes.push(keyword.into());
-                    skip_nodes.push(elsifid.into());
-
-                    let elsifid = identifier(elsifid.into(), &s).unwrap();
-                    if hit {
-                        skip_nodes.push(elsifbody.into());
-                    } else if defines.contains_key(&elsifid) || is_predefined_text_macro(&ifid) {
-                        hit = true;
-                    } else {
-                        skip_nodes.push(elsifbody.into());
-                    }
-                }
-
-                if let Some(elsebody) = elsebody {
-                    let (_, ref keyword, ref elsebody) = elsebody;
-                    skip_nodes.push(keyword.into());
-                    if hit {
-                        skip_nodes.push(elsebody.into());
-                    }
-                }
+            NodeEvent::Enter(x @ RefNode::IfdefDirective(_)) => {
+                conditional_directive(x, s, path.as_ref(), &defines, &mut ret, &mut skip_nodes);
             }
-            NodeEvent::Enter(RefNode::WhiteSpace(x)) if !skip_whitespace && !strip_comments => {
+            NodeEvent::Enter(RefNode::WhiteSpace(x)) if !skip_whitespace && !options.strip_comments => {
                 if let WhiteSpace::Space(_) = x {
                     let locate: Locate = x.try_into().unwrap();
                     let range = Range::new(locate.offset + locate.len, locate.offset + locate.len);
                     ret.push(locate.str(&s), Some((path.as_ref(), range)));
                 }
             }
-            NodeEvent::Enter(RefNode::Comment(x)) if !strip_comments => {
+            NodeEvent::Enter(RefNode::Comment(x)) if !options.strip_comments => {
                 let locate: Locate = x.try_into().unwrap();
                 let range = Range::new(locate.offset, locate.offset + locate.len);
                 ret.push(locate.str(&s), Some((path.as_ref(), range)));
             }
-            NodeEvent::Enter(RefNode::IfndefDirective(x)) => {
-                let (_, ref keyword, ref ifid, ref ifbody, ref elsif, ref elsebody, _, _) = x.nodes;
-                skip_nodes.push(keyword.into());
-                skip_nodes.push(ifid.into());
-
-                let ifid = identifier(ifid.into(), &s).unwrap();
-                let mut hit = false;
-                if !defines.contains_key(&ifid) && !is_predefined_text_macro(&ifid) {
-                    hit = true;
-                } else {
-                    skip_nodes.push(ifbody.into());
-                }
-
-                for x in elsif {
-                    let (_, ref keyword, ref elsifid, ref elsifbody) = x;
-                    skip_nodes.push(keyword.into());
-                    skip_nodes.push(elsifid.into());
-
-                    let elsifid = identifier(elsifid.into(), &s).unwrap();
-                    if hit {
-                        skip_nodes.push(elsifbody.into());
-                    } else if defines.contains_key(&elsifid) || is_predefined_text_macro(&ifid) {
-                        hit = true;
-                    } else {
-                        skip_nodes.push(elsifbody.into());
-                    }
-                }
-
-                if let Some(elsebody) = elsebody {
-                    let (_, ref keyword, ref elsebody) = elsebody;
-                    skip_nodes.push(keyword.into());
-                    if hit {
-                        skip_nodes.push(elsebody.into());
-                    }
-                }
+            NodeEvent::Enter(x @ RefNode::IfndefDirective(_)) => {
+                conditional_directive(x, s, path.as_ref(), &defines, &mut ret, &mut skip_nodes);
             }
             NodeEvent::Enter(RefNode::TextMacroDefinition(x)) => {
                 skip_nodes.push(x.into());
@@ -592,12 +908,32 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
                         None
                     };
 
+                    let locate: Locate = x.try_into().unwrap();
+                    let range = Range::new(locate.offset, locate.offset + locate.len);
                     let define = Define {
                         identifier: id.clone(),
                         arguments: define_args,
                         text: define_text,
+                        origin: Some((PathBuf::from(path.as_ref()), range)),
                     };
 
+                    if let Some(Some(previous)) = defines.get(&id) {
+                        let previous_text = previous.text.as_ref().map(|x| x.text.trim());
+                        let text = define.text.as_ref().map(|x| x.text.trim());
+                        if previous.arguments != define.arguments || previous_text != text {
+                            let origin = (PathBuf::from(path.as_ref()), locate.offset);
+                            ret.warnings.push(Warning::DefineRedefined(id.clone(), origin));
+                        }
+                    }
+                    if defines.contains_key(&id) {
+                        ret.kill_macro(MacroKill::Redefine(id.clone()));
+                    }
+                    ret.macro_definitions.push(MacroDefinition {
+                        define: define.clone(),
+                        undefined: false,
+                        redefined: false,
+                    });
+
                     defines.insert(id, Some(define));
                 }
 
@@ -606,12 +942,13 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
                 let range = Range::new(locate.offset, locate.offset + locate.len);
                 ret.push(locate.str(&s), Some((path.as_ref(), range)));
             }
-            NodeEvent::Enter(RefNode::IncludeCompilerDirective(x)) if !ignore_include => {
+            NodeEvent::Enter(RefNode::IncludeCompilerDirective(x)) if !options.ignore_include => {
                 skip_nodes.push(x.into());
                 skip = true;
 
                 let locate: Locate = x.try_into().unwrap();
                 last_include_line = Some(locate.line);
+                let origin = (PathBuf::from(path.as_ref()), locate.offset);
 
                 // IEEE1800-2017 Clause 22.4, page 675
                 // Only white space or a comment may appear on the same line as
@@ -644,14 +981,16 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
                         skip_nodes.push(keyword.into());
                         skip_nodes.push(x.into());
 
+                        ret.macro_usages.push(macro_usage(x, s, path.as_ref(), &defines));
                         if let Some((p, _, _)) = resolve_text_macro_usage(
                             x,
                             s,
                             path.as_ref(),
                             &defines,
                             include_paths,
-                            strip_comments,
+                            options,
                             resolve_depth + 1,
+                            &mut ret.nested_references,
                         )? {
                             let p = p.trim().trim_matches('"');
                             PathBuf::from(p)
@@ -686,39 +1025,76 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
 
                 let (include, new_defines) =
                     preprocess_inner(
-                        path,
+                        &path,
                         &defines,
                         include_paths,
-                        strip_comments,
-                        false, // ignore_include
+                        options,
                         include_depth + 1).map_err(
                         |x| Error::Include {
                             source: Box::new(x),
                         },
                     )?;
                 defines = new_defines;
+                ret.push_include_expansion(&path, &origin, include.text().len());
+                let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+                ret.push_include(Include {
+                    path,
+                    canonical,
+                    origin,
+                    blank: is_blank(include.text()),
+                });
                 ret.merge(include);
             }
             NodeEvent::Enter(RefNode::TextMacroUsage(x)) => {
                 skip_nodes.push(x.into());
                 skip = true;
 
-                if let Some((text, origin, new_defines)) = resolve_text_macro_usage(
+                let usage = macro_usage(x, s, path.as_ref(), &defines);
+                let (ref symbol, ref id, ref args) = x.nodes;
+
+                if options.allow_undefined && !defines.contains_key(&usage.identifier) {
+                    // Keep the usage as a placeholder which the parser accepts
+                    // in place of an expression, statement or module item.
+                    let begin = symbol.nodes.0.offset;
+                    let end = match args {
+                        Some(p) => {
+                            let (_, _, ref closing) = p.nodes;
+                            closing.nodes.0.offset + closing.nodes.0.len
+                        }
+                        None => {
+                            let mut end = begin;
+                            for x in id {
+                                if let RefNode::Locate(x) = x {
+                                    end = x.offset + x.len;
+                                    break;
+                                }
+                            }
+                            end
+                        }
+                    };
+                    let range = Range::new(begin, end);
+                    ret.push(&s[begin..end], Some((path.as_ref(), range)));
+                    ret.warnings.push(Warning::DefineNotFound(
+                        usage.identifier.clone(),
+                        (PathBuf::from(path.as_ref()), begin),
+                    ));
+                } else if let Some((text, origin, new_defines)) = resolve_text_macro_usage(
                     x,
                     s,
                     path.as_ref(),
                     &defines,
                     include_paths,
-                    strip_comments,
+                    options,
                     resolve_depth + 1,
+                    &mut ret.nested_references,
                 )? {
-                    ret.push(&text, origin);
+                    ret.push_macro_expansion(&text, origin, &usage);
                     defines = new_defines;
                 }
+                ret.macro_usages.push(usage);
 
                 // Push the trailing whitespace attached to either
                 // TextMacroIdentifier or Option<Paren<ListOfActualArguments>>.
-                let (ref _symbol, ref id, ref args) = x.nodes;
                 match args {
                     Some(p) => {
                         // Arguments given to macro in parentheses.
@@ -773,9 +1149,110 @@ pub fn preprocess_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
         }
     }
 
+    // Only the outermost call sees every reference to the pre_defines.
+    if resolve_depth == 0 && include_depth == 0 {
+        if let Some(origin) = ret.translate_off.take() {
+            ret.warnings.push(Warning::TranslateOffUnterminated(origin));
+        }
+
+        for (k, v) in pre_defines {
+            let from_source = matches!(v, Some(Define { origin: Some(_), .. }));
+            if from_source {
+                continue;
+            }
+            let used = ret
+                .conditionals
+                .iter()
+                .flat_map(|x| &x.branches)
+                .any(|x| x.identifier.as_ref() == Some(k))
+                || ret.macro_usages.iter().any(|x| &x.identifier == k)
+                || ret.nested_references.contains(k);
+            if !used {
+                ret.warnings.push(Warning::DefineUnused(k.clone()));
+            }
+        }
+    }
+
     Ok((ret, defines))
 }
 
+// Evaluate an `ifdef or `ifndef block, skipping the branches not taken.
+// This is kept out of `preprocess_str` to reduce the stack used by each
+// level of macro resolution.
+fn conditional_directive<'a>(
+    node: RefNode<'a>,
+    s: &str,
+    path: &Path,
+    defines: &Defines,
+    ret: &mut PreprocessedText,
+    skip_nodes: &mut SkipNodes<'a>,
+) {
+    let (kind, symbol, keyword, ifid, ifbody, elsif, elsebody, endsymbol, endkeyword) = match node {
+        RefNode::IfdefDirective(x) => {
+            let (ref symbol, ref keyword, ref ifid, ref ifbody, ref elsif, ref elsebody, ref endsymbol, ref endkeyword) = x.nodes;
+            (ConditionalKind::Ifdef, symbol, keyword, ifid, RefNode::from(ifbody), elsif, elsebody, endsymbol, endkeyword)
+        }
+        RefNode::IfndefDirective(x) => {
+            let (ref symbol, ref keyword, ref ifid, ref ifbody, ref elsif, ref elsebody, ref endsymbol, ref endkeyword) = x.nodes;
+            (ConditionalKind::Ifndef, symbol, keyword, ifid, RefNode::from(ifbody), elsif, elsebody, endsymbol, endkeyword)
+        }
+        _ => unreachable!(),
+    };
+    skip_nodes.push(keyword.into());
+    skip_nodes.push(ifid.into());
+
+    let mut conditional = Conditional::new(path, symbol, endkeyword);
+
+    let ifid = identifier(ifid.into(), s).unwrap();
+    let defined = defines.contains_key(&ifid) || is_predefined_text_macro(&ifid);
+    if !defined {
+        let origin = (PathBuf::from(path), symbol.nodes.0.offset);
+        ret.check_undefined(&ifid, defines, origin);
+    }
+    let mut hit = false;
+    if defined == (kind == ConditionalKind::Ifdef) {
+        hit = true;
+    } else {
+        skip_nodes.push(ifbody);
+    }
+    conditional.push(kind, Some(ifid.clone()), defined, hit, symbol);
+
+    for x in elsif {
+        let (ref symbol, ref keyword, ref elsifid, ref elsifbody) = x;
+        skip_nodes.push(keyword.into());
+        skip_nodes.push(elsifid.into());
+
+        let elsifid = identifier(elsifid.into(), s).unwrap();
+        let defined = defines.contains_key(&elsifid) || is_predefined_text_macro(&elsifid);
+        if !defined {
+            let origin = (PathBuf::from(path), symbol.nodes.0.offset);
+            ret.check_undefined(&elsifid, defines, origin);
+        }
+        let mut taken = false;
+        if hit {
+            skip_nodes.push(elsifbody.into());
+        } else if defined {
+            hit = true;
+            taken = true;
+        } else {
+            skip_nodes.push(elsifbody.into());
+        }
+        conditional.push(ConditionalKind::Elsif, Some(elsifid), defined, taken, symbol);
+    }
+
+    if let Some(elsebody) = elsebody {
+        let (ref symbol, ref keyword, ref elsebody) = elsebody;
+        skip_nodes.push(keyword.into());
+        if hit {
+            skip_nodes.push(elsebody.into());
+        }
+        conditional.push(ConditionalKind::Else, None, false, !hit, symbol);
+    }
+
+    conditional.close(endsymbol);
+    ret.conditionals.push(conditional);
+}
+
 fn identifier(node: RefNode, s: &str) -> Option<String> {
     for x in node {
         match x {
@@ -795,6 +1272,94 @@ fn identifier(node: RefNode, s: &str) -> Option<String> {
     None
 }
 
+fn macro_usage<T: AsRef<Path>>(
+    x: &TextMacroUsage,
+    s: &str,
+    path: T,
+    defines: &Defines,
+) -> MacroUsage {
+    let (_, ref name, ref args) = x.nodes;
+    let id = identifier((&name.nodes.0).into(), &s).unwrap();
+
+    let mut arguments = Vec::new();
+    if let Some(args) = args {
+        let (_, ref args, _) = args.nodes;
+        let (ref args,) = args.nodes;
+        for arg in args.contents() {
+            if let Some(arg) = arg {
+                let (ref arg,) = arg.nodes;
+                arguments.push(Some(String::from(arg.str(&s).trim_end())));
+            } else {
+                arguments.push(None);
+            }
+        }
+    }
+
+    let locate: Locate = x.try_into().unwrap();
+    let range = Range::new(locate.offset, locate.offset + locate.len);
+    let define = defines.get(&id).cloned().flatten();
+
+    MacroUsage {
+        identifier: id,
+        path: PathBuf::from(path.as_ref()),
+        range,
+        arguments,
+        define,
+    }
+}
+
+// Find a defined macro which looks like the intended spelling of `id`.
+fn similar_define(id: &str, defines: &Defines) -> Option<String> {
+    let lower = id.to_lowercase();
+    defines
+        .keys()
+        .filter(|x| x.to_lowercase() == lower || edit_distance(x, id) <= 1)
+        .min_by_key(|x| (edit_distance(x, id), (*x).clone()))
+        .cloned()
+}
+
+/// Edit distance between `a` and `b`, counting a transposition of adjacent
+/// characters as one edit
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, x) in d.iter_mut().enumerate() {
+        x[0] = i;
+    }
+    for (j, x) in d[0].iter_mut().enumerate() {
+        *x = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+// Whether the text has nothing but whitespace and comments.
+fn is_blank(s: &str) -> bool {
+    let mut rest = s.trim_start();
+    while !rest.is_empty() {
+        if let Some(x) = rest.strip_prefix("//") {
+            rest = x.find('\n').map_or("", |i| &x[i..]);
+        } else if let Some(x) = rest.strip_prefix("/*") {
+            rest = x.find("*/").map_or("", |i| &x[i + 2..]);
+        } else {
+            return false;
+        }
+        rest = rest.trim_start();
+    }
+    true
+}
+
 fn get_str(node: RefNode, s: &str) -> String {
     let mut ret = String::from("");
     for x in node {
@@ -909,8 +1474,9 @@ fn resolve_text_macro_usage<T: AsRef<Path>, U: AsRef<Path>>(
     path: T,
     defines: &Defines,
     include_paths: &[U],
-    strip_comments: bool,
+    options: &PreprocessOptions,
     resolve_depth: usize,
+    references: &mut BTreeSet<String>,
 ) -> Result<Option<(String, Option<(PathBuf, Range)>, Defines)>, Error> {
     let (_, ref name, ref args) = x.nodes;
     let id = identifier((&name.nodes.0).into(), &s).unwrap();
@@ -998,16 +1564,27 @@ fn resolve_text_macro_usage<T: AsRef<Path>, U: AsRef<Path>>(
                 replaced.push_str(&paren);
             }
 
+            // `include directives in the macro text are always expanded, and
+            // translate_off regions in it are never stripped.
+            let options = PreprocessOptions {
+                ignore_include: false,
+                strip_translate_off: false,
+                ..options.clone()
+            };
             let (replaced, new_defines) = preprocess_str(
                 &replaced,
                 path.as_ref(),
                 &defines,
                 include_paths,
-                false,
-                strip_comments,
+                &options,
                 resolve_depth,
                 0, // include_depth
             )?;
+            // Macros referenced by the macro text
+            references.extend(replaced.nested_references.iter().cloned());
+            references.extend(replaced.macro_usages.iter().map(|x| x.identifier.clone()));
+            let conditionals = replaced.conditionals.iter().flat_map(|x| &x.branches);
+            references.extend(conditionals.filter_map(|x| x.identifier.clone()));
             Ok(Some((
                 String::from(replaced.text()),
                 text.origin.clone(),
@@ -1054,8 +1631,7 @@ mod tests {
             testfile_path(s),   // path
             &HashMap::new(),    // pre_defines
             &include_paths,     // include_paths
-            false,              // strip_comments
-            false,              // ignore_include
+            &PreprocessOptions::default(),
         )
     }
 
@@ -1100,6 +1676,33 @@ mod tests {
         };
     } // }}}
 
+    #[test]
+    #[allow(non_snake_case)]
+    fn allow_undefined_DefineNotFound() { // {{{
+        let include_paths = [testfile_path("")];
+        let (ret, _) = preprocess(
+            testfile_path("err_DefineNotFound.sv"),
+            &HashMap::new(),
+            &include_paths,
+            &PreprocessOptions {
+                allow_undefined: true,
+                ..PreprocessOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            ret.text(),
+            testfile_contents("err_DefineNotFound.sv")
+        );
+        assert_eq!(
+            ret.warnings(),
+            &[Warning::DefineNotFound(
+                String::from("A"),
+                (PathBuf::from(testfile_path("err_DefineNotFound.sv")), 1)
+            )]
+        );
+    } // }}}
+
     #[test]
     #[allow(non_snake_case)]
     fn err_DefineArgNotFound() { // {{{
@@ -1261,6 +1864,37 @@ mod tests {
         );
     } // }}}
 
+    #[test]
+    fn ifdef_nested_conditionals() { // {{{
+        let (ret, _) = preprocess_usualargs("ifdef_nested.sv").unwrap();
+        let conditionals = ret.conditionals();
+
+        // The `ifdef OPT_2 block is nested inside the skipped `ifdef DEBUG
+        // branch, so it is never evaluated.
+        assert_eq!(conditionals.len(), 2);
+
+        let x = &conditionals[0];
+        assert_eq!(x.path, PathBuf::from(testfile_path("ifdef_nested.sv")));
+        assert_eq!((x.range.begin, x.range.end), (10, 71));
+        assert_eq!(x.branches.len(), 2);
+        assert_eq!(x.branches[0].kind, ConditionalKind::Ifdef);
+        assert_eq!(x.branches[0].identifier, Some(String::from("OPT_1")));
+        assert!(!x.branches[0].defined);
+        assert!(!x.branches[0].taken);
+        assert_eq!((x.branches[0].range.begin, x.branches[0].range.end), (10, 42));
+        assert_eq!(x.branches[1].kind, ConditionalKind::Else);
+        assert_eq!(x.branches[1].identifier, None);
+        assert!(x.branches[1].taken);
+        assert_eq!((x.branches[1].range.begin, x.branches[1].range.end), (42, 65));
+
+        let x = &conditionals[1];
+        assert_eq!((x.range.begin, x.range.end), (72, 159));
+        assert_eq!(x.branches.len(), 1);
+        assert_eq!(x.branches[0].identifier, Some(String::from("DEBUG")));
+        assert!(!x.branches[0].taken);
+        assert_eq!((x.branches[0].range.begin, x.branches[0].range.end), (72, 153));
+    } // }}}
+
     #[test]
     fn ifdef_predefined() { // {{{
         let mut defines = HashMap::new();
@@ -1269,8 +1903,7 @@ mod tests {
             testfile_path("ifdef_predefined.sv"),
             &defines,
             &[] as &[String],
-            false, // strip_comments
-            false, // ignore_include
+            &PreprocessOptions::default(),
         )
         .unwrap();
         assert_eq!(
@@ -1279,6 +1912,21 @@ mod tests {
         )
     } // }}}
 
+    #[test]
+    fn elsif_predefined() { // {{{
+        let (ret, _) = preprocess_usualargs("elsif_predefined.sv").unwrap();
+        assert_eq!(
+            ret.text(),
+            testfile_contents("expected/elsif_predefined.sv")
+        );
+        let conditionals = ret.conditionals();
+        assert_eq!(conditionals[0].branches[1].identifier, Some(String::from("__LINE__")));
+        assert!(conditionals[0].branches[1].defined);
+        assert!(conditionals[0].branches[1].taken);
+        assert!(conditionals[1].branches[1].defined);
+        assert!(conditionals[1].branches[1].taken);
+    } // }}}
+
     #[test]
     fn ifdef_undefined() { // {{{
         let (ret, _) = preprocess_usualargs("ifdef_undefined.sv").unwrap();
@@ -1311,8 +1959,10 @@ mod tests {
             testfile_path("include_ignore.sv"),
             &HashMap::new(),
             &include_paths,
-            false, // strip_comments
-            true, // ignore_include
+            &PreprocessOptions {
+                ignore_include: true,
+                ..PreprocessOptions::default()
+            },
         )
         .unwrap();
         assert_eq!(
@@ -1499,6 +2149,36 @@ mod tests {
         );
     } // }}}
 
+    #[test]
+    fn macro_arguments_index() { // {{{
+        let (ret, _) = preprocess_usualargs("macro_arguments.sv").unwrap();
+
+        let definitions = ret.macro_definitions();
+        assert_eq!(definitions.len(), 1);
+        let define = &definitions[0].define;
+        assert_eq!(define.identifier, String::from("disp"));
+        assert_eq!(define.arguments.len(), 3);
+        let (ref origin_path, ref origin_range) = define.origin.as_ref().unwrap();
+        assert_eq!(origin_path, &PathBuf::from(testfile_path("macro_arguments.sv")));
+        assert_eq!(origin_range.begin, 443);
+        assert!(!definitions[0].undefined);
+        assert!(!definitions[0].redefined);
+
+        let usages = ret.macro_usages();
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].identifier, String::from("disp"));
+        assert_eq!(usages[0].arguments.len(), 3);
+        assert_eq!(usages[0].define.as_ref(), Some(define));
+
+        // The same definition at another location is equal
+        let mut moved = define.clone();
+        moved.origin = None;
+        moved.text.as_mut().unwrap().origin = None;
+        assert_eq!(&moved, define);
+        moved.arguments.pop();
+        assert_ne!(&moved, define);
+    } // }}}
+
     #[test]
     fn macro_basic() { // {{{
         let (ret, _) = preprocess_usualargs("macro_basic.sv").unwrap();
@@ -1595,6 +2275,77 @@ mod tests {
         );
     } // }}}
 
+    #[test]
+    fn pragma_protect() { // {{{
+        let (ret, _) = preprocess_usualargs("pragma_protect.sv").unwrap();
+        assert_eq!(
+            ret.text(),
+            testfile_contents("pragma_protect.sv")
+        );
+    } // }}}
+
+    #[test]
+    fn translate_off() { // {{{
+        let include_paths = [testfile_path("")];
+        let (ret, defines) = preprocess(
+            testfile_path("translate_off.sv"),
+            &HashMap::new(),
+            &include_paths,
+            &PreprocessOptions {
+                strip_translate_off: true,
+                ..PreprocessOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            ret.text(),
+            testfile_contents("expected/translate_off.sv")
+        );
+        assert!(!defines.contains_key("SIM_ONLY"));
+    } // }}}
+
+    #[test]
+    fn translate_off_include() { // {{{
+        let include_paths = [testfile_path("")];
+        let (ret, _) = preprocess(
+            testfile_path("translate_off_include.sv"),
+            &HashMap::new(),
+            &include_paths,
+            &PreprocessOptions {
+                strip_translate_off: true,
+                ..PreprocessOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            ret.text(),
+            testfile_contents("expected/translate_off_include.sv")
+        );
+        assert_eq!(ret.warnings(), &[]);
+    } // }}}
+
+    #[test]
+    fn translate_off_unterminated() { // {{{
+        let (ret, _) = preprocess(
+            testfile_path("translate_off_unterminated.sv"),
+            &HashMap::new(),
+            &[] as &[String],
+            &PreprocessOptions {
+                strip_translate_off: true,
+                ..PreprocessOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(ret.text(), "module A;\n  ");
+        assert_eq!(
+            ret.warnings(),
+            &[Warning::TranslateOffUnterminated((
+                PathBuf::from(testfile_path("translate_off_unterminated.sv")),
+                12
+            ))]
+        );
+    } // }}}
+
     #[test]
     fn resetall() { // {{{
         let (ret, _) = preprocess_usualargs("resetall.sv").unwrap();
@@ -1631,6 +2382,111 @@ mod tests {
         );
     } // }}}
 
+    #[test]
+    fn undef_warnings() { // {{{
+        let (ret, _) = preprocess_usualargs("undef.sv").unwrap();
+        let path = PathBuf::from(testfile_path("undef.sv"));
+        assert_eq!(
+            ret.warnings(),
+            &[
+                Warning::UndefineNotFound(String::from("FOO"), (path.clone(), 311)),
+                Warning::UndefineNotFound(String::from("FOO"), (path.clone(), 322)),
+                Warning::UndefineNotFound(String::from("FOO"), (path.clone(), 343)),
+            ]
+        );
+    } // }}}
+
+    #[test]
+    fn warnings() { // {{{
+        let mut defines = HashMap::new();
+        defines.insert(String::from("UNUSED"), None);
+        defines.insert(String::from("DEBUG"), None);
+        let src = "`define A 1\n`define A 2\n`ifdef DEBUG_\n`endif\n";
+        let (ret, _) = preprocess_str(
+            src,
+            "warnings.sv",
+            &defines,
+            &[] as &[String],
+            &PreprocessOptions::default(),
+            0, // resolve_depth
+            0, // include_depth
+        )
+        .unwrap();
+        let path = PathBuf::from("warnings.sv");
+        let warnings = ret.warnings();
+        assert_eq!(warnings.len(), 4);
+        assert!(warnings.contains(&Warning::DefineRedefined(String::from("A"), (path.clone(), 12))));
+        assert!(warnings.contains(&Warning::DefineSimilar(
+            String::from("DEBUG_"),
+            String::from("DEBUG"),
+            (path.clone(), 24)
+        )));
+        assert!(warnings.contains(&Warning::DefineUnused(String::from("UNUSED"))));
+        assert!(warnings.contains(&Warning::DefineUnused(String::from("DEBUG"))));
+    } // }}}
+
+    #[test]
+    fn warnings_nested() { // {{{
+        let mut defines = HashMap::new();
+        defines.insert(String::from("WIDTH"), Some(Define::new(
+            String::from("WIDTH"),
+            vec![],
+            Some(DefineText::new(String::from("8"), None)),
+        )));
+        defines.insert(String::from("DEBUG"), None);
+        let src = "`define BUS [`WIDTH-1:0]\n`define CHECK `ifdef DEBUG 1 `endif\nwire `BUS a = `CHECK;\n";
+        let (ret, _) = preprocess_str(
+            src,
+            "warnings_nested.sv",
+            &defines,
+            &[] as &[String],
+            &PreprocessOptions::default(),
+            0, // resolve_depth
+            0, // include_depth
+        )
+        .unwrap();
+        assert_eq!(ret.warnings(), &[]);
+    } // }}}
+
+    #[test]
+    fn include_repeated() { // {{{
+        let (ret, _) = preprocess_usualargs("include_repeated.sv").unwrap();
+        // Each include of include_repeated_c.svh in include_repeated_b.svh
+        // repeats an earlier one and is reported once, even though the
+        // second repeats two earlier includes.
+        let warnings: Vec<_> = ret
+            .warnings()
+            .iter()
+            .map(|x| match x {
+                Warning::IncludeRepeated(path, (origin, pos)) => (path.clone(), origin.clone(), *pos),
+                _ => panic!("{:?}", x),
+            })
+            .collect();
+        let c = PathBuf::from(testfile_path("include_repeated_c.svh"));
+        let b = PathBuf::from(testfile_path("include_repeated_b.svh"));
+        assert_eq!(warnings, [(c.clone(), b.clone(), 0), (c, b, 34)]);
+    } // }}}
+
+    #[test]
+    fn sources() { // {{{
+        let (ret, _) = preprocess_usualargs("include_repeated.sv").unwrap();
+        for s in &["include_repeated.sv", "include_repeated_b.svh", "include_repeated_c.svh"] {
+            let path = PathBuf::from(testfile_path(s));
+            assert_eq!(ret.source(&path), Some(testfile_contents(s).as_str()));
+        }
+        assert_eq!(ret.source(Path::new("included.svh")), None);
+    } // }}}
+
+    #[test]
+    fn undef_index() { // {{{
+        let (ret, _) = preprocess_usualargs("undef.sv").unwrap();
+        let definitions = ret.macro_definitions();
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].define.identifier, String::from("FOO"));
+        assert!(definitions[0].undefined);
+        assert!(!definitions[0].redefined);
+    } // }}}
+
     #[test]
     fn undefineall() { // {{{
         let (ret, _) = preprocess_usualargs("undefineall.sv").unwrap();
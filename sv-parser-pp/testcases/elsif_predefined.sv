@@ -0,0 +1,12 @@
+module A;
+`ifdef NOPE
+wire a;
+`elsif __LINE__
+wire b;
+`endif
+`ifndef __FILE__
+wire c;
+`elsif __LINE__
+wire d;
+`endif
+endmodule
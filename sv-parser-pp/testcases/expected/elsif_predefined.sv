@@ -0,0 +1,6 @@
+module A;
+wire b;
+
+wire d;
+
+endmodule
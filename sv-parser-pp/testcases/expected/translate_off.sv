@@ -0,0 +1,5 @@
+module A;
+    assign a = b;
+  
+endmodule
+
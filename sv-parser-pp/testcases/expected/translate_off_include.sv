@@ -0,0 +1,4 @@
+module A;
+  wire c;
+    assign a = b;
+  endmodule
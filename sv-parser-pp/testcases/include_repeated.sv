@@ -0,0 +1,2 @@
+`include "include_repeated_c.svh"
+`include "include_repeated_b.svh"
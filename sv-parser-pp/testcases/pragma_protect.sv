@@ -0,0 +1,23 @@
+// IEEE1800-2017 Clause 34
+// Encrypted design data is wrapped in a protected envelope with a
+// begin_protected / end_protected pair. The encoded blocks are not valid
+// SystemVerilog text and may contain sequences like "//".
+module secret (
+  input  a,
+  output b
+);
+`pragma protect begin_protected
+`pragma protect encrypt_agent="Example", encrypt_agent_info="1.0"
+`pragma protect key_keyowner="Example", key_keyname="key1", key_method="rsa"
+`pragma protect encoding=(enctype="base64", line_length=76, bytes=64)
+`pragma protect key_block
+X4Rf//Wq0b6PslIkMPMvwMFmmE0uj5vN0d0wE7Kx+eGmS1xp6w5RmT1j3LKx6ZP2yUeR8/W+fQ3N
+/*c2VjcmV0IGtleQ==
+`pragma protect encoding=(enctype="base64", line_length=76, bytes=48)
+`pragma protect data_keyowner="Example", data_method="aes128-cbc"
+`pragma protect data_block
+//8AZ3N0dWZmIHRoYXQgY2Fubm90IGJlIHBhcnNlZA==+/+/
+QmFzZTY0IGVuY29kZWQgZGF0YQ==
+`pragma protect end_protected
+endmodule
+// This file should be emitted from the preprocessor unchanged.
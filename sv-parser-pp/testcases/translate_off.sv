@@ -0,0 +1,11 @@
+module A;
+  // synopsys translate_off
+  initial $display("simulation only");
+  // synopsys translate_on
+  assign a = b;
+  /* pragma translate_off */
+  `define SIM_ONLY
+  /* pragma translate_on */
+endmodule
+`ifdef SIM_ONLY
+`endif
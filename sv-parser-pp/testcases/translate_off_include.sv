@@ -0,0 +1,9 @@
+module A;
+`include "translate_off_open.svh"
+  initial $display("simulation only");
+  // synopsys translate_on
+  assign a = b;
+  // synopsys translate_off
+`include "missing.svh"
+  // synopsys translate_on
+endmodule
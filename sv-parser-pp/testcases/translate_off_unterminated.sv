@@ -0,0 +1,4 @@
+module A;
+  // synopsys translate_off
+  initial $display("simulation only");
+endmodule
@@ -0,0 +1,378 @@
+//! Python bindings of sv-parser
+//!
+//! The module is built by maturin as `sv_parser`.
+
+#![recursion_limit = "256"]
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyOSError};
+use pyo3::prelude::*;
+use pyo3::types::{PyIterator, PyList};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+create_exception!(sv_parser, ParseError, PyException);
+create_exception!(sv_parser, PreprocessError, PyException);
+
+/// Syntax tree returned by `parse_sv` and `parse_sv_str`
+#[pyclass(module = "sv_parser", frozen)]
+struct SyntaxTree {
+    tree: sv_parser::SyntaxTree,
+    table: sv_parser::NodeTable,
+}
+
+/// A node of `SyntaxTree`, whose kind is a variant name of `RefNode`
+#[pyclass(module = "sv_parser", frozen)]
+struct Node {
+    tree: Py<SyntaxTree>,
+    id: usize,
+}
+
+/// Text returned by `preprocess`
+#[pyclass(module = "sv_parser", frozen)]
+struct PreprocessedText {
+    text: sv_parser::PreprocessedText,
+}
+
+// -----------------------------------------------------------------------------
+
+impl SyntaxTree {
+    fn new(tree: sv_parser::SyntaxTree) -> Self {
+        let table = sv_parser::NodeTable::new(&tree);
+        SyntaxTree { tree, table }
+    }
+
+    fn text(&self, locate: Option<sv_parser::Locate>) -> Option<&str> {
+        self.tree.get_str(&locate?)
+    }
+
+    fn check(slf: &Bound<'_, Self>, node: &Bound<'_, Node>) -> PyResult<usize> {
+        if node.get().tree.is(slf) {
+            Ok(node.get().id)
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "node belongs to another syntax tree",
+            ))
+        }
+    }
+}
+
+fn node(tree: &Bound<'_, SyntaxTree>, id: usize) -> Node {
+    Node {
+        tree: tree.clone().unbind(),
+        id,
+    }
+}
+
+fn find(tree: &Bound<'_, SyntaxTree>, beg: usize, end: usize, kind: &str) -> Vec<Node> {
+    let t = tree.get();
+    let kind = match t.table.kinds.iter().position(|x| x == kind) {
+        Some(x) => x,
+        None => return vec![],
+    };
+    (beg..=end)
+        .filter(|x| t.table.nodes[*x].kind == kind)
+        .map(|x| node(tree, x))
+        .collect()
+}
+
+#[pymethods]
+impl SyntaxTree {
+    #[getter]
+    fn root(slf: &Bound<'_, Self>) -> Node {
+        node(slf, 0)
+    }
+
+    /// All nodes in pre-order
+    fn __iter__<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyIterator>> {
+        let nodes: Vec<Node> = (0..slf.get().table.nodes.len())
+            .map(|x| node(slf, x))
+            .collect();
+        PyList::new(slf.py(), nodes)?.try_iter()
+    }
+
+    fn __len__(&self) -> usize {
+        self.table.nodes.len()
+    }
+
+    fn __str__(&self) -> String {
+        format!("{}", self.tree)
+    }
+
+    /// All nodes of `kind` in pre-order
+    fn find(slf: &Bound<'_, Self>, kind: &str) -> Vec<Node> {
+        find(slf, 0, slf.get().table.nodes.len() - 1, kind)
+    }
+
+    /// Source text of the node, same as `SyntaxTree::get_str`
+    fn get_str(slf: &Bound<'_, Self>, node: &Bound<'_, Node>) -> PyResult<Option<String>> {
+        let id = Self::check(slf, node)?;
+        let t = slf.get();
+        Ok(t.text(t.table.nodes[id].locate).map(String::from))
+    }
+
+    /// Source text of the node without trailing white space
+    fn get_str_trim(slf: &Bound<'_, Self>, node: &Bound<'_, Node>) -> PyResult<Option<String>> {
+        let id = Self::check(slf, node)?;
+        let t = slf.get();
+        Ok(t.text(t.table.nodes[id].locate_trim).map(String::from))
+    }
+
+    /// Source file path and byte offset of the first token of the node
+    fn get_origin(
+        slf: &Bound<'_, Self>,
+        node: &Bound<'_, Node>,
+    ) -> PyResult<Option<(PathBuf, usize)>> {
+        let id = Self::check(slf, node)?;
+        let t = slf.get();
+        let origin = t.table.nodes[id].locate.and_then(|x| t.tree.get_origin(&x));
+        Ok(origin.map(|(path, pos)| (path.clone(), pos)))
+    }
+}
+
+#[pymethods]
+impl Node {
+    #[getter]
+    fn kind(&self) -> &str {
+        let tree = self.tree.get();
+        &tree.table.kinds[tree.table.nodes[self.id].kind]
+    }
+
+    #[getter]
+    fn parent(&self, py: Python<'_>) -> Option<Node> {
+        let tree = self.tree.bind(py);
+        tree.get().table.nodes[self.id]
+            .parent
+            .map(|x| node(tree, x))
+    }
+
+    #[getter]
+    fn children(&self, py: Python<'_>) -> Vec<Node> {
+        let tree = self.tree.bind(py);
+        tree.get().table.nodes[self.id]
+            .children
+            .iter()
+            .map(|x| node(tree, *x))
+            .collect()
+    }
+
+    /// Source text of the node without trailing white space
+    #[getter]
+    fn text(&self) -> Option<&str> {
+        let tree = self.tree.get();
+        tree.text(tree.table.nodes[self.id].locate_trim)
+    }
+
+    /// The node and its descendants in pre-order
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        let tree = self.tree.bind(py);
+        let nodes: Vec<Node> = (self.id..=tree.get().table.nodes[self.id].last)
+            .map(|x| node(tree, x))
+            .collect();
+        PyList::new(py, nodes)?.try_iter()
+    }
+
+    /// Nodes of `kind` in the node and its descendants
+    fn find(&self, py: Python<'_>, kind: &str) -> Vec<Node> {
+        let tree = self.tree.bind(py);
+        find(tree, self.id, tree.get().table.nodes[self.id].last, kind)
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.tree.is(&other.tree) && self.id == other.id
+    }
+
+    fn __hash__(&self) -> usize {
+        self.id
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Node({})", self.kind())
+    }
+}
+
+#[pymethods]
+impl PreprocessedText {
+    #[getter]
+    fn text(&self) -> &str {
+        self.text.text()
+    }
+
+    /// Source file path and byte offset of the position of the text
+    fn origin(&self, pos: usize) -> Option<(PathBuf, usize)> {
+        self.text.origin(pos).map(|(path, pos)| (path.clone(), pos))
+    }
+
+    fn __str__(&self) -> &str {
+        self.text.text()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+type Defines = HashMap<String, Option<String>>;
+
+fn to_defines(defines: Option<Defines>) -> sv_parser::Defines {
+    sv_parser::defines_from(defines.unwrap_or_default())
+}
+
+fn from_defines(defines: sv_parser::Defines) -> Defines {
+    defines
+        .into_iter()
+        .map(|(k, v)| (k, v.and_then(|x| x.text).map(|x| x.text)))
+        .collect()
+}
+
+fn to_py_err(error: sv_parser::Error) -> PyErr {
+    let message = format!("{}", error);
+    match error {
+        sv_parser::Error::Parse(x) => {
+            let (path, pos) = x.map_or((None, None), |(x, y)| (Some(x), Some(y)));
+            ParseError::new_err((message, path, pos))
+        }
+        sv_parser::Error::Io(x) => PyOSError::new_err(format!("{}", x)),
+        sv_parser::Error::File { source, path } => PyOSError::new_err((
+            source.raw_os_error().unwrap_or(0),
+            format!("{}", source),
+            path,
+        )),
+        sv_parser::Error::Include { source } => to_py_err(*source),
+        sv_parser::Error::Preprocess(x) => {
+            let (path, pos) = x.map_or((None, None), |(x, y)| (Some(x), Some(y)));
+            PreprocessError::new_err((message, path, pos))
+        }
+        _ => PreprocessError::new_err((message, None::<PathBuf>, None::<usize>)),
+    }
+}
+
+// Run `f` on a thread with enough stack for the parser without holding the GIL
+fn run<T, F>(py: Python<'_>, f: F) -> PyResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, sv_parser::Error> + Send + 'static,
+{
+    let result = py.allow_threads(|| {
+        std::thread::Builder::new()
+            .stack_size(sv_parser::PARSER_STACK_SIZE)
+            .spawn(f)
+            .expect("thread spawn failure")
+            .join()
+    });
+    match result {
+        Ok(x) => x.map_err(to_py_err),
+        Err(_) => Err(PyException::new_err("parser panicked")),
+    }
+}
+
+fn to_result(
+    py: Python<'_>,
+    (tree, defines): (sv_parser::SyntaxTree, sv_parser::Defines),
+) -> PyResult<(Py<SyntaxTree>, Defines)> {
+    Ok((Py::new(py, SyntaxTree::new(tree))?, from_defines(defines)))
+}
+
+/// Parse a file and return the syntax tree and the defines after parse.
+///
+/// `defines` maps macro names to their text, or `None` for macros without text.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (path, defines=None, include_paths=None, ignore_include=false, allow_incomplete=false, allow_undefined=false, strip_translate_off=false))]
+fn parse_sv(
+    py: Python<'_>,
+    path: PathBuf,
+    defines: Option<Defines>,
+    include_paths: Option<Vec<PathBuf>>,
+    ignore_include: bool,
+    allow_incomplete: bool,
+    allow_undefined: bool,
+    strip_translate_off: bool,
+) -> PyResult<(Py<SyntaxTree>, Defines)> {
+    let defines = to_defines(defines);
+    let include_paths = include_paths.unwrap_or_default();
+    let options = sv_parser::ParseOptions {
+        ignore_include,
+        allow_incomplete,
+        allow_undefined,
+        strip_translate_off,
+        ..Default::default()
+    };
+    let ret = run(py, move || {
+        sv_parser::parse_sv(&path, &defines, &include_paths, &options)
+    })?;
+    to_result(py, ret)
+}
+
+/// Parse a string and return the syntax tree and the defines after parse.
+///
+/// `path` is used to resolve relative includes and to report origins.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (text, path="", defines=None, include_paths=None, ignore_include=false, allow_incomplete=false, allow_undefined=false, strip_translate_off=false))]
+fn parse_sv_str(
+    py: Python<'_>,
+    text: String,
+    path: &str,
+    defines: Option<Defines>,
+    include_paths: Option<Vec<PathBuf>>,
+    ignore_include: bool,
+    allow_incomplete: bool,
+    allow_undefined: bool,
+    strip_translate_off: bool,
+) -> PyResult<(Py<SyntaxTree>, Defines)> {
+    let path = PathBuf::from(path);
+    let defines = to_defines(defines);
+    let include_paths = include_paths.unwrap_or_default();
+    let options = sv_parser::ParseOptions {
+        ignore_include,
+        allow_incomplete,
+        allow_undefined,
+        strip_translate_off,
+        ..Default::default()
+    };
+    let ret = run(py, move || {
+        sv_parser::parse_sv_str(&text, &path, &defines, &include_paths, &options)
+    })?;
+    to_result(py, ret)
+}
+
+/// Preprocess a file and return the preprocessed text and the defines after it.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (path, defines=None, include_paths=None, strip_comments=false, ignore_include=false, allow_undefined=false, strip_translate_off=false))]
+fn preprocess(
+    py: Python<'_>,
+    path: PathBuf,
+    defines: Option<Defines>,
+    include_paths: Option<Vec<PathBuf>>,
+    strip_comments: bool,
+    ignore_include: bool,
+    allow_undefined: bool,
+    strip_translate_off: bool,
+) -> PyResult<(PreprocessedText, Defines)> {
+    let defines = to_defines(defines);
+    let include_paths = include_paths.unwrap_or_default();
+    let options = sv_parser::PreprocessOptions {
+        strip_comments,
+        ignore_include,
+        allow_undefined,
+        strip_translate_off,
+    };
+    let (text, defines) = run(py, move || {
+        sv_parser::preprocess(&path, &defines, &include_paths, &options)
+    })?;
+    Ok((PreprocessedText { text }, from_defines(defines)))
+}
+
+#[pymodule]
+#[pyo3(name = "sv_parser")]
+fn sv_parser_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<SyntaxTree>()?;
+    m.add_class::<Node>()?;
+    m.add_class::<PreprocessedText>()?;
+    m.add("ParseError", m.py().get_type::<ParseError>())?;
+    m.add("PreprocessError", m.py().get_type::<PreprocessError>())?;
+    m.add_function(wrap_pyfunction!(parse_sv, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_sv_str, m)?)?;
+    m.add_function(wrap_pyfunction!(preprocess, m)?)?;
+    Ok(())
+}
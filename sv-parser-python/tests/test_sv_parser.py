@@ -0,0 +1,124 @@
+from pathlib import Path
+
+import pytest
+
+import sv_parser
+
+ROOT = Path(__file__).resolve().parents[2]
+TESTCASES = ROOT / "sv-parser" / "testcases"
+PP_TESTCASES = ROOT / "sv-parser-pp" / "testcases"
+
+
+def test_parse_sv():
+    tree, defines = sv_parser.parse_sv(TESTCASES / "test1.sv")
+    assert tree.root.kind == "SourceText"
+    assert tree.root.parent is None
+    assert "dimm" in [x.text for x in tree.find("ModuleIdentifier")]
+    assert defines["SV_COV_START"] == "0"
+
+    tree, _ = sv_parser.parse_sv(str(TESTCASES / "test2.sv"))
+    declarations = tree.find("ModuleDeclarationAnsi")
+    assert len(declarations) == 3
+    names = [x.find("ModuleIdentifier")[0].text for x in declarations]
+    assert names == ["memMod", "cpuMod", "top"]
+
+
+def test_parse_sv_str():
+    src = "module A #(parameter W = `WIDTH) (input logic [W-1:0] a);\nendmodule\n"
+    tree, defines = sv_parser.parse_sv_str(src, "a.sv", defines={"WIDTH": "8"})
+    assert defines["WIDTH"] == "8"
+
+    param = tree.find("ParamAssignment")[0]
+    assert tree.get_str(param) == "W = 8"
+    assert tree.get_str_trim(param) == "W = 8"
+
+    module = tree.find("ModuleIdentifier")[0]
+    assert module.text == "A"
+    assert tree.get_str(module) == "A "
+    assert tree.get_origin(module) == ("a.sv", 7)
+
+    keyword = tree.find("Keyword")[0]
+    assert keyword.parent.kind == "ModuleKeyword"
+    assert keyword in keyword.parent.children
+
+
+def test_iteration():
+    tree, _ = sv_parser.parse_sv_str("module A; endmodule")
+    nodes = list(tree)
+    assert len(nodes) == len(tree)
+    assert nodes[0] == tree.root
+
+    module = tree.find("ModuleDeclarationAnsi")[0]
+    descendants = list(module)
+    assert descendants[0] == module
+    assert all(x in descendants for x in module.children)
+    assert module.find("ModuleIdentifier") == tree.find("ModuleIdentifier")
+    assert "ModuleDeclarationAnsi" in str(tree)
+
+
+def test_node_of_another_tree():
+    tree_a, _ = sv_parser.parse_sv_str("module A; endmodule")
+    tree_b, _ = sv_parser.parse_sv_str("module B; endmodule")
+    with pytest.raises(ValueError):
+        tree_a.get_str(tree_b.root)
+
+
+def test_include():
+    tree, _ = sv_parser.parse_sv(
+        PP_TESTCASES / "include_withindent.sv", include_paths=[PP_TESTCASES]
+    )
+    ports = tree.find("PortIdentifier")
+    assert [x.text for x in ports] == ["a", "b", "c", "a", "b", "c"]
+    origins = {Path(tree.get_origin(x)[0]).name for x in tree.find("Locate")}
+    assert origins == {"include_withindent.sv", "included.svh"}
+
+
+def test_preprocess():
+    text, _ = sv_parser.preprocess(
+        PP_TESTCASES / "include_withindent.sv", include_paths=[PP_TESTCASES]
+    )
+    assert text.text.startswith("module and_op (a, b, c);\n  // a\n  output a;")
+    assert text.origin(0) == (str(PP_TESTCASES / "include_withindent.sv"), 0)
+    assert text.origin(34) == (str(PP_TESTCASES / "included.svh"), 0)
+
+    text, defines = sv_parser.preprocess(
+        PP_TESTCASES / "include_withindent.sv",
+        defines={"behavioral": None},
+        include_paths=[PP_TESTCASES],
+    )
+    assert "wire a = b & c;" in text.text
+    assert "behavioral" in defines
+    assert str(text) == text.text
+
+
+def test_parse_error():
+    with pytest.raises(sv_parser.ParseError) as e:
+        sv_parser.parse_sv_str("module A;\n  assign = b;\nendmodule\n", "b.sv")
+    message, path, offset = e.value.args
+    assert message.startswith("Parse error")
+    assert path == "b.sv"
+    assert offset == 19
+
+    with pytest.raises(OSError):
+        sv_parser.parse_sv(TESTCASES / "not_found.sv")
+
+
+def test_allow_undefined():
+    src = "module A;\n  assign a = `UNDEFINED;\nendmodule\n"
+    with pytest.raises(sv_parser.PreprocessError):
+        sv_parser.parse_sv_str(src)
+
+    tree, _ = sv_parser.parse_sv_str(src, allow_undefined=True)
+    assert len(tree.find("TextMacroUsage")) == 1
+
+
+def test_strip_translate_off():
+    src = (
+        "module A;\n  // synopsys translate_off\n  initial $display();\n"
+        "  // synopsys translate_on\nendmodule\n"
+    )
+    tree, _ = sv_parser.parse_sv_str(src)
+    assert len(tree.find("SystemTfCall")) == 1
+
+    tree, _ = sv_parser.parse_sv_str(src, strip_translate_off=True)
+    assert tree.find("SystemTfCall") == []
@@ -51,6 +51,16 @@ static ANY_NODE_DISPLAY_FOOTER: &str = r##"
 }
 "##;
 
+static REF_NODE_KINDS_HEADER: &str = r##"
+/// Variant names of `RefNode`
+pub const REF_NODE_KINDS: &[&str] = &[
+    "Locate",
+"##;
+
+static REF_NODE_KINDS_FOOTER: &str = r##"
+];
+"##;
+
 fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
     let dest = Path::new(&out_dir).join("any_node.rs");
@@ -60,6 +70,7 @@ fn main() {
     let mut any_node = String::from(ANY_NODE_HEADER);
     let mut ref_node_display = String::from(REF_NODE_DISPLAY_HEADER);
     let mut any_node_display = String::from(ANY_NODE_DISPLAY_HEADER);
+    let mut ref_node_kinds = String::from(REF_NODE_KINDS_HEADER);
 
     let re_node = Regex::new(r"#\[derive.*Node.*\]").unwrap();
 
@@ -83,6 +94,7 @@ fn main() {
                         "{}            AnyNode::{}(_) => write!(f, \"{}\"),\n",
                         any_node_display, name, name
                     );
+                    ref_node_kinds = format!("{}    \"{}\",\n", ref_node_kinds, name);
                     hit_node = false;
                 }
                 if re_node.is_match(&line) {
@@ -96,8 +108,10 @@ fn main() {
     any_node = format!("{}{}\n", any_node, ANY_NODE_FOOTER);
     ref_node_display = format!("{}{}\n", ref_node_display, REF_NODE_DISPLAY_FOOTER);
     any_node_display = format!("{}{}\n", any_node_display, ANY_NODE_DISPLAY_FOOTER);
+    ref_node_kinds = format!("{}{}\n", ref_node_kinds, REF_NODE_KINDS_FOOTER);
     let _ = write!(out, "{}", ref_node);
     let _ = write!(out, "{}", any_node);
     let _ = write!(out, "{}", ref_node_display);
     let _ = write!(out, "{}", any_node_display);
+    let _ = write!(out, "{}", ref_node_kinds);
 }
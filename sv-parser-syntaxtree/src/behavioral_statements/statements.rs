@@ -44,6 +44,8 @@ pub enum StatementItem {
     RandsequenceStatement(Box<RandsequenceStatement>),
     RandcaseStatement(Box<RandcaseStatement>),
     ExpectPropertyStatement(Box<ExpectPropertyStatement>),
+    TextMacroUsage(Box<(TextMacroUsage, Option<Symbol>)>),
+    ContributionStatement(Box<ContributionStatement>),
 }
 
 #[derive(Clone, Debug, PartialEq, Node)]
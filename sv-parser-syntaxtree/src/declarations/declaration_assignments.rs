@@ -138,3 +138,11 @@ pub struct ClassNewExpression {
 pub struct DynamicArrayNew {
     pub nodes: (Keyword, Bracket<Expression>, Option<Paren<Expression>>),
 }
+
+// -----------------------------------------------------------------------------
+
+impl ParamAssignment {
+    pub fn name(&self) -> &ParameterIdentifier {
+        &self.nodes.0
+    }
+}
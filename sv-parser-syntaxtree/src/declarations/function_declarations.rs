@@ -10,7 +10,12 @@ pub enum FunctionDataTypeOrImplicit {
 
 #[derive(Clone, Debug, PartialEq, Node)]
 pub struct FunctionDeclaration {
-    pub nodes: (Keyword, Option<Lifetime>, FunctionBodyDeclaration),
+    pub nodes: (
+        Keyword,
+        Option<DynamicOverrideSpecifiers>,
+        Option<Lifetime>,
+        FunctionBodyDeclaration,
+    ),
 }
 
 #[derive(Clone, Debug, PartialEq, Node)]
@@ -58,6 +63,7 @@ pub enum InterfaceIdentifierOrClassScope {
 pub struct FunctionPrototype {
     pub nodes: (
         Keyword,
+        Option<DynamicOverrideSpecifiers>,
         DataTypeOrVoid,
         FunctionIdentifier,
         Option<Paren<Option<TfPortList>>>,
@@ -146,3 +152,67 @@ pub struct DpiFunctionProto {
 pub struct DpiTaskProto {
     pub nodes: (TaskPrototype,),
 }
+
+// -----------------------------------------------------------------------------
+
+impl FunctionDeclaration {
+    pub fn name(&self) -> &FunctionIdentifier {
+        match self.nodes.3 {
+            FunctionBodyDeclaration::WithoutPort(ref x) => &x.nodes.2,
+            FunctionBodyDeclaration::WithPort(ref x) => &x.nodes.2,
+        }
+    }
+
+    pub fn return_type(&self) -> &FunctionDataTypeOrImplicit {
+        match self.nodes.3 {
+            FunctionBodyDeclaration::WithoutPort(ref x) => &x.nodes.0,
+            FunctionBodyDeclaration::WithPort(ref x) => &x.nodes.0,
+        }
+    }
+
+    /// Arguments in the port list, or in port declarations of the body
+    pub fn arguments(&self) -> Vec<FunctionArgument<'_>> {
+        match self.nodes.3 {
+            FunctionBodyDeclaration::WithoutPort(ref x) => {
+                let mut ret = vec![];
+                for item in &x.nodes.4 {
+                    if let TfItemDeclaration::TfPortDeclaration(ref x) = item {
+                        for (name, _, _) in (x.nodes.4).nodes.0.contents() {
+                            ret.push(FunctionArgument::PortDeclaration(x, name));
+                        }
+                    }
+                }
+                ret
+            }
+            FunctionBodyDeclaration::WithPort(ref x) => match (x.nodes.3).nodes.1 {
+                Some(ref x) => x
+                    .nodes
+                    .0
+                    .contents()
+                    .into_iter()
+                    .map(FunctionArgument::PortItem)
+                    .collect(),
+                None => vec![],
+            },
+        }
+    }
+}
+
+/// An argument of `FunctionDeclaration`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FunctionArgument<'a> {
+    /// An item of `function f(input int a);`
+    PortItem(&'a TfPortItem),
+    /// A name declared by `input int a, b;` in the body
+    PortDeclaration(&'a TfPortDeclaration, &'a PortIdentifier),
+}
+
+impl<'a> FunctionArgument<'a> {
+    /// `None` for items without name like in prototypes
+    pub fn name(&self) -> Option<&'a PortIdentifier> {
+        match self {
+            FunctionArgument::PortItem(x) => x.nodes.4.as_ref().map(|x| &x.0),
+            FunctionArgument::PortDeclaration(_, x) => Some(x),
+        }
+    }
+}
@@ -258,6 +258,7 @@ pub enum DataTypeOrVoid {
 pub enum StructUnion {
     Struct(Box<Keyword>),
     Union(Box<Keyword>),
+    UnionSoft(Box<(Keyword, Keyword)>),
     UnionTagged(Box<(Keyword, Keyword)>),
 }
 
@@ -74,6 +74,7 @@ pub struct NonZeroUnsignedNumber {
 pub enum RealNumber {
     FixedPointNumber(Box<FixedPointNumber>),
     Floating(Box<RealNumberFloating>),
+    ScaleFactor(Box<RealNumberScaleFactor>),
 }
 
 #[derive(Clone, Debug, PartialEq, Node)]
@@ -87,6 +88,15 @@ pub struct RealNumberFloating {
     ),
 }
 
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct RealNumberScaleFactor {
+    pub nodes: (
+        UnsignedNumber,
+        Option<(Symbol, UnsignedNumber)>,
+        ScaleFactor,
+    ),
+}
+
 #[derive(Clone, Debug, PartialEq, Node)]
 pub struct FixedPointNumber {
     pub nodes: (UnsignedNumber, Symbol, UnsignedNumber),
@@ -97,6 +107,11 @@ pub struct Exp {
     pub nodes: (Symbol,),
 }
 
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct ScaleFactor {
+    pub nodes: (Symbol,),
+}
+
 #[derive(Clone, Debug, PartialEq, Node)]
 pub struct UnsignedNumber {
     pub nodes: (Locate, Vec<WhiteSpace>),
@@ -98,6 +98,8 @@ pub enum Primary {
     This(Box<Keyword>),
     Dollar(Box<Keyword>),
     Null(Box<Keyword>),
+    TextMacroUsage(Box<TextMacroUsage>),
+    AnalogBuiltinCall(Box<AnalogBuiltinCall>),
 }
 
 #[derive(Clone, Debug, PartialEq, Node)]
@@ -18,6 +18,7 @@ pub enum CompilerDirective {
     CelldefineDriveCompilerDirective(Box<CelldefineDriveCompilerDirective>),
     EndcelldefineDriveCompilerDirective(Box<EndcelldefineDriveCompilerDirective>),
     Pragma(Box<Pragma>),
+    ProtectedEnvelope(Box<ProtectedEnvelope>),
     LineCompilerDirective(Box<LineCompilerDirective>),
     PositionCompilerDirective(Box<PositionCompilerDirective>),
     KeywordsDirective(Box<KeywordsDirective>),
@@ -276,6 +277,22 @@ pub struct PragmaKeyword {
     pub nodes: (SimpleIdentifier,),
 }
 
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct ProtectedEnvelope {
+    pub nodes: (Pragma, Vec<ProtectedEnvelopeItem>, Pragma),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum ProtectedEnvelopeItem {
+    Pragma(Box<Pragma>),
+    ProtectedBlock(Box<ProtectedBlock>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct ProtectedBlock {
+    pub nodes: (Locate,),
+}
+
 #[derive(Clone, Debug, PartialEq, Node)]
 pub struct LineCompilerDirective {
     pub nodes: (Symbol, Keyword, Number, StringLiteral, Level),
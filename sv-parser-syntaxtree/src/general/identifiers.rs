@@ -527,3 +527,89 @@ pub enum PackageScopeOrClassScope {
     PackageScope(Box<PackageScope>),
     ClassScope(Box<ClassScope>),
 }
+
+// -----------------------------------------------------------------------------
+
+impl Identifier {
+    /// The token of the identifier, including `\` of escaped identifiers
+    pub fn locate(&self) -> &Locate {
+        match self {
+            Identifier::SimpleIdentifier(x) => &x.nodes.0,
+            Identifier::EscapedIdentifier(x) => &x.nodes.0,
+        }
+    }
+
+    pub fn is_escaped(&self) -> bool {
+        matches!(self, Identifier::EscapedIdentifier(_))
+    }
+}
+
+impl AsRef<Identifier> for Identifier {
+    fn as_ref(&self) -> &Identifier {
+        self
+    }
+}
+
+macro_rules! impl_as_ref_identifier {
+    ($($x:ident),*) => {
+        $(
+            impl AsRef<Identifier> for $x {
+                fn as_ref(&self) -> &Identifier {
+                    &self.nodes.0
+                }
+            }
+        )*
+    };
+}
+
+impl_as_ref_identifier!(
+    ArrayIdentifier,
+    BlockIdentifier,
+    BinIdentifier,
+    CellIdentifier,
+    CheckerIdentifier,
+    ClassIdentifier,
+    ClockingIdentifier,
+    ConfigIdentifier,
+    ConstIdentifier,
+    ConstraintIdentifier,
+    CovergroupIdentifier,
+    CoverPointIdentifier,
+    CrossIdentifier,
+    EnumIdentifier,
+    FormalIdentifier,
+    FormalPortIdentifier,
+    FunctionIdentifier,
+    GenerateBlockIdentifier,
+    GenvarIdentifier,
+    IndexVariableIdentifier,
+    InterfaceIdentifier,
+    InterfaceInstanceIdentifier,
+    InoutPortIdentifier,
+    InputPortIdentifier,
+    InstanceIdentifier,
+    LibraryIdentifier,
+    MemberIdentifier,
+    MethodIdentifier,
+    ModportIdentifier,
+    ModuleIdentifier,
+    NetIdentifier,
+    NetTypeIdentifier,
+    OutputPortIdentifier,
+    PackageIdentifier,
+    ParameterIdentifier,
+    PortIdentifier,
+    ProductionIdentifier,
+    ProgramIdentifier,
+    PropertyIdentifier,
+    SequenceIdentifier,
+    SignalIdentifier,
+    SpecparamIdentifier,
+    TaskIdentifier,
+    TfIdentifier,
+    TerminalIdentifier,
+    TopmoduleIdentifier,
+    TypeIdentifier,
+    UdpIdentifier,
+    VariableIdentifier
+);
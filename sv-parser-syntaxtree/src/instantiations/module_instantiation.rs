@@ -94,3 +94,70 @@ pub struct NamedPortConnectionIdentifier {
 pub struct NamedPortConnectionAsterisk {
     pub nodes: (Vec<AttributeInstance>, Symbol),
 }
+
+// -----------------------------------------------------------------------------
+
+impl ModuleInstantiation {
+    pub fn module_name(&self) -> &ModuleIdentifier {
+        &self.nodes.0
+    }
+
+    pub fn instances(&self) -> Vec<&HierarchicalInstance> {
+        self.nodes.2.contents()
+    }
+}
+
+impl HierarchicalInstance {
+    pub fn name(&self) -> &InstanceIdentifier {
+        &(self.nodes.0).nodes.0
+    }
+
+    pub fn connections(&self) -> Vec<PortConnection<'_>> {
+        match (self.nodes.1).nodes.1 {
+            Some(ListOfPortConnections::Ordered(ref x)) => x
+                .nodes
+                .0
+                .contents()
+                .into_iter()
+                .map(PortConnection::Ordered)
+                .collect(),
+            Some(ListOfPortConnections::Named(ref x)) => x
+                .nodes
+                .0
+                .contents()
+                .into_iter()
+                .map(PortConnection::Named)
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
+/// A port connection of `HierarchicalInstance`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PortConnection<'a> {
+    Ordered(&'a OrderedPortConnection),
+    Named(&'a NamedPortConnection),
+}
+
+impl<'a> PortConnection<'a> {
+    /// `None` for ordered connections and `.*`
+    pub fn name(&self) -> Option<&'a PortIdentifier> {
+        match self {
+            PortConnection::Named(NamedPortConnection::Identifier(x)) => Some(&x.nodes.2),
+            _ => None,
+        }
+    }
+
+    /// `None` for unconnected ports, `.name` and `.*`
+    pub fn expression(&self) -> Option<&'a Expression> {
+        match self {
+            PortConnection::Ordered(x) => x.nodes.1.as_ref(),
+            PortConnection::Named(NamedPortConnection::Identifier(x)) => match x.nodes.3 {
+                Some(ref x) => x.nodes.1.as_ref(),
+                None => None,
+            },
+            PortConnection::Named(NamedPortConnection::Asterisk(_)) => None,
+        }
+    }
+}
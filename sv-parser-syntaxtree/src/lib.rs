@@ -17,6 +17,7 @@ pub mod source_text;
 pub mod special_node;
 pub mod specify_section;
 pub mod udp_declaration_and_instantiation;
+pub mod verilog_ams;
 pub use any_node::*;
 pub use behavioral_statements::*;
 pub use declarations::*;
@@ -29,6 +30,7 @@ pub use source_text::*;
 pub use special_node::*;
 pub use specify_section::*;
 pub use udp_declaration_and_instantiation::*;
+pub use verilog_ams::*;
 
 pub(crate) use sv_parser_macros::*;
 
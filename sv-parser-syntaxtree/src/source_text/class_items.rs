@@ -116,7 +116,12 @@ pub struct ClassMethodExternConstructor {
 
 #[derive(Clone, Debug, PartialEq, Node)]
 pub struct ClassConstructorPrototype {
-    pub nodes: (Keyword, Keyword, Option<Paren<Option<TfPortList>>>, Symbol),
+    pub nodes: (
+        Keyword,
+        Keyword,
+        Option<Paren<Option<ClassConstructorArgList>>>,
+        Symbol,
+    ),
 }
 
 #[derive(Clone, Debug, PartialEq, Node)]
@@ -163,14 +168,14 @@ pub struct ClassConstructorDeclaration {
         Keyword,
         Option<ClassScope>,
         Keyword,
-        Option<Paren<Option<TfPortList>>>,
+        Option<Paren<Option<ClassConstructorArgList>>>,
         Symbol,
         Vec<BlockItemDeclaration>,
         Option<(
             Keyword,
             Symbol,
             Keyword,
-            Option<Paren<ListOfArguments>>,
+            Option<Paren<ListOfArgumentsOrDefault>>,
             Symbol,
         )>,
         Vec<FunctionStatementOrNull>,
@@ -179,7 +184,40 @@ pub struct ClassConstructorDeclaration {
     ),
 }
 
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct ClassConstructorArgList {
+    pub nodes: (List<Symbol, ClassConstructorArg>,),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum ClassConstructorArg {
+    TfPortItem(Box<TfPortItem>),
+    Default(Box<Keyword>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum ListOfArgumentsOrDefault {
+    ListOfArguments(Box<ListOfArguments>),
+    Default(Box<Keyword>),
+}
+
 #[derive(Clone, Debug, PartialEq, Node)]
 pub struct New {
     pub nodes: (Keyword,),
 }
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct DynamicOverrideSpecifiers {
+    pub nodes: (Option<InitialOrExtendsSpecifier>, Option<FinalSpecifier>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum InitialOrExtendsSpecifier {
+    Initial(Box<(Symbol, Keyword)>),
+    Extends(Box<(Symbol, Keyword)>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct FinalSpecifier {
+    pub nodes: (Symbol, Keyword),
+}
@@ -71,6 +71,7 @@ pub enum ModuleOrGenerateItem {
     Udp(Box<ModuleOrGenerateItemUdp>),
     Module(Box<ModuleOrGenerateItemModule>),
     ModuleItem(Box<ModuleOrGenerateItemModuleItem>),
+    Analog(Box<ModuleOrGenerateItemAnalog>),
 }
 
 #[derive(Clone, Debug, PartialEq, Node)]
@@ -98,6 +99,11 @@ pub struct ModuleOrGenerateItemModuleItem {
     pub nodes: (Vec<AttributeInstance>, ModuleCommonItem),
 }
 
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct ModuleOrGenerateItemAnalog {
+    pub nodes: (Vec<AttributeInstance>, AnalogModuleItem),
+}
+
 #[derive(Clone, Debug, PartialEq, Node)]
 pub enum ModuleOrGenerateItemDeclaration {
     PackageOrGenerateItemDeclaration(Box<PackageOrGenerateItemDeclaration>),
@@ -127,6 +133,7 @@ pub enum NonPortModuleItem {
     ModuleDeclaration(Box<ModuleDeclaration>),
     InterfaceDeclaration(Box<InterfaceDeclaration>),
     TimeunitsDeclaration(Box<TimeunitsDeclaration>),
+    TextMacroUsage(Box<(TextMacroUsage, Option<Symbol>)>),
 }
 
 #[derive(Clone, Debug, PartialEq, Node)]
@@ -194,3 +194,102 @@ pub struct AnsiPortDeclarationParen {
         Paren<Option<Expression>>,
     ),
 }
+
+// -----------------------------------------------------------------------------
+
+impl ParameterPortList {
+    /// Value parameters, excluding type parameters
+    pub fn param_assignments(&self) -> Vec<&ParamAssignment> {
+        match self {
+            ParameterPortList::Assignment(x) => {
+                let (ref list, ref decls) = (x.nodes.1).nodes.1;
+                let mut ret = list.nodes.0.contents();
+                for (_, x) in decls {
+                    ret.append(&mut x.param_assignments());
+                }
+                ret
+            }
+            ParameterPortList::Declaration(x) => (x.nodes.1)
+                .nodes
+                .1
+                .contents()
+                .into_iter()
+                .flat_map(|x| x.param_assignments())
+                .collect(),
+            ParameterPortList::Empty(_) => vec![],
+        }
+    }
+}
+
+impl ParameterPortDeclaration {
+    /// Value parameters, excluding type parameters
+    pub fn param_assignments(&self) -> Vec<&ParamAssignment> {
+        match self {
+            ParameterPortDeclaration::ParameterDeclaration(x) => match x.as_ref() {
+                ParameterDeclaration::Param(x) => x.nodes.2.nodes.0.contents(),
+                ParameterDeclaration::Type(_) => vec![],
+            },
+            ParameterPortDeclaration::LocalParameterDeclaration(x) => match x.as_ref() {
+                LocalParameterDeclaration::Param(x) => x.nodes.2.nodes.0.contents(),
+                LocalParameterDeclaration::Type(_) => vec![],
+            },
+            ParameterPortDeclaration::ParamList(x) => x.nodes.1.nodes.0.contents(),
+            ParameterPortDeclaration::TypeList(_) => vec![],
+        }
+    }
+}
+
+/// A port of `ModuleDeclaration`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ModulePort<'a> {
+    Ansi(&'a AnsiPortDeclaration),
+    Nonansi(&'a Port),
+}
+
+impl<'a> ModulePort<'a> {
+    /// `None` for unnamed non-ANSI ports like `{a, b}`
+    pub fn name(&self) -> Option<&'a PortIdentifier> {
+        match self {
+            ModulePort::Ansi(x) => Some(x.name()),
+            ModulePort::Nonansi(x) => x.name(),
+        }
+    }
+}
+
+impl ListOfPorts {
+    pub fn ports(&self) -> Vec<&Port> {
+        (self.nodes.0).nodes.1.contents()
+    }
+}
+
+impl ListOfPortDeclarations {
+    pub fn ports(&self) -> Vec<&AnsiPortDeclaration> {
+        match (self.nodes.0).nodes.1 {
+            Some(ref x) => x.contents().into_iter().map(|(_, x)| x).collect(),
+            None => vec![],
+        }
+    }
+}
+
+impl Port {
+    /// `None` for unnamed ports like `{a, b}`
+    pub fn name(&self) -> Option<&PortIdentifier> {
+        match self {
+            Port::NonNamed(x) => match x.nodes.0 {
+                Some(PortExpression::PortReference(ref x)) => Some(&x.nodes.0),
+                _ => None,
+            },
+            Port::Named(x) => Some(&x.nodes.1),
+        }
+    }
+}
+
+impl AnsiPortDeclaration {
+    pub fn name(&self) -> &PortIdentifier {
+        match self {
+            AnsiPortDeclaration::Net(x) => &x.nodes.1,
+            AnsiPortDeclaration::Variable(x) => &x.nodes.1,
+            AnsiPortDeclaration::Paren(x) => &x.nodes.2,
+        }
+    }
+}
@@ -23,6 +23,8 @@ pub enum Description {
     PackageItem(Box<DescriptionPackageItem>),
     BindDirective(Box<DescriptionBindDirective>),
     ConfigDeclaration(Box<ConfigDeclaration>),
+    NatureDeclaration(Box<NatureDeclaration>),
+    DisciplineDeclaration(Box<DisciplineDeclaration>),
 }
 
 #[derive(Clone, Debug, PartialEq, Node)]
@@ -313,10 +315,11 @@ pub struct ClassDeclaration {
     pub nodes: (
         Option<Virtual>,
         Keyword,
+        Option<FinalSpecifier>,
         Option<Lifetime>,
         ClassIdentifier,
         Option<ParameterPortList>,
-        Option<(Keyword, ClassType, Option<Paren<ListOfArguments>>)>,
+        Option<(Keyword, ClassType, Option<Paren<ListOfArgumentsOrDefault>>)>,
         Option<(Keyword, List<Symbol, InterfaceClassType>)>,
         Symbol,
         Vec<ClassItem>,
@@ -332,7 +335,11 @@ pub struct Virtual {
 
 #[derive(Clone, Debug, PartialEq, Node)]
 pub struct InterfaceClassType {
-    pub nodes: (PsClassIdentifier, Option<ParameterValueAssignment>),
+    pub nodes: (
+        PsClassIdentifier,
+        Option<ParameterValueAssignment>,
+        Vec<(Symbol, ClassIdentifier, Option<ParameterValueAssignment>)>,
+    ),
 }
 
 #[derive(Clone, Debug, PartialEq, Node)]
@@ -411,3 +418,74 @@ pub struct TimeunitsDeclarationTimeunitTimeprecision {
 pub struct TimeunitsDeclarationTimeprecisionTimeunit {
     pub nodes: (Keyword, TimeLiteral, Symbol, Keyword, TimeLiteral, Symbol),
 }
+
+// -----------------------------------------------------------------------------
+
+impl ModuleDeclaration {
+    pub fn name(&self) -> &ModuleIdentifier {
+        match self {
+            ModuleDeclaration::Nonansi(x) => &(x.nodes.0).nodes.3,
+            ModuleDeclaration::Ansi(x) => &(x.nodes.0).nodes.3,
+            ModuleDeclaration::Wildcard(x) => &x.nodes.3,
+            ModuleDeclaration::ExternNonansi(x) => &(x.nodes.1).nodes.3,
+            ModuleDeclaration::ExternAnsi(x) => &(x.nodes.1).nodes.3,
+        }
+    }
+
+    /// Value parameters of the header, excluding type parameters
+    pub fn parameters(&self) -> Vec<&ParamAssignment> {
+        let list = match self {
+            ModuleDeclaration::Nonansi(x) => &(x.nodes.0).nodes.5,
+            ModuleDeclaration::Ansi(x) => &(x.nodes.0).nodes.5,
+            ModuleDeclaration::Wildcard(_) => &None,
+            ModuleDeclaration::ExternNonansi(x) => &(x.nodes.1).nodes.5,
+            ModuleDeclaration::ExternAnsi(x) => &(x.nodes.1).nodes.5,
+        };
+        match list {
+            Some(x) => x.param_assignments(),
+            None => vec![],
+        }
+    }
+
+    /// Ports of the header, which are empty for `module m(.*);`
+    pub fn ports(&self) -> Vec<ModulePort<'_>> {
+        fn nonansi(x: &ModuleNonansiHeader) -> Vec<ModulePort<'_>> {
+            x.nodes
+                .6
+                .ports()
+                .into_iter()
+                .map(ModulePort::Nonansi)
+                .collect()
+        }
+        fn ansi(x: &ModuleAnsiHeader) -> Vec<ModulePort<'_>> {
+            match x.nodes.6 {
+                Some(ref x) => x.ports().into_iter().map(ModulePort::Ansi).collect(),
+                None => vec![],
+            }
+        }
+        match self {
+            ModuleDeclaration::Nonansi(x) => nonansi(&x.nodes.0),
+            ModuleDeclaration::Ansi(x) => ansi(&x.nodes.0),
+            ModuleDeclaration::Wildcard(_) => vec![],
+            ModuleDeclaration::ExternNonansi(x) => nonansi(&x.nodes.1),
+            ModuleDeclaration::ExternAnsi(x) => ansi(&x.nodes.1),
+        }
+    }
+
+    /// Items of the body except port declarations
+    pub fn items(&self) -> Vec<&NonPortModuleItem> {
+        let items = match self {
+            ModuleDeclaration::Nonansi(x) => &x.nodes.2,
+            ModuleDeclaration::Ansi(x) => return x.nodes.2.iter().collect(),
+            ModuleDeclaration::Wildcard(x) => &x.nodes.7,
+            ModuleDeclaration::ExternNonansi(_) | ModuleDeclaration::ExternAnsi(_) => return vec![],
+        };
+        items
+            .iter()
+            .filter_map(|x| match x {
+                ModuleItem::NonPortModuleItem(x) => Some(x.as_ref()),
+                _ => None,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,38 @@
+use crate::*;
+
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum AnalogModuleItem {
+    AnalogConstruct(Box<AnalogConstruct>),
+    AnalogFunctionDeclaration(Box<AnalogFunctionDeclaration>),
+    BranchDeclaration(Box<BranchDeclaration>),
+    GroundDeclaration(Box<GroundDeclaration>),
+    NetDisciplineDeclaration(Box<NetDisciplineDeclaration>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum AnalogConstruct {
+    Analog(Box<(Keyword, Statement)>),
+    AnalogInitial(Box<(Keyword, Keyword, Statement)>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct AnalogFunctionDeclaration {
+    pub nodes: (Keyword, FunctionDeclaration),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct ContributionStatement {
+    pub nodes: (BranchProbeFunctionCall, Symbol, Expression, Symbol),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct BranchProbeFunctionCall {
+    pub nodes: (NatureAccessIdentifier, Paren<List<Symbol, NetLvalue>>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct AnalogBuiltinCall {
+    pub nodes: (Keyword, Option<Paren<ListOfArguments>>),
+}
@@ -0,0 +1,134 @@
+use crate::*;
+
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct NatureDeclaration {
+    pub nodes: (
+        Keyword,
+        NatureIdentifier,
+        Option<(Symbol, ParentNature)>,
+        Option<Symbol>,
+        Vec<NatureItem>,
+        Keyword,
+    ),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum ParentNature {
+    Nature(Box<NatureIdentifier>),
+    Discipline(Box<(DisciplineIdentifier, Symbol, PotentialOrFlow)>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct NatureItem {
+    pub nodes: (NatureAttributeIdentifier, Symbol, ConstantExpression, Symbol),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum NatureAttributeIdentifier {
+    Keyword(Box<Keyword>),
+    Identifier(Box<Identifier>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct DisciplineDeclaration {
+    pub nodes: (
+        Keyword,
+        DisciplineIdentifier,
+        Option<Symbol>,
+        Vec<DisciplineItem>,
+        Keyword,
+    ),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum DisciplineItem {
+    NatureBinding(Box<NatureBinding>),
+    DisciplineDomainBinding(Box<DisciplineDomainBinding>),
+    NatureAttributeOverride(Box<NatureAttributeOverride>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct NatureBinding {
+    pub nodes: (PotentialOrFlow, NatureIdentifier, Symbol),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum PotentialOrFlow {
+    Potential(Box<Keyword>),
+    Flow(Box<Keyword>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct DisciplineDomainBinding {
+    pub nodes: (Keyword, DiscreteOrContinuous, Symbol),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub enum DiscreteOrContinuous {
+    Discrete(Box<Keyword>),
+    Continuous(Box<Keyword>),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct NatureAttributeOverride {
+    pub nodes: (
+        PotentialOrFlow,
+        Symbol,
+        NatureAttributeIdentifier,
+        Symbol,
+        ConstantExpression,
+        Symbol,
+    ),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct NetDisciplineDeclaration {
+    pub nodes: (
+        DisciplineIdentifier,
+        Option<PackedDimension>,
+        List<Symbol, NetIdentifier>,
+        Symbol,
+    ),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct GroundDeclaration {
+    pub nodes: (
+        Keyword,
+        Option<PackedDimension>,
+        List<Symbol, NetIdentifier>,
+        Symbol,
+    ),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct BranchDeclaration {
+    pub nodes: (
+        Keyword,
+        Paren<(NetLvalue, Option<(Symbol, NetLvalue)>)>,
+        List<Symbol, BranchIdentifier>,
+        Symbol,
+    ),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct NatureIdentifier {
+    pub nodes: (Identifier,),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct DisciplineIdentifier {
+    pub nodes: (Identifier,),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct BranchIdentifier {
+    pub nodes: (Identifier,),
+}
+
+#[derive(Clone, Debug, PartialEq, Node)]
+pub struct NatureAccessIdentifier {
+    pub nodes: (Identifier,),
+}
@@ -0,0 +1,4 @@
+pub mod analog_blocks;
+pub mod disciplines_and_natures;
+pub use analog_blocks::*;
+pub use disciplines_and_natures::*;
@@ -5,7 +5,7 @@ extern crate test;
 use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
-use sv_parser::parse_sv;
+use sv_parser::{parse_sv, ParseOptions};
 use test::Bencher;
 
 fn get_path(s: &str) -> PathBuf {
@@ -22,7 +22,7 @@ fn test1(b: &mut Bencher) {
     let includes: Vec<PathBuf> = Vec::new();
     let path = get_path("test1.sv");
     b.iter(|| {
-        let _ = parse_sv(&path, &defines, &includes, false, false);
+        let _ = parse_sv(&path, &defines, &includes, &ParseOptions::default());
     });
 }
 
@@ -32,6 +32,6 @@ fn test2(b: &mut Bencher) {
     let includes: Vec<PathBuf> = Vec::new();
     let path = get_path("test2.sv");
     b.iter(|| {
-        let _ = parse_sv(&path, &defines, &includes, false, false);
+        let _ = parse_sv(&path, &defines, &includes, &ParseOptions::default());
     });
 }
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use std::{env, fs};
-use sv_parser::parse_sv;
+use sv_parser::{parse_sv, ParseOptions};
 
 fn get_path(s: &str) -> PathBuf {
     PathBuf::from(format!(
@@ -26,7 +26,7 @@ fn gen_benchmark_group(c: &mut Criterion, s: &str) {
     let mut group = c.benchmark_group(s);
     group.throughput(Throughput::Bytes(size));
     group.bench_function(s, |b| {
-        b.iter_with_large_drop(|| parse_sv(&path, &defines, &includes, false, false))
+        b.iter_with_large_drop(|| parse_sv(&path, &defines, &includes, &ParseOptions::default()))
     });
     group.finish();
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
-use sv_parser::{parse_sv, unwrap_node, Locate, RefNode};
+use sv_parser::{parse_sv, IdentifierText, ParseOptions, RefNode};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -14,46 +14,24 @@ fn main() {
     let includes: Vec<PathBuf> = Vec::new();
 
     // Parse
-    let result = parse_sv(&path, &defines, &includes, false, false);
+    let result = parse_sv(&path, &defines, &includes, &ParseOptions::default());
 
     if let Ok((syntax_tree, _)) = result {
         // &SyntaxTree is iterable
         for node in &syntax_tree {
             // The type of each node is RefNode
-            match node {
-                RefNode::ModuleDeclarationNonansi(x) => {
-                    // unwrap_node! gets the nearest ModuleIdentifier from x
-                    let id = unwrap_node!(x, ModuleIdentifier).unwrap();
-
-                    let id = get_identifier(id).unwrap();
-
-                    // Original string can be got by SyntexTree::get_str(self, node: &RefNode)
-                    let id = syntax_tree.get_str(&id).unwrap();
-                    println!("module: {}", id);
+            if let RefNode::ModuleDeclaration(x) = node {
+                // Accessors return typed nodes, and IdentifierText::text gets
+                // the original string of identifiers from SyntaxTree
+                println!("module: {}", x.name().text(&syntax_tree));
+                for port in x.ports() {
+                    if let Some(name) = port.name() {
+                        println!("  port: {}", name.text(&syntax_tree));
+                    }
                 }
-                RefNode::ModuleDeclarationAnsi(x) => {
-                    let id = unwrap_node!(x, ModuleIdentifier).unwrap();
-                    let id = get_identifier(id).unwrap();
-                    let id = syntax_tree.get_str(&id).unwrap();
-                    println!("module: {}", id);
-                }
-                _ => (),
             }
         }
     } else {
         println!("Parse failed");
     }
 }
-
-fn get_identifier(node: RefNode) -> Option<Locate> {
-    // unwrap_node! can take multiple types
-    match unwrap_node!(node, SimpleIdentifier, EscapedIdentifier) {
-        Some(RefNode::SimpleIdentifier(x)) => {
-            return Some(x.nodes.0);
-        }
-        Some(RefNode::EscapedIdentifier(x)) => {
-            return Some(x.nodes.0);
-        }
-        _ => None,
-    }
-}
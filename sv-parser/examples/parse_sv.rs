@@ -5,9 +5,9 @@ use std::io::Read;
 use std::path::PathBuf;
 use std::{cmp, process};
 use structopt::StructOpt;
-use sv_parser::{parse_sv, Define, DefineText};
+use sv_parser::{parse_sv, Define, DefineText, ParseOptions};
 use sv_parser_error::Error;
-use sv_parser_pp::preprocess::preprocess;
+use sv_parser_pp::preprocess::{preprocess, PreprocessOptions};
 
 #[derive(StructOpt)]
 struct Opt {
@@ -66,8 +66,7 @@ fn main() {
                         &path,
                         &defines,
                         &opt.includes,
-                        false, // strip_comments
-                        false, // ignore_include
+                        &PreprocessOptions::default(),
                     ) {
                         Ok((preprocessed_text, new_defines)) => {
                             println!("{}", preprocessed_text.text());
@@ -76,7 +75,11 @@ fn main() {
                         _ => (),
                     }
                 } else {
-                    match parse_sv(&path, &defines, &opt.includes, false, opt.incomplete) {
+                    let options = ParseOptions {
+                        allow_incomplete: opt.incomplete,
+                        ..ParseOptions::default()
+                    };
+                    match parse_sv(&path, &defines, &opt.includes, &options) {
                         Ok((syntax_tree, new_defines)) => {
                             if opt.tree {
                                 println!("{}", syntax_tree);
@@ -0,0 +1,1534 @@
+use crate::*;
+use std::collections::HashMap;
+use sv_parser_parser::utils::is_reserved_word;
+
+/// Construction of syntax tree nodes without source text
+///
+/// Tokens of the built nodes refer to the text held by the builder, so nodes
+/// have to be printed by the builder which built them. `Locate::line` of the
+/// tokens is always 1.
+#[derive(Clone, Debug, Default)]
+pub struct Builder {
+    text: String,
+}
+
+/// Nodes which can be placed in the body of a module
+pub trait IntoModuleItem {
+    fn into_module_item(self) -> NonPortModuleItem;
+}
+
+/// Base of `Builder::sized_number`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberBase {
+    Binary,
+    Octal,
+    Decimal,
+    Hex,
+}
+
+/// Keyword of `Builder::vector_type`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorKind {
+    Bit,
+    Logic,
+    Reg,
+}
+
+/// Direction of `Builder::ansi_port_declaration`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Input,
+    Output,
+    Inout,
+    Ref,
+}
+
+/// Keyword of `Builder::always_construct`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlwaysKind {
+    Always,
+    AlwaysComb,
+    AlwaysLatch,
+    AlwaysFf,
+}
+
+/// Edge of an event of `Builder::event_control_statement`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    Posedge,
+    Negedge,
+    Edge,
+}
+
+/// Operator of `Builder::expression_unary`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnaryOp {
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+    /// `!`
+    LogicalNot,
+    /// `~`
+    Not,
+    /// `&`
+    And,
+    /// `~&`
+    Nand,
+    /// `|`
+    Or,
+    /// `~|`
+    Nor,
+    /// `^`
+    Xor,
+    /// `~^`
+    Xnor,
+}
+
+/// Operator of `Builder::expression_binary` and `Builder::constant_binary`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    /// `**`
+    Power,
+    /// `*`
+    Mul,
+    /// `/`
+    Div,
+    /// `%`
+    Rem,
+    /// `+`
+    Add,
+    /// `-`
+    Sub,
+    /// `<<`
+    Shl,
+    /// `>>`
+    Shr,
+    /// `<<<`
+    ArithShl,
+    /// `>>>`
+    ArithShr,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `===`
+    CaseEq,
+    /// `!==`
+    CaseNe,
+    /// `==?`
+    WildcardEq,
+    /// `!=?`
+    WildcardNe,
+    /// `&`
+    And,
+    /// `^`
+    Xor,
+    /// `~^`
+    Xnor,
+    /// `|`
+    Or,
+    /// `&&`
+    LogicalAnd,
+    /// `||`
+    LogicalOr,
+    /// `->`
+    Implication,
+    /// `<->`
+    Equivalence,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Builder {
+            text: String::new(),
+        }
+    }
+
+    /// Create a builder holding the text of `tree`, so nodes cloned from
+    /// `tree` can be combined with built nodes and printed. White spaces and
+    /// comments of the cloned nodes are not printed.
+    pub fn from_syntax_tree(tree: &SyntaxTree) -> Self {
+        Builder {
+            text: String::from(tree.text.text()),
+        }
+    }
+
+    fn locate(&mut self, s: &str) -> Locate {
+        let offset = self.text.len();
+        self.text.push_str(s);
+        Locate {
+            offset,
+            line: 1,
+            len: s.len(),
+        }
+    }
+
+    fn str(&self, locate: &Locate) -> &str {
+        &self.text[locate.offset..locate.offset + locate.len]
+    }
+
+    pub fn keyword(&mut self, s: &str) -> Keyword {
+        Keyword {
+            nodes: (self.locate(s), vec![]),
+        }
+    }
+
+    pub fn symbol(&mut self, s: &str) -> Symbol {
+        Symbol {
+            nodes: (self.locate(s), vec![]),
+        }
+    }
+
+    /// Build an identifier, which is escaped if `name` isn't a valid simple
+    /// identifier
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is empty or contains whitespace.
+    pub fn identifier(&mut self, name: &str) -> Identifier {
+        assert!(
+            !name.is_empty() && !name.contains(char::is_whitespace),
+            "invalid identifier: {:?}",
+            name
+        );
+        let simple = name.starts_with(|x: char| x.is_ascii_alphabetic() || x == '_')
+            && name
+                .chars()
+                .all(|x| x.is_ascii_alphanumeric() || x == '_' || x == '$')
+            && !is_reserved_word(name);
+        if simple {
+            Identifier::SimpleIdentifier(Box::new(SimpleIdentifier {
+                nodes: (self.locate(name), vec![]),
+            }))
+        } else {
+            let name = format!("\\{}", name);
+            Identifier::EscapedIdentifier(Box::new(EscapedIdentifier {
+                nodes: (self.locate(&name), vec![]),
+            }))
+        }
+    }
+
+    fn paren<T>(&mut self, x: T) -> Paren<T> {
+        Paren {
+            nodes: (self.symbol("("), x, self.symbol(")")),
+        }
+    }
+
+    fn bracket<T>(&mut self, x: T) -> Bracket<T> {
+        Bracket {
+            nodes: (self.symbol("["), x, self.symbol("]")),
+        }
+    }
+
+    fn list<T>(&mut self, x: Vec<T>) -> Option<List<Symbol, T>> {
+        let mut x = x.into_iter();
+        let first = x.next()?;
+        let rest = x.map(|x| (self.symbol(","), x)).collect();
+        Some(List {
+            nodes: (first, rest),
+        })
+    }
+
+    fn hierarchical_identifier(&mut self, name: &str) -> HierarchicalIdentifier {
+        HierarchicalIdentifier {
+            nodes: (None, vec![], self.identifier(name)),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+
+    /// Build an unsized decimal number
+    pub fn number(&mut self, value: u64) -> Number {
+        let x = UnsignedNumber {
+            nodes: (self.locate(&value.to_string()), vec![]),
+        };
+        Number::IntegralNumber(Box::new(IntegralNumber::DecimalNumber(Box::new(
+            DecimalNumber::UnsignedNumber(Box::new(x)),
+        ))))
+    }
+
+    /// Build a sized number like `8'hff`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn sized_number(&mut self, size: u32, base: NumberBase, value: u64) -> Number {
+        assert!(size > 0, "number size must be positive");
+        let size = Some(Size {
+            nodes: (NonZeroUnsignedNumber {
+                nodes: (self.locate(&size.to_string()), vec![]),
+            },),
+        });
+        let base_text = format!("'{}", base.as_str());
+        let x = match base {
+            NumberBase::Binary => IntegralNumber::BinaryNumber(Box::new(BinaryNumber {
+                nodes: (
+                    size,
+                    BinaryBase {
+                        nodes: (self.locate(&base_text), vec![]),
+                    },
+                    BinaryValue {
+                        nodes: (self.locate(&format!("{:b}", value)), vec![]),
+                    },
+                ),
+            })),
+            NumberBase::Octal => IntegralNumber::OctalNumber(Box::new(OctalNumber {
+                nodes: (
+                    size,
+                    OctalBase {
+                        nodes: (self.locate(&base_text), vec![]),
+                    },
+                    OctalValue {
+                        nodes: (self.locate(&format!("{:o}", value)), vec![]),
+                    },
+                ),
+            })),
+            NumberBase::Decimal => IntegralNumber::DecimalNumber(Box::new(
+                DecimalNumber::BaseUnsigned(Box::new(DecimalNumberBaseUnsigned {
+                    nodes: (
+                        size,
+                        DecimalBase {
+                            nodes: (self.locate(&base_text), vec![]),
+                        },
+                        UnsignedNumber {
+                            nodes: (self.locate(&value.to_string()), vec![]),
+                        },
+                    ),
+                })),
+            )),
+            NumberBase::Hex => IntegralNumber::HexNumber(Box::new(HexNumber {
+                nodes: (
+                    size,
+                    HexBase {
+                        nodes: (self.locate(&base_text), vec![]),
+                    },
+                    HexValue {
+                        nodes: (self.locate(&format!("{:x}", value)), vec![]),
+                    },
+                ),
+            })),
+        };
+        Number::IntegralNumber(Box::new(x))
+    }
+
+    // -------------------------------------------------------------------------
+
+    pub fn expression_identifier(&mut self, name: &str) -> Expression {
+        let x = self.hierarchical_identifier(name);
+        Expression::Primary(Box::new(Primary::Hierarchical(Box::new(
+            PrimaryHierarchical {
+                nodes: (None, x, empty_select()),
+            },
+        ))))
+    }
+
+    /// Build a bit-select like `name[index]`
+    pub fn expression_select(&mut self, name: &str, index: Expression) -> Expression {
+        let x = self.hierarchical_identifier(name);
+        let index = self.bracket(index);
+        let select = Select {
+            nodes: (
+                None,
+                BitSelect {
+                    nodes: (vec![index],),
+                },
+                None,
+            ),
+        };
+        Expression::Primary(Box::new(Primary::Hierarchical(Box::new(
+            PrimaryHierarchical {
+                nodes: (None, x, select),
+            },
+        ))))
+    }
+
+    pub fn expression_number(&mut self, number: Number) -> Expression {
+        Expression::Primary(Box::new(Primary::PrimaryLiteral(Box::new(
+            PrimaryLiteral::Number(Box::new(number)),
+        ))))
+    }
+
+    /// Build a parenthesized expression
+    pub fn expression_paren(&mut self, x: Expression) -> Expression {
+        let x = self.paren(MintypmaxExpression::Expression(Box::new(x)));
+        Expression::Primary(Box::new(Primary::MintypmaxExpression(Box::new(
+            PrimaryMintypmaxExpression { nodes: (x,) },
+        ))))
+    }
+
+    /// Build a unary operation, where the operand is parenthesized unless it
+    /// is a primary
+    pub fn expression_unary(&mut self, operator: UnaryOp, operand: Expression) -> Expression {
+        let operand = match operand {
+            Expression::Primary(x) => *x,
+            x => match self.expression_paren(x) {
+                Expression::Primary(x) => *x,
+                _ => unreachable!(),
+            },
+        };
+        let operator = UnaryOperator {
+            nodes: (self.symbol(operator.as_str()),),
+        };
+        Expression::Unary(Box::new(ExpressionUnary {
+            nodes: (operator, vec![], operand),
+        }))
+    }
+
+    /// Build a binary operation, where operands are parenthesized if the
+    /// printed text would be parsed in another order
+    pub fn expression_binary(
+        &mut self,
+        lhs: Expression,
+        operator: BinaryOp,
+        rhs: Expression,
+    ) -> Expression {
+        let (lhs_paren, rhs_paren) =
+            operand_parens(operator, self.precedence(&lhs), self.precedence(&rhs));
+        let lhs = if lhs_paren {
+            self.expression_paren(lhs)
+        } else {
+            lhs
+        };
+        let rhs = if rhs_paren {
+            self.expression_paren(rhs)
+        } else {
+            rhs
+        };
+        let operator = BinaryOperator {
+            nodes: (self.symbol(operator.as_str()),),
+        };
+        Expression::Binary(Box::new(ExpressionBinary {
+            nodes: (lhs, operator, vec![], rhs),
+        }))
+    }
+
+    /// Build a reference to a parameter
+    pub fn constant_identifier(&mut self, name: &str) -> ConstantExpression {
+        let x = PsParameterIdentifier::Scope(Box::new(PsParameterIdentifierScope {
+            nodes: (
+                None,
+                ParameterIdentifier {
+                    nodes: (self.identifier(name),),
+                },
+            ),
+        }));
+        ConstantExpression::ConstantPrimary(Box::new(ConstantPrimary::PsParameter(Box::new(
+            ConstantPrimaryPsParameter {
+                nodes: (x, empty_constant_select()),
+            },
+        ))))
+    }
+
+    pub fn constant_number(&mut self, number: Number) -> ConstantExpression {
+        ConstantExpression::ConstantPrimary(Box::new(ConstantPrimary::PrimaryLiteral(Box::new(
+            PrimaryLiteral::Number(Box::new(number)),
+        ))))
+    }
+
+    /// Build a parenthesized constant expression
+    pub fn constant_paren(&mut self, x: ConstantExpression) -> ConstantExpression {
+        let x = self.paren(ConstantMintypmaxExpression::Unary(Box::new(x)));
+        ConstantExpression::ConstantPrimary(Box::new(ConstantPrimary::MintypmaxExpression(
+            Box::new(ConstantPrimaryMintypmaxExpression { nodes: (x,) }),
+        )))
+    }
+
+    /// Build a binary operation of constant expressions, see
+    /// `expression_binary`
+    pub fn constant_binary(
+        &mut self,
+        lhs: ConstantExpression,
+        operator: BinaryOp,
+        rhs: ConstantExpression,
+    ) -> ConstantExpression {
+        let (lhs_paren, rhs_paren) = operand_parens(
+            operator,
+            self.constant_precedence(&lhs),
+            self.constant_precedence(&rhs),
+        );
+        let lhs = if lhs_paren {
+            self.constant_paren(lhs)
+        } else {
+            lhs
+        };
+        let rhs = if rhs_paren {
+            self.constant_paren(rhs)
+        } else {
+            rhs
+        };
+        let operator = BinaryOperator {
+            nodes: (self.symbol(operator.as_str()),),
+        };
+        ConstantExpression::Binary(Box::new(ConstantExpressionBinary {
+            nodes: (lhs, operator, vec![], rhs),
+        }))
+    }
+
+    fn precedence(&self, x: &Expression) -> Option<usize> {
+        match x {
+            Expression::Binary(x) => precedence(self.str(&(x.nodes.1).nodes.0.nodes.0)),
+            _ => None,
+        }
+    }
+
+    fn constant_precedence(&self, x: &ConstantExpression) -> Option<usize> {
+        match x {
+            ConstantExpression::Binary(x) => precedence(self.str(&(x.nodes.1).nodes.0.nodes.0)),
+            _ => None,
+        }
+    }
+
+    // -------------------------------------------------------------------------
+
+    /// Build a vector type like `logic [7:0]` from packed dimensions given as
+    /// pairs of MSB and LSB
+    pub fn vector_type(
+        &mut self,
+        kind: VectorKind,
+        dimensions: Vec<(ConstantExpression, ConstantExpression)>,
+    ) -> DataType {
+        let x = Box::new(self.keyword(kind.as_str()));
+        let x = match kind {
+            VectorKind::Bit => IntegerVectorType::Bit(x),
+            VectorKind::Logic => IntegerVectorType::Logic(x),
+            VectorKind::Reg => IntegerVectorType::Reg(x),
+        };
+        let mut packed = Vec::new();
+        for (msb, lsb) in dimensions {
+            let range = ConstantRange {
+                nodes: (msb, self.symbol(":"), lsb),
+            };
+            let range = self.bracket(range);
+            packed.push(PackedDimension::Range(Box::new(PackedDimensionRange {
+                nodes: (range,),
+            })));
+        }
+        DataType::Vector(Box::new(DataTypeVector {
+            nodes: (x, None, packed),
+        }))
+    }
+
+    /// Build an ANSI style port declaration like `input logic [7:0] d`
+    pub fn ansi_port_declaration(
+        &mut self,
+        direction: Direction,
+        data_type: Option<DataType>,
+        name: &str,
+    ) -> AnsiPortDeclaration {
+        let x = Box::new(self.keyword(direction.as_str()));
+        let direction = match direction {
+            Direction::Input => PortDirection::Input(x),
+            Direction::Output => PortDirection::Output(x),
+            Direction::Inout => PortDirection::Inout(x),
+            Direction::Ref => PortDirection::Ref(x),
+        };
+        let data_type = match data_type {
+            Some(x) => DataTypeOrImplicit::DataType(Box::new(x)),
+            None => DataTypeOrImplicit::ImplicitDataType(Box::new(ImplicitDataType {
+                nodes: (None, vec![]),
+            })),
+        };
+        let header = NetPortHeader {
+            nodes: (
+                Some(direction),
+                NetPortType::DataType(Box::new(NetPortTypeDataType {
+                    nodes: (None, data_type),
+                })),
+            ),
+        };
+        AnsiPortDeclaration::Net(Box::new(AnsiPortDeclarationNet {
+            nodes: (
+                Some(NetPortHeaderOrInterfacePortHeader::NetPortHeader(Box::new(
+                    header,
+                ))),
+                PortIdentifier {
+                    nodes: (self.identifier(name),),
+                },
+                vec![],
+                None,
+            ),
+        }))
+    }
+
+    /// Build a parameter declaration like `parameter W = 8` of a parameter
+    /// port list
+    pub fn parameter_port_declaration(
+        &mut self,
+        name: &str,
+        value: ConstantExpression,
+    ) -> ParameterPortDeclaration {
+        let keyword = self.keyword("parameter");
+        let assignment = ParamAssignment {
+            nodes: (
+                ParameterIdentifier {
+                    nodes: (self.identifier(name),),
+                },
+                vec![],
+                Some((
+                    self.symbol("="),
+                    ConstantParamExpression::ConstantMintypmaxExpression(Box::new(
+                        ConstantMintypmaxExpression::Unary(Box::new(value)),
+                    )),
+                )),
+            ),
+        };
+        ParameterPortDeclaration::ParameterDeclaration(Box::new(ParameterDeclaration::Param(
+            Box::new(ParameterDeclarationParam {
+                nodes: (
+                    keyword,
+                    DataTypeOrImplicit::ImplicitDataType(Box::new(ImplicitDataType {
+                        nodes: (None, vec![]),
+                    })),
+                    ListOfParamAssignments {
+                        nodes: (List {
+                            nodes: (assignment, vec![]),
+                        },),
+                    },
+                ),
+            }),
+        )))
+    }
+
+    /// Build a variable declaration like `logic [7:0] q;`
+    pub fn data_declaration(&mut self, data_type: DataType, name: &str) -> DataDeclaration {
+        let x = VariableDeclAssignment::Variable(Box::new(VariableDeclAssignmentVariable {
+            nodes: (
+                VariableIdentifier {
+                    nodes: (self.identifier(name),),
+                },
+                vec![],
+                None,
+            ),
+        }));
+        DataDeclaration::Variable(Box::new(DataDeclarationVariable {
+            nodes: (
+                None,
+                None,
+                None,
+                DataTypeOrImplicit::DataType(Box::new(data_type)),
+                ListOfVariableDeclAssignments {
+                    nodes: (List { nodes: (x, vec![]) },),
+                },
+                self.symbol(";"),
+            ),
+        }))
+    }
+
+    pub fn module_declaration_ansi(
+        &mut self,
+        name: &str,
+        parameters: Vec<ParameterPortDeclaration>,
+        ports: Vec<AnsiPortDeclaration>,
+        items: Vec<NonPortModuleItem>,
+    ) -> ModuleDeclarationAnsi {
+        let keyword = ModuleKeyword::Module(Box::new(self.keyword("module")));
+        let identifier = ModuleIdentifier {
+            nodes: (self.identifier(name),),
+        };
+        let parameters = self.list(parameters).map(|x| {
+            ParameterPortList::Declaration(Box::new(ParameterPortListDeclaration {
+                nodes: (self.symbol("#"), self.paren(x)),
+            }))
+        });
+        let ports: Vec<_> = ports.into_iter().map(|x| (vec![], x)).collect();
+        let ports = self.list(ports).map(|x| ListOfPortDeclarations {
+            nodes: (self.paren(Some(x)),),
+        });
+        let header = ModuleAnsiHeader {
+            nodes: (
+                vec![],
+                keyword,
+                None,
+                identifier,
+                vec![],
+                parameters,
+                ports,
+                self.symbol(";"),
+            ),
+        };
+        ModuleDeclarationAnsi {
+            nodes: (header, None, items, self.keyword("endmodule"), None),
+        }
+    }
+
+    /// Build an instantiation with named parameter assignments and named port
+    /// connections, where `None` leaves the port unconnected
+    pub fn module_instantiation(
+        &mut self,
+        module: &str,
+        parameters: Vec<(&str, Expression)>,
+        instance: &str,
+        ports: Vec<(&str, Option<Expression>)>,
+    ) -> ModuleInstantiation {
+        let module = ModuleIdentifier {
+            nodes: (self.identifier(module),),
+        };
+
+        let mut assignments = Vec::new();
+        for (name, value) in parameters {
+            let value = ParamExpression::MintypmaxExpression(Box::new(
+                MintypmaxExpression::Expression(Box::new(value)),
+            ));
+            assignments.push(NamedParameterAssignment {
+                nodes: (
+                    self.symbol("."),
+                    ParameterIdentifier {
+                        nodes: (self.identifier(name),),
+                    },
+                    self.paren(Some(value)),
+                ),
+            });
+        }
+        let parameters = match self.list(assignments) {
+            Some(x) => {
+                let x =
+                    ListOfParameterAssignments::Named(Box::new(ListOfParameterAssignmentsNamed {
+                        nodes: (x,),
+                    }));
+                Some(ParameterValueAssignment {
+                    nodes: (self.symbol("#"), self.paren(Some(x))),
+                })
+            }
+            None => None,
+        };
+
+        let mut connections = Vec::new();
+        for (name, value) in ports {
+            let x = NamedPortConnectionIdentifier {
+                nodes: (
+                    vec![],
+                    self.symbol("."),
+                    PortIdentifier {
+                        nodes: (self.identifier(name),),
+                    },
+                    Some(self.paren(value)),
+                ),
+            };
+            connections.push(NamedPortConnection::Identifier(Box::new(x)));
+        }
+        let connections = self.list(connections).map(|x| {
+            ListOfPortConnections::Named(Box::new(ListOfPortConnectionsNamed { nodes: (x,) }))
+        });
+
+        let instance = HierarchicalInstance {
+            nodes: (
+                NameOfInstance {
+                    nodes: (
+                        InstanceIdentifier {
+                            nodes: (self.identifier(instance),),
+                        },
+                        vec![],
+                    ),
+                },
+                self.paren(connections),
+            ),
+        };
+        ModuleInstantiation {
+            nodes: (
+                module,
+                parameters,
+                List {
+                    nodes: (instance, vec![]),
+                },
+                self.symbol(";"),
+            ),
+        }
+    }
+
+    /// Build a continuous assignment like `assign lhs = rhs;`
+    pub fn continuous_assign(&mut self, lhs: &str, rhs: Expression) -> ContinuousAssign {
+        let keyword = self.keyword("assign");
+        let lhs = NetLvalue::Identifier(Box::new(NetLvalueIdentifier {
+            nodes: (
+                PsOrHierarchicalNetIdentifier::PackageScope(Box::new(
+                    PsOrHierarchicalNetIdentifierPackageScope {
+                        nodes: (
+                            None,
+                            NetIdentifier {
+                                nodes: (self.identifier(lhs),),
+                            },
+                        ),
+                    },
+                )),
+                empty_constant_select(),
+            ),
+        }));
+        let x = NetAssignment {
+            nodes: (lhs, self.symbol("="), rhs),
+        };
+        ContinuousAssign::Net(Box::new(ContinuousAssignNet {
+            nodes: (
+                keyword,
+                None,
+                None,
+                ListOfNetAssignments {
+                    nodes: (List { nodes: (x, vec![]) },),
+                },
+                self.symbol(";"),
+            ),
+        }))
+    }
+
+    /// Build an always construct like `always_ff stmt`
+    pub fn always_construct(&mut self, kind: AlwaysKind, statement: Statement) -> AlwaysConstruct {
+        let x = Box::new(self.keyword(kind.as_str()));
+        let keyword = match kind {
+            AlwaysKind::Always => AlwaysKeyword::Always(x),
+            AlwaysKind::AlwaysComb => AlwaysKeyword::AlwaysComb(x),
+            AlwaysKind::AlwaysLatch => AlwaysKeyword::AlwaysLatch(x),
+            AlwaysKind::AlwaysFf => AlwaysKeyword::AlwaysFf(x),
+        };
+        AlwaysConstruct {
+            nodes: (keyword, statement),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+
+    fn variable_lvalue(&mut self, name: &str) -> VariableLvalue {
+        VariableLvalue::Identifier(Box::new(VariableLvalueIdentifier {
+            nodes: (
+                None,
+                HierarchicalVariableIdentifier {
+                    nodes: (self.hierarchical_identifier(name),),
+                },
+                empty_select(),
+            ),
+        }))
+    }
+
+    /// Build a blocking assignment like `lhs = rhs;`
+    pub fn blocking_assignment(&mut self, lhs: &str, rhs: Expression) -> Statement {
+        let x = OperatorAssignment {
+            nodes: (
+                self.variable_lvalue(lhs),
+                AssignmentOperator {
+                    nodes: (self.symbol("="),),
+                },
+                rhs,
+            ),
+        };
+        let x = BlockingAssignment::OperatorAssignment(Box::new(x));
+        statement(StatementItem::BlockingAssignment(Box::new((
+            x,
+            self.symbol(";"),
+        ))))
+    }
+
+    /// Build a nonblocking assignment like `lhs <= rhs;`
+    pub fn nonblocking_assignment(&mut self, lhs: &str, rhs: Expression) -> Statement {
+        let x = NonblockingAssignment {
+            nodes: (self.variable_lvalue(lhs), self.symbol("<="), None, rhs),
+        };
+        statement(StatementItem::NonblockingAssignment(Box::new((
+            x,
+            self.symbol(";"),
+        ))))
+    }
+
+    /// Build an if statement, where the `then` branch is enclosed by
+    /// `begin`-`end` if the `else` branch would be bound to an inner if
+    /// statement
+    pub fn conditional_statement(
+        &mut self,
+        condition: Expression,
+        then: Statement,
+        otherwise: Option<Statement>,
+    ) -> Statement {
+        let dangling = match &then.nodes.2 {
+            StatementItem::ConditionalStatement(x) => x.nodes.5.is_none(),
+            _ => false,
+        };
+        let then = if dangling && otherwise.is_some() {
+            self.seq_block(vec![then])
+        } else {
+            then
+        };
+        let keyword = self.keyword("if");
+        let condition = CondPredicate {
+            nodes: (List {
+                nodes: (
+                    ExpressionOrCondPattern::Expression(Box::new(condition)),
+                    vec![],
+                ),
+            },),
+        };
+        let condition = self.paren(condition);
+        let otherwise = otherwise.map(|x| {
+            (
+                self.keyword("else"),
+                StatementOrNull::Statement(Box::new(x)),
+            )
+        });
+        let x = ConditionalStatement {
+            nodes: (
+                None,
+                keyword,
+                condition,
+                StatementOrNull::Statement(Box::new(then)),
+                vec![],
+                otherwise,
+            ),
+        };
+        statement(StatementItem::ConditionalStatement(Box::new(x)))
+    }
+
+    /// Build a `begin`-`end` block
+    pub fn seq_block(&mut self, statements: Vec<Statement>) -> Statement {
+        let x = SeqBlock {
+            nodes: (
+                self.keyword("begin"),
+                None,
+                vec![],
+                statements
+                    .into_iter()
+                    .map(|x| StatementOrNull::Statement(Box::new(x)))
+                    .collect(),
+                self.keyword("end"),
+                None,
+            ),
+        };
+        statement(StatementItem::SeqBlock(Box::new(x)))
+    }
+
+    /// Build a statement with an event control like `@(posedge clk) stmt`
+    /// from events joined by `or`, where an event is an optional edge and an
+    /// expression
+    ///
+    /// # Panics
+    ///
+    /// Panics if `events` is empty.
+    pub fn event_control_statement(
+        &mut self,
+        events: Vec<(Option<Edge>, Expression)>,
+        statement: Statement,
+    ) -> Statement {
+        let mut ret: Option<EventExpression> = None;
+        for (edge, x) in events {
+            let edge = edge.map(|edge| {
+                let x = Box::new(self.keyword(edge.as_str()));
+                match edge {
+                    Edge::Posedge => EdgeIdentifier::Posedge(x),
+                    Edge::Negedge => EdgeIdentifier::Negedge(x),
+                    Edge::Edge => EdgeIdentifier::Edge(x),
+                }
+            });
+            let x = EventExpression::Expression(Box::new(EventExpressionExpression {
+                nodes: (edge, x, None),
+            }));
+            ret = Some(match ret {
+                Some(y) => EventExpression::Or(Box::new(EventExpressionOr {
+                    nodes: (y, self.keyword("or"), x),
+                })),
+                None => x,
+            });
+        }
+        let x = ret.expect("no events are given");
+        let x = EventControl::EventExpression(Box::new(EventControlEventExpression {
+            nodes: (self.symbol("@"), self.paren(x)),
+        }));
+        let x = ProceduralTimingControlStatement {
+            nodes: (
+                ProceduralTimingControl::EventControl(Box::new(x)),
+                StatementOrNull::Statement(Box::new(statement)),
+            ),
+        };
+        self::statement(StatementItem::ProceduralTimingControlStatement(Box::new(x)))
+    }
+
+    // -------------------------------------------------------------------------
+
+    /// Print nodes built by the builder as source text
+    ///
+    /// Module items and statements are placed on their own lines indented by
+    /// 2 spaces, and ports, parameters and named connections are placed one
+    /// per line.
+    pub fn print<'a, T: Into<RefNodes<'a>>>(&self, nodes: T) -> String {
+        let tokens = self.tokens(nodes.into());
+
+        let mut ret = String::new();
+        let mut indent = 0;
+        let mut newline = false;
+        for (i, token) in tokens.iter().enumerate() {
+            indent += token.layout.indent_before;
+            if i > 0 {
+                let prev = &tokens[i - 1];
+                let end_else = prev.keyword && prev.text == "end" && token.text == "else";
+                if (newline || token.layout.newline_before) && !end_else {
+                    ret.push('\n');
+                    for _ in 0..indent {
+                        ret.push_str("  ");
+                    }
+                } else if space(prev, token) {
+                    ret.push(' ');
+                }
+            }
+            ret.push_str(token.text);
+            indent += token.layout.indent_after;
+            newline = token.layout.newline_after;
+        }
+        if newline {
+            ret.push('\n');
+        }
+        ret
+    }
+
+    /// Print nodes built by the builder and parse the printed text
+    pub fn to_syntax_tree<'a, T: Into<RefNodes<'a>>>(&self, nodes: T) -> Result<SyntaxTree, Error> {
+        let text = self.print(nodes);
+        let defines: HashMap<String, Option<Define>> = HashMap::new();
+        let includes: [&str; 0] = [];
+        let options = ParseOptions {
+            ignore_include: true,
+            ..ParseOptions::default()
+        };
+        parse_sv_str(&text, "", &defines, &includes, &options).map(|(x, _)| x)
+    }
+
+    fn tokens<'a>(&'a self, nodes: RefNodes<'a>) -> Vec<Token<'a>> {
+        let mut ret: Vec<Token> = Vec::new();
+        // Nodes enclosing the current node, and whether the node is a
+        // statement placed on its own line
+        let mut stack: Vec<(RefNode, bool)> = Vec::new();
+        let mut pending = Layout::default();
+        // Depth in white spaces of nodes cloned from a syntax tree
+        let mut skip = 0;
+        for event in Iter::new(nodes).event() {
+            match event {
+                NodeEvent::Enter(_) if skip > 0 => skip += 1,
+                NodeEvent::Leave(_) if skip > 0 => skip -= 1,
+                NodeEvent::Enter(RefNode::WhiteSpace(_)) => skip = 1,
+                NodeEvent::Enter(RefNode::Locate(x)) => {
+                    let leaf = stack.last().map(|x| x.0.clone());
+                    let owner = stack.iter().rev().nth(1).map(|x| x.0.clone());
+                    let keyword = matches!(leaf, Some(RefNode::Keyword(_)));
+                    let symbol = matches!(leaf, Some(RefNode::Symbol(_)));
+                    let text = self.str(x);
+
+                    let mut layout = std::mem::take(&mut pending);
+                    let vertical = matches!(
+                        owner,
+                        Some(RefNode::ListOfPortDeclarations(_))
+                            | Some(RefNode::ListOfPorts(_))
+                            | Some(RefNode::ParameterPortListDeclaration(_))
+                            | Some(RefNode::HierarchicalInstance(_))
+                            | Some(RefNode::ParameterValueAssignment(_))
+                    );
+                    let separator = matches!(
+                        owner,
+                        Some(RefNode::ListOfPortDeclarations(_))
+                            | Some(RefNode::ListOfPorts(_))
+                            | Some(RefNode::ParameterPortListDeclaration(_))
+                            | Some(RefNode::ListOfPortConnectionsNamed(_))
+                            | Some(RefNode::ListOfParameterAssignmentsNamed(_))
+                    );
+                    if keyword && (text == "begin" || text == "fork") {
+                        layout.newline_after = true;
+                        layout.indent_after += 1;
+                    } else if keyword && (text.starts_with("end") || text.starts_with("join")) {
+                        layout.newline_before = true;
+                        layout.indent_before -= 1;
+                        layout.newline_after = true;
+                    } else if symbol && text == ";" {
+                        layout.newline_after = true;
+                        if let Some(RefNode::ModuleAnsiHeader(_))
+                        | Some(RefNode::ModuleNonansiHeader(_))
+                        | Some(RefNode::ModuleDeclarationWildcard(_)) = owner
+                        {
+                            layout.indent_after += 1;
+                        }
+                    } else if symbol && vertical && text == "(" {
+                        layout.newline_after = true;
+                        layout.indent_after += 1;
+                    } else if symbol && vertical && text == ")" {
+                        layout.newline_before = true;
+                        layout.indent_before -= 1;
+                    } else if symbol && separator && text == "," {
+                        layout.newline_after = true;
+                    }
+
+                    // Empty lists stay on a line.
+                    if let Some(prev) = ret.last_mut() {
+                        if prev.vertical && prev.text == "(" && vertical && text == ")" {
+                            prev.layout.newline_after = false;
+                            prev.layout.indent_after -= 1;
+                            layout.newline_before = false;
+                            layout.indent_before += 1;
+                        }
+                    }
+
+                    ret.push(Token {
+                        text,
+                        leaf,
+                        owner,
+                        keyword,
+                        symbol,
+                        vertical: symbol && vertical,
+                        layout,
+                    });
+                }
+                NodeEvent::Leave(RefNode::Locate(_)) => (),
+                NodeEvent::Enter(x) => {
+                    let own_line = match &x {
+                        RefNode::Statement(y) => {
+                            let parent = stack
+                                .iter()
+                                .rev()
+                                .map(|x| &x.0)
+                                .find(|x| !matches!(x, RefNode::StatementOrNull(_)));
+                            let else_if = matches!(
+                                y.nodes.2,
+                                StatementItem::ConditionalStatement(_)
+                            ) && matches!(ret.last(), Some(x) if x.keyword && x.text == "else");
+                            matches!(
+                                parent,
+                                Some(RefNode::AlwaysConstruct(_))
+                                    | Some(RefNode::ConditionalStatement(_))
+                                    | Some(RefNode::ProceduralTimingControlStatement(_))
+                            ) && !matches!(
+                                y.nodes.2,
+                                StatementItem::SeqBlock(_)
+                                    | StatementItem::ParBlock(_)
+                                    | StatementItem::ProceduralTimingControlStatement(_)
+                            ) && !else_if
+                        }
+                        _ => false,
+                    };
+                    if own_line {
+                        pending.newline_before = true;
+                        pending.indent_before += 1;
+                    }
+                    stack.push((x, own_line));
+                }
+                NodeEvent::Leave(_) => {
+                    if let Some((_, true)) = stack.pop() {
+                        if let Some(x) = ret.last_mut() {
+                            x.layout.indent_after -= 1;
+                        }
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
+#[derive(Default)]
+struct Layout {
+    newline_before: bool,
+    indent_before: isize,
+    newline_after: bool,
+    indent_after: isize,
+}
+
+struct Token<'a> {
+    text: &'a str,
+    /// The node holding the token like `Symbol` and `SimpleIdentifier`
+    leaf: Option<RefNode<'a>>,
+    /// The parent of `leaf`
+    owner: Option<RefNode<'a>>,
+    keyword: bool,
+    symbol: bool,
+    /// Parenthesis of a list placed one item per line
+    vertical: bool,
+    layout: Layout,
+}
+
+// Whether a space is placed between tokens on the same line
+fn space(prev: &Token, next: &Token) -> bool {
+    if matches!(prev.leaf, Some(RefNode::EscapedIdentifier(_))) {
+        return true;
+    }
+    if prev.symbol && matches!(prev.text, "(" | "[" | "." | "#" | "@") {
+        return false;
+    }
+    if next.symbol && matches!(next.text, ")" | "]" | "," | ";") {
+        return false;
+    }
+    if matches!(prev.owner, Some(RefNode::UnaryOperator(_))) {
+        return false;
+    }
+    if next.symbol && next.text == "(" {
+        return !matches!(
+            next.owner,
+            Some(RefNode::NamedPortConnectionIdentifier(_))
+                | Some(RefNode::NamedParameterAssignment(_))
+                | Some(RefNode::PortNamed(_))
+        );
+    }
+    if next.symbol && next.text == "[" {
+        return !matches!(next.owner, Some(RefNode::BitSelect(_)));
+    }
+    if matches!(next.owner, Some(RefNode::TimeUnit(_))) {
+        return false;
+    }
+    let range =
+        |x: &Token| x.symbol && x.text == ":" && matches!(x.owner, Some(RefNode::ConstantRange(_)));
+    if range(prev) || range(next) {
+        return false;
+    }
+    !is_number_base(&prev.leaf) && !is_number_base(&next.leaf)
+}
+
+fn is_number_base(x: &Option<RefNode>) -> bool {
+    matches!(
+        x,
+        Some(RefNode::BinaryBase(_))
+            | Some(RefNode::OctalBase(_))
+            | Some(RefNode::DecimalBase(_))
+            | Some(RefNode::HexBase(_))
+    )
+}
+
+fn statement(x: StatementItem) -> Statement {
+    Statement {
+        nodes: (None, vec![], x),
+    }
+}
+
+fn empty_select() -> Select {
+    Select {
+        nodes: (None, BitSelect { nodes: (vec![],) }, None),
+    }
+}
+
+fn empty_constant_select() -> ConstantSelect {
+    ConstantSelect {
+        nodes: (None, ConstantBitSelect { nodes: (vec![],) }, None),
+    }
+}
+
+// Whether the operands of a binary operation have to be parenthesized,
+// from precedences of the operators of the operands
+fn operand_parens(operator: BinaryOp, lhs: Option<usize>, rhs: Option<usize>) -> (bool, bool) {
+    let p = operator.precedence();
+    // Implication operators are right associative.
+    let lhs = matches!(lhs, Some(x) if x < p || x == p && p == 0);
+    let rhs = matches!(rhs, Some(x) if x <= p);
+    (lhs, rhs)
+}
+
+// Precedence of the operator of a built or cloned binary operation
+fn precedence(operator: &str) -> Option<usize> {
+    BinaryOp::from_str(operator).map(BinaryOp::precedence)
+}
+
+impl NumberBase {
+    fn as_str(self) -> &'static str {
+        match self {
+            NumberBase::Binary => "b",
+            NumberBase::Octal => "o",
+            NumberBase::Decimal => "d",
+            NumberBase::Hex => "h",
+        }
+    }
+}
+
+impl VectorKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            VectorKind::Bit => "bit",
+            VectorKind::Logic => "logic",
+            VectorKind::Reg => "reg",
+        }
+    }
+}
+
+impl Direction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Direction::Input => "input",
+            Direction::Output => "output",
+            Direction::Inout => "inout",
+            Direction::Ref => "ref",
+        }
+    }
+}
+
+impl AlwaysKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            AlwaysKind::Always => "always",
+            AlwaysKind::AlwaysComb => "always_comb",
+            AlwaysKind::AlwaysLatch => "always_latch",
+            AlwaysKind::AlwaysFf => "always_ff",
+        }
+    }
+}
+
+impl Edge {
+    fn as_str(self) -> &'static str {
+        match self {
+            Edge::Posedge => "posedge",
+            Edge::Negedge => "negedge",
+            Edge::Edge => "edge",
+        }
+    }
+}
+
+impl UnaryOp {
+    fn as_str(self) -> &'static str {
+        match self {
+            UnaryOp::Plus => "+",
+            UnaryOp::Minus => "-",
+            UnaryOp::LogicalNot => "!",
+            UnaryOp::Not => "~",
+            UnaryOp::And => "&",
+            UnaryOp::Nand => "~&",
+            UnaryOp::Or => "|",
+            UnaryOp::Nor => "~|",
+            UnaryOp::Xor => "^",
+            UnaryOp::Xnor => "~^",
+        }
+    }
+}
+
+impl BinaryOp {
+    fn as_str(self) -> &'static str {
+        match self {
+            BinaryOp::Power => "**",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Shl => "<<",
+            BinaryOp::Shr => ">>",
+            BinaryOp::ArithShl => "<<<",
+            BinaryOp::ArithShr => ">>>",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::CaseEq => "===",
+            BinaryOp::CaseNe => "!==",
+            BinaryOp::WildcardEq => "==?",
+            BinaryOp::WildcardNe => "!=?",
+            BinaryOp::And => "&",
+            BinaryOp::Xor => "^",
+            BinaryOp::Xnor => "~^",
+            BinaryOp::Or => "|",
+            BinaryOp::LogicalAnd => "&&",
+            BinaryOp::LogicalOr => "||",
+            BinaryOp::Implication => "->",
+            BinaryOp::Equivalence => "<->",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        let ret = match s {
+            "**" => BinaryOp::Power,
+            "*" => BinaryOp::Mul,
+            "/" => BinaryOp::Div,
+            "%" => BinaryOp::Rem,
+            "+" => BinaryOp::Add,
+            "-" => BinaryOp::Sub,
+            "<<" => BinaryOp::Shl,
+            ">>" => BinaryOp::Shr,
+            "<<<" => BinaryOp::ArithShl,
+            ">>>" => BinaryOp::ArithShr,
+            "<" => BinaryOp::Lt,
+            "<=" => BinaryOp::Le,
+            ">" => BinaryOp::Gt,
+            ">=" => BinaryOp::Ge,
+            "==" => BinaryOp::Eq,
+            "!=" => BinaryOp::Ne,
+            "===" => BinaryOp::CaseEq,
+            "!==" => BinaryOp::CaseNe,
+            "==?" => BinaryOp::WildcardEq,
+            "!=?" => BinaryOp::WildcardNe,
+            "&" => BinaryOp::And,
+            "^" => BinaryOp::Xor,
+            "~^" | "^~" => BinaryOp::Xnor,
+            "|" => BinaryOp::Or,
+            "&&" => BinaryOp::LogicalAnd,
+            "||" => BinaryOp::LogicalOr,
+            "->" => BinaryOp::Implication,
+            "<->" => BinaryOp::Equivalence,
+            _ => return None,
+        };
+        Some(ret)
+    }
+
+    // IEEE1800-2017 Table 11-2, where larger values bind tighter
+    fn precedence(self) -> usize {
+        match self {
+            BinaryOp::Power => 11,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 10,
+            BinaryOp::Add | BinaryOp::Sub => 9,
+            BinaryOp::Shl | BinaryOp::Shr | BinaryOp::ArithShl | BinaryOp::ArithShr => 8,
+            BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => 7,
+            BinaryOp::Eq
+            | BinaryOp::Ne
+            | BinaryOp::CaseEq
+            | BinaryOp::CaseNe
+            | BinaryOp::WildcardEq
+            | BinaryOp::WildcardNe => 6,
+            BinaryOp::And => 5,
+            BinaryOp::Xor | BinaryOp::Xnor => 4,
+            BinaryOp::Or => 3,
+            BinaryOp::LogicalAnd => 2,
+            BinaryOp::LogicalOr => 1,
+            BinaryOp::Implication | BinaryOp::Equivalence => 0,
+        }
+    }
+}
+
+impl IntoModuleItem for ModuleCommonItem {
+    fn into_module_item(self) -> NonPortModuleItem {
+        NonPortModuleItem::ModuleOrGenerateItem(Box::new(ModuleOrGenerateItem::ModuleItem(
+            Box::new(ModuleOrGenerateItemModuleItem {
+                nodes: (vec![], self),
+            }),
+        )))
+    }
+}
+
+impl IntoModuleItem for ContinuousAssign {
+    fn into_module_item(self) -> NonPortModuleItem {
+        ModuleCommonItem::ContinuousAssign(Box::new(self)).into_module_item()
+    }
+}
+
+impl IntoModuleItem for AlwaysConstruct {
+    fn into_module_item(self) -> NonPortModuleItem {
+        ModuleCommonItem::AlwaysConstruct(Box::new(self)).into_module_item()
+    }
+}
+
+impl IntoModuleItem for DataDeclaration {
+    fn into_module_item(self) -> NonPortModuleItem {
+        let x = PackageOrGenerateItemDeclaration::DataDeclaration(Box::new(self));
+        let x = ModuleOrGenerateItemDeclaration::PackageOrGenerateItemDeclaration(Box::new(x));
+        ModuleCommonItem::ModuleOrGenerateItemDeclaration(Box::new(x)).into_module_item()
+    }
+}
+
+impl IntoModuleItem for ModuleInstantiation {
+    fn into_module_item(self) -> NonPortModuleItem {
+        NonPortModuleItem::ModuleOrGenerateItem(Box::new(ModuleOrGenerateItem::Module(Box::new(
+            ModuleOrGenerateItemModule {
+                nodes: (vec![], self),
+            },
+        ))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_builder() {
+        let mut b = Builder::new();
+
+        let w = b.constant_identifier("W");
+        let one = b.number(1);
+        let one = b.constant_number(one);
+        let msb = b.constant_binary(w, BinaryOp::Sub, one);
+        let zero = b.number(0);
+        let lsb = b.constant_number(zero);
+        let width = vec![(msb, lsb)];
+
+        let eight = b.number(8);
+        let eight = b.constant_number(eight);
+        let parameters = vec![b.parameter_port_declaration("W", eight)];
+        let logic = b.vector_type(VectorKind::Logic, vec![]);
+        let clk = b.ansi_port_declaration(Direction::Input, Some(logic), "clk");
+        let rst_n = b.ansi_port_declaration(Direction::Input, None, "rst_n");
+        let q_type = b.vector_type(VectorKind::Logic, width);
+        let q = b.ansi_port_declaration(Direction::Output, Some(q_type), "q");
+        let ports = vec![clk, rst_n, q];
+
+        let count = b.expression_identifier("count");
+        let one = b.number(1);
+        let one = b.expression_number(one);
+        let a = b.expression_identifier("a");
+        let c = b.expression_identifier("c");
+        let sum = b.expression_binary(one, BinaryOp::Add, a);
+        let rhs = b.expression_binary(count, BinaryOp::Sub, sum);
+        let rhs = b.expression_binary(rhs, BinaryOp::And, c);
+        let assign = b.continuous_assign("q", rhs);
+
+        let rst_n = b.expression_identifier("rst_n");
+        let cond = b.expression_unary(UnaryOp::LogicalNot, rst_n);
+        let zero = b.sized_number(8, NumberBase::Hex, 0);
+        let zero = b.expression_number(zero);
+        let reset = b.nonblocking_assignment("count", zero);
+        let count = b.expression_identifier("count");
+        let index = b.number(0);
+        let index = b.expression_number(index);
+        let bit = b.expression_select("q", index);
+        let next = b.expression_binary(count, BinaryOp::Add, bit);
+        let next = b.nonblocking_assignment("count", next);
+        let stmt = b.conditional_statement(cond, reset, Some(next));
+        let stmt = b.seq_block(vec![stmt]);
+        let clk = b.expression_identifier("clk");
+        let rst_n = b.expression_identifier("rst_n");
+        let stmt = b.event_control_statement(
+            vec![(Some(Edge::Posedge), clk), (Some(Edge::Negedge), rst_n)],
+            stmt,
+        );
+        let always = b.always_construct(AlwaysKind::AlwaysFf, stmt);
+
+        let w = b.expression_identifier("W");
+        let clk = b.expression_identifier("clk");
+        let inst = b.module_instantiation(
+            "sub",
+            vec![("W", w)],
+            "u_sub",
+            vec![("clk", Some(clk)), ("y", None)],
+        );
+
+        let items = vec![
+            assign.into_module_item(),
+            always.into_module_item(),
+            inst.into_module_item(),
+        ];
+        let module = b.module_declaration_ansi("counter", parameters, ports, items);
+
+        let expected = r##"module counter #(
+  parameter W = 8
+) (
+  input logic clk,
+  input rst_n,
+  output logic [W - 1:0] q
+);
+  assign q = count - (1 + a) & c;
+  always_ff @(posedge clk or negedge rst_n) begin
+    if (!rst_n)
+      count <= 8'h0;
+    else
+      count <= count + q[0];
+  end
+  sub #(
+    .W(W)
+  ) u_sub (
+    .clk(clk),
+    .y()
+  );
+endmodule
+"##;
+        assert_eq!(b.print(&module), expected);
+        let syntax_tree = b.to_syntax_tree(&module).unwrap();
+        assert!(unwrap_node!(&syntax_tree, ModuleInstantiation).is_some());
+
+        let mut b = Builder::new();
+        let x = b.identifier("module");
+        assert_eq!(b.print(&x), "\\module");
+        let c = b.expression_identifier("c");
+        let x = b.continuous_assign("a.b", c);
+        assert_eq!(b.print(&x), "assign \\a.b = c;\n");
+    }
+}
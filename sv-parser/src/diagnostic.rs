@@ -0,0 +1,519 @@
+use crate::{Error, Expansion, Warning};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+#[cfg(feature = "serde")]
+use serde_json::{json, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "lowercase"))]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// Location in an original source file
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Location {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_path"))]
+    pub path: PathBuf,
+    /// Byte offset, `None` if the whole file is pointed
+    pub offset: Option<usize>,
+    /// 1-origin line number, `None` until resolved from the source text
+    pub line: Option<usize>,
+    /// 1-origin column number in characters
+    pub column: Option<usize>,
+    /// Byte offset just after the token at `offset`
+    pub end_offset: Option<usize>,
+    pub end_line: Option<usize>,
+    pub end_column: Option<usize>,
+}
+
+impl Location {
+    /// Create a location without line and column numbers, see `resolve`.
+    pub fn new<T: AsRef<Path>>(path: T, offset: Option<usize>) -> Self {
+        Location {
+            path: PathBuf::from(path.as_ref()),
+            offset,
+            line: None,
+            column: None,
+            end_offset: None,
+            end_line: None,
+            end_column: None,
+        }
+    }
+
+    /// Set line and column numbers of the offset and the end of the token
+    /// there from `source`, the text of the file
+    pub fn resolve(&mut self, source: &str) {
+        let offset = match self.offset {
+            Some(x) => x,
+            None => return,
+        };
+        let end = match source.get(offset..) {
+            Some(x) => offset + token_len(x),
+            None => return,
+        };
+        if let (Some(begin), Some(end)) = (line_column(source, offset), line_column(source, end)) {
+            self.line = Some(begin.0);
+            self.column = Some(begin.1);
+            self.end_offset = Some(end.2);
+            self.end_line = Some(end.0);
+            self.end_column = Some(end.1);
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{}:{}:{}", self.path.display(), line, column),
+            _ => write!(f, "{}", self.path.display()),
+        }
+    }
+}
+
+// Line, column and the offset itself
+fn line_column(source: &str, offset: usize) -> Option<(usize, usize, usize)> {
+    let head = source.get(..offset)?;
+    let line_head = head.rfind('\n').map_or(0, |x| x + 1);
+    let line = head.matches('\n').count() + 1;
+    let column = head[line_head..].chars().count() + 1;
+    Some((line, column, offset))
+}
+
+// Length of the identifier, the keyword or the directive at the head of `s`,
+// or of the first character unless it is a white space
+fn token_len(s: &str) -> usize {
+    let word = |x: char| x.is_ascii_alphanumeric() || x == '_' || x == '$';
+    let mut chars = s.char_indices();
+    match chars.next() {
+        Some((_, x)) if x == '`' || word(x) => {
+            chars.find(|(_, x)| !word(*x)).map_or(s.len(), |(i, _)| i)
+        }
+        Some((_, x)) if x.is_whitespace() => 0,
+        Some((_, x)) => x.len_utf8(),
+        None => 0,
+    }
+}
+
+#[cfg(feature = "serde")]
+fn serialize_path<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&path.to_string_lossy())
+}
+
+/// Location related to a diagnostic, like an `include directive of the file
+/// where an error is found
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct RelatedLocation {
+    pub message: String,
+    pub location: Location,
+}
+
+impl From<&Expansion> for RelatedLocation {
+    fn from(x: &Expansion) -> Self {
+        match x {
+            Expansion::Include(_, (path, offset)) => RelatedLocation {
+                message: String::from("included from here"),
+                location: Location::new(path, Some(*offset)),
+            },
+            Expansion::Macro(identifier, (path, offset)) => RelatedLocation {
+                message: format!("in expansion of macro `{}`", identifier),
+                location: Location::new(path, Some(*offset)),
+            },
+        }
+    }
+}
+
+/// Structured record of an error or a warning
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable identifier of the kind of the diagnostic, e.g. `parse`
+    pub code: &'static str,
+    pub message: String,
+    /// Primary location, `None` if the diagnostic isn't bound to a file
+    pub location: Option<Location>,
+    /// Related locations from the innermost, e.g. the include stack
+    pub related: Vec<RelatedLocation>,
+}
+
+impl Diagnostic {
+    fn new(
+        severity: Severity,
+        code: &'static str,
+        message: String,
+        origin: Option<&(PathBuf, usize)>,
+    ) -> Self {
+        Diagnostic {
+            severity,
+            code,
+            message,
+            location: origin.map(|(path, offset)| Location::new(path, Some(*offset))),
+            related: Vec::new(),
+        }
+    }
+
+    /// Set line and column numbers of the locations from the texts of the
+    /// source files, e.g. `|x| tree.get_source(x)`
+    pub fn resolve<'a, F: Fn(&Path) -> Option<&'a str>>(&mut self, source: F) {
+        let related = self.related.iter_mut().map(|x| &mut x.location);
+        for x in self.location.iter_mut().chain(related) {
+            if let Some(s) = source(&x.path) {
+                x.resolve(s);
+            }
+        }
+    }
+
+    /// Serialize to a JSON object in a line
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+impl From<&Error> for Diagnostic {
+    fn from(x: &Error) -> Self {
+        let error = |code, message| Diagnostic::new(Severity::Error, code, message, None);
+        match x {
+            Error::Io(x) => error("io", format!("IO error: {}", x)),
+            Error::File { source, path } => {
+                let mut ret = error("file", format!("File error: {}", source));
+                ret.location = Some(Location::new(path, None));
+                ret
+            }
+            Error::ReadUtf8(path) => {
+                let mut ret = error("read-utf8", String::from("File could not be read as UTF8"));
+                ret.location = Some(Location::new(path, None));
+                ret
+            }
+            Error::Include { source } => Diagnostic::from(source.as_ref()),
+            Error::Parse(origin) => Diagnostic::new(
+                Severity::Error,
+                "parse",
+                String::from("Parse error"),
+                origin.as_ref(),
+            ),
+            Error::Preprocess(origin) => Diagnostic::new(
+                Severity::Error,
+                "preprocess",
+                String::from("Preprocess error"),
+                origin.as_ref(),
+            ),
+            Error::DefineArgNotFound(_) => error("define-arg-not-found", x.to_string()),
+            Error::DefineNotFound(_) => error("define-not-found", x.to_string()),
+            Error::DefineNoArgs(id) => error(
+                "define-no-args",
+                format!("Define must have argument: {}", id),
+            ),
+            Error::ExceedRecursiveLimit => error("exceed-recursive-limit", x.to_string()),
+            Error::IncludeLine => error("include-line", x.to_string()),
+        }
+    }
+}
+
+impl From<&Warning> for Diagnostic {
+    fn from(x: &Warning) -> Self {
+        let (code, origin) = match x {
+            Warning::DefineRedefined(_, x) => ("define-redefined", Some(x)),
+            Warning::DefineNotFound(_, x) => ("define-not-found", Some(x)),
+            Warning::DefineSimilar(_, _, x) => ("define-similar", Some(x)),
+            Warning::DefineUnused(_) => ("define-unused", None),
+            Warning::UndefineNotFound(_, x) => ("undefine-not-found", Some(x)),
+            Warning::IncludeRepeated(_, x) => ("include-repeated", Some(x)),
+            Warning::TimescaleInherited(_, x) => ("timescale-inherited", Some(x)),
+            Warning::TimescaleMissing(_, x) => ("timescale-missing", Some(x)),
+            Warning::TranslateOffUnterminated(x) => ("translate-off-unterminated", Some(x)),
+        };
+        Diagnostic::new(Severity::Warning, code, x.to_string(), origin)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref location) = self.location {
+            write!(f, "{}: ", location)?;
+        }
+        write!(f, "{}: {}", self.severity.as_str(), self.message)?;
+        for x in &self.related {
+            write!(f, "\n{}: note: {}", x.location, x.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serialize diagnostics to JSON Lines, a JSON object per line
+#[cfg(feature = "serde")]
+pub fn to_json_lines(diagnostics: &[Diagnostic]) -> String {
+    let mut ret = String::new();
+    for x in diagnostics {
+        ret.push_str(&x.to_json());
+        ret.push('\n');
+    }
+    ret
+}
+
+/// Serialize diagnostics to a SARIF 2.1.0 log
+#[cfg(feature = "serde")]
+pub fn to_sarif(diagnostics: &[Diagnostic]) -> String {
+    let mut rules: Vec<&str> = Vec::new();
+    let mut results = Vec::new();
+    for x in diagnostics {
+        let rule_index = match rules.iter().position(|r| *r == x.code) {
+            Some(i) => i,
+            None => {
+                rules.push(x.code);
+                rules.len() - 1
+            }
+        };
+        let mut result = json!({
+            "ruleId": x.code,
+            "ruleIndex": rule_index,
+            "level": x.severity.as_str(),
+            "message": {"text": x.message},
+        });
+        if let Some(ref location) = x.location {
+            result["locations"] = json!([{"physicalLocation": sarif_location(location)}]);
+        }
+        if !x.related.is_empty() {
+            let related: Vec<_> = x
+                .related
+                .iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    json!({
+                        "id": i,
+                        "message": {"text": x.message},
+                        "physicalLocation": sarif_location(&x.location),
+                    })
+                })
+                .collect();
+            result["relatedLocations"] = Value::Array(related);
+        }
+        results.push(result);
+    }
+    let rules: Vec<_> = rules.iter().map(|x| json!({ "id": x })).collect();
+    let ret = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {"driver": {
+                "name": "sv-parser",
+                "version": env!("CARGO_PKG_VERSION"),
+                "informationUri": "https://github.com/dalance/sv-parser",
+                "rules": rules,
+            }},
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    });
+    format!("{}\n", ret)
+}
+
+#[cfg(feature = "serde")]
+fn sarif_location(x: &Location) -> Value {
+    let mut ret = json!({"artifactLocation": {"uri": uri(&x.path)}});
+    let mut region = serde_json::Map::new();
+    if let (Some(line), Some(column)) = (x.line, x.column) {
+        region.insert(String::from("startLine"), json!(line));
+        region.insert(String::from("startColumn"), json!(column));
+    }
+    if let (Some(line), Some(column)) = (x.end_line, x.end_column) {
+        region.insert(String::from("endLine"), json!(line));
+        region.insert(String::from("endColumn"), json!(column));
+    }
+    if let Some(offset) = x.offset {
+        region.insert(String::from("byteOffset"), json!(offset));
+        if let Some(end) = x.end_offset {
+            region.insert(String::from("byteLength"), json!(end - offset));
+        }
+    }
+    if !region.is_empty() {
+        ret["region"] = Value::Object(region);
+    }
+    ret
+}
+
+// Relative paths are kept relative to be resolved against the checkout
+// directory by SARIF consumers.
+#[cfg(feature = "serde")]
+fn uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut ret = String::new();
+    if path.starts_with('/') {
+        ret.push_str("file://");
+    } else if path.as_bytes().get(1) == Some(&b':') {
+        // Windows drive letter
+        ret.push_str("file:///");
+    }
+    for x in path.bytes() {
+        if x.is_ascii_alphanumeric() || b"-._~/:".contains(&x) {
+            ret.push(x as char);
+        } else {
+            ret.push_str(&format!("%{:02X}", x));
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::testfile_path;
+    use crate::*;
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+
+    fn parse_diagnostic(path: &Path) -> Diagnostic {
+        let dir = testfile_path("diagnostic");
+        let (text, defines) = preprocess(
+            path,
+            &HashMap::new(),
+            &[&dir],
+            &PreprocessOptions::default(),
+        )
+        .unwrap();
+        let ret = parse_sv_pp_diagnostic(text, defines, &ParseOptions::default());
+        *ret.err().unwrap()
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let dir = testfile_path("diagnostic");
+
+        let ret = parse_sv(
+            dir.join("top.sv"),
+            &HashMap::new(),
+            &[&dir],
+            &ParseOptions::default(),
+        );
+        let error = ret.unwrap_err();
+        assert!(matches!(error, Error::Parse(Some(_))));
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(diagnostic.location.as_ref().unwrap().line, None);
+
+        let diagnostic = parse_diagnostic(&dir.join("top.sv"));
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.code, "parse");
+        let location = diagnostic.location.as_ref().unwrap();
+        assert_eq!(location.path, dir.join("inc.svh"));
+        assert_eq!(location.offset, Some(17));
+        assert_eq!((location.line, location.column), (Some(2), Some(10)));
+        assert_eq!(location.end_offset, Some(18));
+        assert_eq!(
+            (location.end_line, location.end_column),
+            (Some(2), Some(11))
+        );
+        assert_eq!(diagnostic.related.len(), 1);
+        assert_eq!(diagnostic.related[0].message, "included from here");
+        assert_eq!(diagnostic.related[0].location.path, dir.join("top.sv"));
+        assert_eq!(diagnostic.related[0].location.line, Some(2));
+        assert_eq!(diagnostic.related[0].location.end_column, Some(9));
+        assert_eq!(
+            diagnostic.to_string(),
+            format!(
+                "{}:2:10: error: Parse error\n{}:2:1: note: included from here",
+                dir.join("inc.svh").display(),
+                dir.join("top.sv").display()
+            )
+        );
+
+        let diagnostic = parse_diagnostic(&dir.join("macro.sv"));
+        assert_eq!(diagnostic.location.as_ref().unwrap().line, Some(1));
+        let related = &diagnostic.related[0];
+        assert_eq!(related.message, "in expansion of macro `BAD`");
+        assert_eq!(
+            (related.location.line, related.location.column),
+            (Some(3), Some(3))
+        );
+        assert_eq!(related.location.end_column, Some(7));
+
+        // Source text given in memory
+        let (text, defines) = preprocess_str(
+            "module A;\n  wire = 1;\nendmodule\n",
+            "mem.sv",
+            &HashMap::new(),
+            &[] as &[&str],
+            &PreprocessOptions::default(),
+            0,
+            0,
+        )
+        .unwrap();
+        let ret = parse_sv_pp_diagnostic(text, defines, &ParseOptions::default());
+        let location = ret.err().unwrap().location.unwrap();
+        assert_eq!(location.path, PathBuf::from("mem.sv"));
+        assert_eq!((location.line, location.column), (Some(2), Some(8)));
+
+        let src = "module A;\nendmodule\n`define X 1\n`define X 2\n";
+        let (tree, _) = parse_sv_str(
+            src,
+            "mem.sv",
+            &HashMap::new(),
+            &[] as &[&str],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let warning = tree
+            .get_warnings()
+            .iter()
+            .find(|x| matches!(x, Warning::DefineRedefined(_, _)))
+            .unwrap();
+        let mut diagnostic = Diagnostic::from(warning);
+        diagnostic.resolve(|x| tree.get_source(x));
+        let location = diagnostic.location.unwrap();
+        assert_eq!((location.line, location.column), (Some(4), Some(1)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize() {
+        let dir = testfile_path("diagnostic");
+        let diagnostic = parse_diagnostic(&dir.join("top.sv"));
+
+        let json = to_json_lines(std::slice::from_ref(&diagnostic));
+        assert_eq!(json.lines().count(), 1);
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["severity"], "error");
+        assert_eq!(json["code"], "parse");
+        assert_eq!(json["message"], "Parse error");
+        assert_eq!(json["location"]["offset"], 17);
+        assert_eq!(json["location"]["line"], 2);
+        assert_eq!(json["location"]["column"], 10);
+        assert_eq!(json["location"]["end_column"], 11);
+        assert_eq!(json["related"][0]["message"], "included from here");
+
+        let warning = Diagnostic::from(&Warning::DefineUnused(String::from("A\"B")));
+        assert_eq!(warning.to_json().matches('\n').count(), 0);
+        let sarif = to_sarif(&[diagnostic, warning]);
+        let sarif: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "parse");
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "define-unused");
+        let region = &run["results"][0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 2);
+        assert_eq!(region["startColumn"], 10);
+        assert_eq!(region["endLine"], 2);
+        assert_eq!(region["endColumn"], 11);
+        assert_eq!(region["byteOffset"], 17);
+        assert_eq!(region["byteLength"], 1);
+        assert_eq!(run["results"][0]["relatedLocations"][0]["id"], 0);
+        assert_eq!(run["results"][1]["ruleIndex"], 1);
+        assert_eq!(
+            run["results"][1]["message"]["text"],
+            "Define never referenced: A\"B"
+        );
+    }
+}
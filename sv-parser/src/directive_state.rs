@@ -0,0 +1,332 @@
+use crate::{Locate, NodeEvent, RefNode, SyntaxTree, TimeunitsDeclaration, Warning};
+use std::path::PathBuf;
+
+/// `timescale directive value
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Timescale {
+    pub unit: String,
+    pub precision: String,
+    /// Location of the `timescale directive
+    pub origin: (PathBuf, usize),
+}
+
+/// Compiler directives in effect at a point of source text
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirectiveState {
+    pub timescale: Option<Timescale>,
+    /// Value of `default_nettype, e.g. `wire` or `none`
+    pub default_nettype: String,
+    /// Value of `unconnected_drive, `pull0` or `pull1`
+    pub unconnected_drive: Option<String>,
+    pub celldefine: bool,
+}
+
+impl Default for DirectiveState {
+    fn default() -> Self {
+        DirectiveState {
+            timescale: None,
+            default_nettype: String::from("wire"),
+            unconnected_drive: None,
+            celldefine: false,
+        }
+    }
+}
+
+/// Where the time unit and precision of a design element come from
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TimescaleSource {
+    /// `timeunit`/`timeprecision` declarations in the design element
+    Declaration,
+    /// `timescale directive in the same syntax tree
+    Directive,
+    /// `timescale directive in a previous syntax tree
+    Inherited,
+    /// No timescale is specified, so the tool default is used
+    Default,
+}
+
+/// Compiler directive state in effect for a module, interface, program or package
+#[derive(Clone, Debug)]
+pub struct DesignElementState<'a> {
+    pub node: RefNode<'a>,
+    pub identifier: String,
+    pub time_unit: Option<String>,
+    pub time_precision: Option<String>,
+    pub timescale_source: TimescaleSource,
+    pub default_nettype: String,
+    pub unconnected_drive: Option<String>,
+    pub celldefine: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct DirectiveStates<'a> {
+    pub elements: Vec<DesignElementState<'a>>,
+    /// State at the end of the syntax tree, which is inherited by the next file
+    pub last: DirectiveState,
+    pub warnings: Vec<Warning>,
+}
+
+impl SyntaxTree {
+    /// Get the effective compiler directive state of each design element.
+    ///
+    /// `initial` is the state left by the previously compiled file, or
+    /// `DirectiveState::default()` for the first file.
+    pub fn get_directive_states(&self, initial: &DirectiveState) -> DirectiveStates<'_> {
+        let mut state = initial.clone();
+        let mut local_timescale = false;
+        let mut elements: Vec<DesignElementState> = Vec::new();
+        let mut open: Vec<usize> = Vec::new();
+
+        for node in self.into_iter().event() {
+            match node {
+                NodeEvent::Enter(RefNode::TimescaleCompilerDirective(x)) => {
+                    let (
+                        ref symbol,
+                        _,
+                        ref unit_value,
+                        ref unit,
+                        _,
+                        ref precision_value,
+                        ref precision,
+                    ) = x.nodes;
+                    let unit = self.get_str_trim(unit_value).unwrap_or("").to_string()
+                        + self.get_str_trim(unit).unwrap_or("");
+                    let precision = self.get_str_trim(precision_value).unwrap_or("").to_string()
+                        + self.get_str_trim(precision).unwrap_or("");
+                    state.timescale = Some(Timescale {
+                        unit,
+                        precision,
+                        origin: origin(self, &symbol.nodes.0),
+                    });
+                    local_timescale = true;
+                }
+                NodeEvent::Enter(RefNode::DefaultNettypeCompilerDirective(x)) => {
+                    let nettype = self.get_str_trim(&x.nodes.2).unwrap_or("");
+                    state.default_nettype = String::from(nettype);
+                }
+                NodeEvent::Enter(RefNode::UnconnectedDriveCompilerDirective(x)) => {
+                    let pull = self.get_str_trim(&x.nodes.2).unwrap_or("");
+                    state.unconnected_drive = Some(String::from(pull));
+                }
+                NodeEvent::Enter(RefNode::NounconnectedDriveCompilerDirective(_)) => {
+                    state.unconnected_drive = None;
+                }
+                NodeEvent::Enter(RefNode::CelldefineDriveCompilerDirective(_)) => {
+                    state.celldefine = true;
+                }
+                NodeEvent::Enter(RefNode::EndcelldefineDriveCompilerDirective(_)) => {
+                    state.celldefine = false;
+                }
+                NodeEvent::Enter(RefNode::ResetallCompilerDirective(_)) => {
+                    state = DirectiveState::default();
+                    local_timescale = true;
+                }
+                NodeEvent::Enter(RefNode::TimeunitsDeclaration(x)) => {
+                    if let Some(i) = open.last() {
+                        let (unit, precision) = match x {
+                            TimeunitsDeclaration::Timeunit(x) => {
+                                (Some(&x.nodes.1), x.nodes.2.as_ref().map(|(_, y)| y))
+                            }
+                            TimeunitsDeclaration::Timeprecision(x) => (None, Some(&x.nodes.1)),
+                            TimeunitsDeclaration::TimeunitTimeprecision(x) => {
+                                (Some(&x.nodes.1), Some(&x.nodes.4))
+                            }
+                            TimeunitsDeclaration::TimeprecisionTimeunit(x) => {
+                                (Some(&x.nodes.4), Some(&x.nodes.1))
+                            }
+                        };
+                        let element = &mut elements[*i];
+                        element.timescale_source = TimescaleSource::Declaration;
+                        if let Some(x) = unit {
+                            element.time_unit = self.get_str_trim(x).map(String::from);
+                        }
+                        if let Some(x) = precision {
+                            element.time_precision = self.get_str_trim(x).map(String::from);
+                        }
+                    }
+                }
+                NodeEvent::Enter(x) if is_design_element(&x) => {
+                    let identifier = crate::unwrap_node!(
+                        x.clone(),
+                        ModuleIdentifier,
+                        InterfaceIdentifier,
+                        ProgramIdentifier,
+                        PackageIdentifier
+                    )
+                    .and_then(|x| crate::unwrap_locate!(x))
+                    .and_then(|x| self.get_str(x))
+                    .unwrap_or("");
+                    let (time_unit, time_precision, timescale_source) = match open.last() {
+                        // Nested design elements inherit the time unit of the parent
+                        Some(i) => {
+                            let parent = &elements[*i];
+                            (
+                                parent.time_unit.clone(),
+                                parent.time_precision.clone(),
+                                parent.timescale_source,
+                            )
+                        }
+                        None => match state.timescale {
+                            Some(ref t) if local_timescale => (
+                                Some(t.unit.clone()),
+                                Some(t.precision.clone()),
+                                TimescaleSource::Directive,
+                            ),
+                            Some(ref t) => (
+                                Some(t.unit.clone()),
+                                Some(t.precision.clone()),
+                                TimescaleSource::Inherited,
+                            ),
+                            None => (None, None, TimescaleSource::Default),
+                        },
+                    };
+                    let element = DesignElementState {
+                        node: x.clone(),
+                        identifier: String::from(identifier),
+                        time_unit,
+                        time_precision,
+                        timescale_source,
+                        default_nettype: state.default_nettype.clone(),
+                        unconnected_drive: state.unconnected_drive.clone(),
+                        celldefine: state.celldefine,
+                    };
+                    open.push(elements.len());
+                    elements.push(element);
+                }
+                NodeEvent::Leave(x) if is_design_element(&x) => {
+                    open.pop();
+                }
+                _ => (),
+            }
+        }
+
+        // IEEE1800-2017 Clause 3.14.2.3
+        // If a timescale is specified for some design elements but not for
+        // others, the result depends on the order of compilation.
+        let mut warnings = Vec::new();
+        let specified = elements
+            .iter()
+            .any(|x| x.timescale_source != TimescaleSource::Default);
+        for x in &elements {
+            let origin = match crate::unwrap_locate!(x.node.clone()) {
+                Some(locate) => origin(self, locate),
+                None => (PathBuf::new(), 0),
+            };
+            match x.timescale_source {
+                TimescaleSource::Inherited => {
+                    warnings.push(Warning::TimescaleInherited(x.identifier.clone(), origin));
+                }
+                TimescaleSource::Default if specified => {
+                    warnings.push(Warning::TimescaleMissing(x.identifier.clone(), origin));
+                }
+                _ => (),
+            }
+        }
+
+        DirectiveStates {
+            elements,
+            last: state,
+            warnings,
+        }
+    }
+}
+
+fn is_design_element(x: &RefNode) -> bool {
+    matches!(
+        x,
+        RefNode::ModuleDeclaration(_)
+            | RefNode::InterfaceDeclaration(_)
+            | RefNode::ProgramDeclaration(_)
+            | RefNode::PackageDeclaration(_)
+    )
+}
+
+fn origin(tree: &SyntaxTree, locate: &Locate) -> (PathBuf, usize) {
+    tree.get_origin(locate)
+        .map(|(path, pos)| (path.clone(), pos))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_directive_states() {
+        let src = r##"module A; endmodule
+`timescale 1 ns / 10 ps
+`default_nettype none
+`celldefine
+module B;
+    timeprecision 1ps;
+endmodule
+`endcelldefine
+`unconnected_drive pull1
+interface C; endinterface
+`resetall
+package D;
+    timeunit 100ps / 1ps;
+endpackage"##;
+        let (syntax_tree, _) = parse_sv_str(
+            src,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let states = syntax_tree.get_directive_states(&DirectiveState::default());
+        let elements = &states.elements;
+        assert_eq!(elements.len(), 4);
+
+        assert_eq!(elements[0].identifier, "A");
+        assert_eq!(elements[0].timescale_source, TimescaleSource::Default);
+        assert_eq!(elements[0].default_nettype, "wire");
+
+        assert_eq!(elements[1].identifier, "B");
+        assert_eq!(elements[1].time_unit.as_deref(), Some("1ns"));
+        assert_eq!(elements[1].time_precision.as_deref(), Some("1ps"));
+        assert_eq!(elements[1].timescale_source, TimescaleSource::Declaration);
+        assert_eq!(elements[1].default_nettype, "none");
+        assert!(elements[1].celldefine);
+
+        assert_eq!(elements[2].identifier, "C");
+        assert_eq!(elements[2].time_precision.as_deref(), Some("10ps"));
+        assert_eq!(elements[2].timescale_source, TimescaleSource::Directive);
+        assert_eq!(elements[2].unconnected_drive.as_deref(), Some("pull1"));
+        assert!(!elements[2].celldefine);
+
+        assert_eq!(elements[3].identifier, "D");
+        assert_eq!(elements[3].time_unit.as_deref(), Some("100ps"));
+        assert_eq!(elements[3].default_nettype, "wire");
+        assert_eq!(elements[3].unconnected_drive, None);
+
+        assert_eq!(states.warnings.len(), 1);
+        assert!(matches!(states.warnings[0], Warning::TimescaleMissing(ref x, _) if x == "A"));
+
+        let src = r##"module E; endmodule"##;
+        let (syntax_tree, _) = parse_sv_str(
+            src,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let initial = DirectiveState {
+            timescale: states.elements[2].time_unit.clone().map(|unit| Timescale {
+                unit,
+                precision: String::from("10ps"),
+                origin: (PathBuf::from(""), 0),
+            }),
+            ..DirectiveState::default()
+        };
+        let states = syntax_tree.get_directive_states(&initial);
+        assert_eq!(
+            states.elements[0].timescale_source,
+            TimescaleSource::Inherited
+        );
+        assert!(matches!(states.warnings[0], Warning::TimescaleInherited(ref x, _) if x == "E"));
+    }
+}
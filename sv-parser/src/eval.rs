@@ -0,0 +1,404 @@
+use crate::*;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+impl SyntaxTree {
+    /// Evaluate a constant expression to an integer.
+    ///
+    /// Parameters are looked up in `parameters` by name. `None` is returned
+    /// if the expression refers to unknown identifiers, contains `x`, `z`,
+    /// reals or strings, or overflows a 64-bit signed integer.
+    pub fn eval_constant_expression(
+        &self,
+        x: &ConstantExpression,
+        parameters: &HashMap<String, i64>,
+    ) -> Option<i64> {
+        Eval::new(self, parameters).constant_expression(x)
+    }
+
+    /// Evaluate an expression to an integer like `eval_constant_expression`.
+    pub fn eval_expression(
+        &self,
+        x: &Expression,
+        parameters: &HashMap<String, i64>,
+    ) -> Option<i64> {
+        Eval::new(self, parameters).expression(x)
+    }
+
+    /// Evaluate `[msb:lsb]` to `(msb, lsb)`. `[]` and `[$]` are `None`.
+    pub fn eval_packed_dimension(
+        &self,
+        x: &PackedDimension,
+        parameters: &HashMap<String, i64>,
+    ) -> Option<(i64, i64)> {
+        match x {
+            PackedDimension::Range(x) => {
+                let eval = Eval::new(self, parameters);
+                let x = &(x.nodes.0).nodes.1;
+                let left = eval.constant_expression(&x.nodes.0)?;
+                let right = eval.constant_expression(&x.nodes.2)?;
+                Some((left, right))
+            }
+            PackedDimension::UnsizedDimension(_) => None,
+        }
+    }
+}
+
+// Binary operators are parsed right-recursively without precedence, and the
+// conditional operator takes the rest of the expression as its condition.
+// Operands are collected to apply operators by precedence.
+struct Chain<'b, T> {
+    operands: Vec<i64>,
+    operators: Vec<&'b BinaryOperator>,
+    conditional: Option<(&'b T, &'b T)>,
+}
+
+impl<'b, T> Chain<'b, T> {
+    fn new() -> Self {
+        Chain {
+            operands: Vec::new(),
+            operators: Vec::new(),
+            conditional: None,
+        }
+    }
+}
+
+// IEEE1800-2017 Clause 11.3.2
+fn precedence(op: &str) -> Option<u8> {
+    let ret = match op {
+        "**" => 10,
+        "*" | "/" | "%" => 9,
+        "+" | "-" => 8,
+        "<<" | ">>" | "<<<" | ">>>" => 7,
+        "<" | "<=" | ">" | ">=" => 6,
+        "==" | "!=" | "===" | "!==" => 5,
+        "&" => 4,
+        "^" | "^~" | "~^" => 3,
+        "|" => 2,
+        "&&" => 1,
+        "||" => 0,
+        _ => return None,
+    };
+    Some(ret)
+}
+
+struct Eval<'a> {
+    tree: &'a SyntaxTree,
+    parameters: &'a HashMap<String, i64>,
+}
+
+impl<'a> Eval<'a> {
+    fn new(tree: &'a SyntaxTree, parameters: &'a HashMap<String, i64>) -> Self {
+        Eval { tree, parameters }
+    }
+
+    fn constant_expression(&self, x: &ConstantExpression) -> Option<i64> {
+        let mut chain = Chain::new();
+        self.constant_chain(x, &mut chain)?;
+        let value = self.reduce(&chain)?;
+        match chain.conditional {
+            Some((y, _)) if value != 0 => self.constant_expression(y),
+            Some((_, y)) => self.constant_expression(y),
+            None => Some(value),
+        }
+    }
+
+    fn constant_chain<'b>(
+        &self,
+        x: &'b ConstantExpression,
+        chain: &mut Chain<'b, ConstantExpression>,
+    ) -> Option<()> {
+        if chain.conditional.is_some() {
+            return None;
+        }
+        match x {
+            ConstantExpression::ConstantPrimary(x) => {
+                chain.operands.push(self.constant_primary(x)?);
+            }
+            ConstantExpression::Unary(x) => {
+                let value = self.constant_primary(&x.nodes.2)?;
+                chain.operands.push(self.unary(&x.nodes.0, value)?);
+            }
+            ConstantExpression::Binary(x) => {
+                self.constant_chain(&x.nodes.0, chain)?;
+                chain.operators.push(&x.nodes.1);
+                self.constant_chain(&x.nodes.3, chain)?;
+            }
+            ConstantExpression::Ternary(x) => {
+                self.constant_chain(&x.nodes.0, chain)?;
+                chain.conditional = Some((&x.nodes.3, &x.nodes.5));
+            }
+        }
+        Some(())
+    }
+
+    fn constant_primary(&self, x: &ConstantPrimary) -> Option<i64> {
+        match x {
+            ConstantPrimary::PrimaryLiteral(x) => self.primary_literal(x),
+            ConstantPrimary::PsParameter(x) => {
+                let (ref name, ref select) = x.nodes;
+                match name {
+                    PsParameterIdentifier::Scope(y) if y.nodes.0.is_none() => {
+                        if self.tree.get_str(select).is_some() {
+                            return None;
+                        }
+                        self.parameter(&y.nodes.1)
+                    }
+                    _ => None,
+                }
+            }
+            ConstantPrimary::ConstantFunctionCall(x) => self.subroutine_call(&(x.nodes.0).nodes.0),
+            ConstantPrimary::MintypmaxExpression(x) => match (x.nodes.0).nodes.1 {
+                ConstantMintypmaxExpression::Unary(ref y) => self.constant_expression(y),
+                ConstantMintypmaxExpression::Ternary(_) => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn expression(&self, x: &Expression) -> Option<i64> {
+        let mut chain = Chain::new();
+        self.chain(x, &mut chain)?;
+        let value = self.reduce(&chain)?;
+        match chain.conditional {
+            Some((y, _)) if value != 0 => self.expression(y),
+            Some((_, y)) => self.expression(y),
+            None => Some(value),
+        }
+    }
+
+    fn chain<'b>(&self, x: &'b Expression, chain: &mut Chain<'b, Expression>) -> Option<()> {
+        if chain.conditional.is_some() {
+            return None;
+        }
+        match x {
+            Expression::Primary(x) => {
+                chain.operands.push(self.primary(x)?);
+            }
+            Expression::Unary(x) => {
+                let value = self.primary(&x.nodes.2)?;
+                chain.operands.push(self.unary(&x.nodes.0, value)?);
+            }
+            Expression::Binary(x) => {
+                self.chain(&x.nodes.0, chain)?;
+                chain.operators.push(&x.nodes.1);
+                self.chain(&x.nodes.3, chain)?;
+            }
+            Expression::ConditionalExpression(x) => {
+                match (x.nodes.0).nodes.0.contents().as_slice() {
+                    [ExpressionOrCondPattern::Expression(y)] => self.chain(y, chain)?,
+                    _ => return None,
+                }
+                chain.conditional = Some((&x.nodes.3, &x.nodes.5));
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+
+    // Apply binary operators by precedence
+    fn reduce<T>(&self, chain: &Chain<T>) -> Option<i64> {
+        let ops: Option<Vec<_>> = chain
+            .operators
+            .iter()
+            .map(|x| self.tree.get_str_trim(*x))
+            .collect();
+        let mut values = vec![*chain.operands.first()?];
+        let mut operators: Vec<&str> = Vec::new();
+        for (op, x) in ops?.into_iter().zip(&chain.operands[1..]) {
+            while let Some(top) = operators.last() {
+                if precedence(top)? < precedence(op)? {
+                    break;
+                }
+                let y = values.pop()?;
+                let x = values.pop()?;
+                values.push(self.binary(top, x, y)?);
+                operators.pop();
+            }
+            operators.push(op);
+            values.push(*x);
+        }
+        while let Some(op) = operators.pop() {
+            let y = values.pop()?;
+            let x = values.pop()?;
+            values.push(self.binary(op, x, y)?);
+        }
+        values.pop()
+    }
+
+    fn primary(&self, x: &Primary) -> Option<i64> {
+        match x {
+            Primary::PrimaryLiteral(x) => self.primary_literal(x),
+            Primary::Hierarchical(x) => {
+                let (ref scope, ref name, ref select) = x.nodes;
+                let (ref root, ref path, ref name) = name.nodes;
+                // An empty class qualifier is placed before simple identifiers
+                if self.tree.get_str(scope).is_some()
+                    || root.is_some()
+                    || !path.is_empty()
+                    || self.tree.get_str(select).is_some()
+                {
+                    return None;
+                }
+                self.parameter(name)
+            }
+            Primary::FunctionSubroutineCall(x) => self.subroutine_call(&x.nodes.0),
+            Primary::MintypmaxExpression(x) => match (x.nodes.0).nodes.1 {
+                MintypmaxExpression::Expression(ref y) => self.expression(y),
+                MintypmaxExpression::Ternary(_) => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn primary_literal(&self, x: &PrimaryLiteral) -> Option<i64> {
+        match x {
+            PrimaryLiteral::Number(x) => match x.as_ref() {
+                Number::IntegralNumber(x) => self.integral_number(x),
+                Number::RealNumber(_) => None,
+            },
+            PrimaryLiteral::UnbasedUnsizedLiteral(x) => match self.tree.get_str_trim(x.as_ref()) {
+                Some("'0") => Some(0),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn integral_number(&self, x: &IntegralNumber) -> Option<i64> {
+        let (size, radix, value) = match x {
+            IntegralNumber::DecimalNumber(x) => match x.as_ref() {
+                DecimalNumber::UnsignedNumber(x) => (None, 10, &x.nodes.0),
+                DecimalNumber::BaseUnsigned(x) => (x.nodes.0.as_ref(), 10, &(x.nodes.2).nodes.0),
+                _ => return None,
+            },
+            IntegralNumber::OctalNumber(x) => (x.nodes.0.as_ref(), 8, &(x.nodes.2).nodes.0),
+            IntegralNumber::BinaryNumber(x) => (x.nodes.0.as_ref(), 2, &(x.nodes.2).nodes.0),
+            IntegralNumber::HexNumber(x) => (x.nodes.0.as_ref(), 16, &(x.nodes.2).nodes.0),
+        };
+        let value: String = value
+            .str(self.tree.text.text())
+            .chars()
+            .filter(|x| *x != '_')
+            .collect();
+        let value = i64::from_str_radix(&value, radix).ok()?;
+        match size {
+            Some(x) => {
+                let size: u32 = self.tree.get_str_trim(x)?.parse().ok()?;
+                if size < 63 {
+                    Some(value & ((1 << size) - 1))
+                } else {
+                    Some(value)
+                }
+            }
+            None => Some(value),
+        }
+    }
+
+    fn parameter<T: AsRef<Identifier>>(&self, x: &T) -> Option<i64> {
+        self.parameters.get(x.text(self.tree)).copied()
+    }
+
+    fn subroutine_call(&self, x: &SubroutineCall) -> Option<i64> {
+        match x {
+            SubroutineCall::SystemTfCall(x) => self.system_tf_call(x),
+            // An identifier without arguments is parsed as a function call
+            SubroutineCall::TfCall(x) => match x.nodes {
+                (PsOrHierarchicalTfIdentifier::PackageScope(ref y), ref attrs, None)
+                    if y.nodes.0.is_none() && attrs.is_empty() =>
+                {
+                    self.parameter(&y.nodes.1)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn system_tf_call(&self, x: &SystemTfCall) -> Option<i64> {
+        let (name, args) = match x {
+            SystemTfCall::ArgOptionl(x) => match x.nodes.1 {
+                Some(ref y) => match y.nodes.1 {
+                    ListOfArguments::Ordered(ref z) => (&x.nodes.0, (z.nodes.0).contents()),
+                    ListOfArguments::Named(_) => return None,
+                },
+                None => (&x.nodes.0, vec![]),
+            },
+            SystemTfCall::ArgExpression(x) => (&x.nodes.0, ((x.nodes.1).nodes.1).0.contents()),
+            _ => return None,
+        };
+        let args: Option<Vec<_>> = args
+            .into_iter()
+            .map(|x| x.as_ref().and_then(|x| self.expression(x)))
+            .collect();
+        match (self.tree.get_str_trim(name)?, args?.as_slice()) {
+            ("$clog2", [x]) if *x >= 0 => Some(i64::from(64 - (x - 1).max(0).leading_zeros())),
+            _ => None,
+        }
+    }
+
+    fn unary(&self, op: &UnaryOperator, x: i64) -> Option<i64> {
+        match self.tree.get_str_trim(op)? {
+            "+" => Some(x),
+            "-" => x.checked_neg(),
+            "!" => Some((x == 0) as i64),
+            "~" => Some(!x),
+            _ => None,
+        }
+    }
+
+    fn binary(&self, op: &str, x: i64, y: i64) -> Option<i64> {
+        match op {
+            "+" => x.checked_add(y),
+            "-" => x.checked_sub(y),
+            "*" => x.checked_mul(y),
+            "/" => x.checked_div(y),
+            "%" => x.checked_rem(y),
+            "**" => x.checked_pow(u32::try_from(y).ok()?),
+            "<<" | "<<<" => x.checked_shl(u32::try_from(y).ok()?),
+            ">>" => Some(((x as u64).checked_shr(u32::try_from(y).ok()?)?) as i64),
+            ">>>" => x.checked_shr(u32::try_from(y).ok()?),
+            "&" => Some(x & y),
+            "|" => Some(x | y),
+            "^" => Some(x ^ y),
+            "^~" | "~^" => Some(!(x ^ y)),
+            "==" | "===" => Some((x == y) as i64),
+            "!=" | "!==" => Some((x != y) as i64),
+            "<" => Some((x < y) as i64),
+            "<=" => Some((x <= y) as i64),
+            ">" => Some((x > y) as i64),
+            ">=" => Some((x >= y) as i64),
+            "&&" => Some((x != 0 && y != 0) as i64),
+            "||" => Some((x != 0 || y != 0) as i64),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_eval() {
+        let src = "module A #(parameter W = 8, N = W * 4 + 'h10 - 2'b11, M = (N > 40) ? $clog2(N) : 1, S = W / 0) (input [M-1:0] a, input [X:0] b); endmodule";
+        let (syntax_tree, _) = parse_sv_str(
+            src,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let units = syntax_tree.get_design_unit_summaries();
+        let values: Vec<_> = units[0].parameters.iter().map(|x| x.value).collect();
+        assert_eq!(values, [Some(8), Some(45), Some(6), None]);
+        let ranges: Vec<_> = units[0]
+            .ports
+            .iter()
+            .map(|x| x.packed_ranges.clone())
+            .collect();
+        assert_eq!(ranges, [vec![Some((5, 0))], vec![None]]);
+    }
+}
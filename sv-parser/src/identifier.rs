@@ -0,0 +1,151 @@
+use crate::{Identifier, SyntaxTree};
+
+/// Source text of identifiers
+///
+/// This is implemented for `Identifier` and all identifiers wrapping it like
+/// `ModuleIdentifier`, because syntax tree nodes don't hold the source text.
+pub trait IdentifierText {
+    /// Get the name, where the leading `\` of escaped identifiers is removed
+    /// and `\bus[0]` becomes `bus[0]`.
+    fn text<'a>(&self, tree: &'a SyntaxTree) -> &'a str;
+}
+
+impl<T: AsRef<Identifier>> IdentifierText for T {
+    fn text<'a>(&self, tree: &'a SyntaxTree) -> &'a str {
+        let x = self.as_ref();
+        let s = x.locate().str(tree.text.text());
+        if x.is_escaped() {
+            &s[1..]
+        } else {
+            s
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_accessors() {
+        let src = r##"module A #(parameter W = 8, type T = logic, localparam D = W * 2) (input clk, output logic [W-1:0] \q[0] );
+    B #(.W(W)) u_b0 (.x(clk), .y(), .z), u_b1 (.*);
+    C u_c (clk, , \q[0] );
+    function automatic int f(input int a, int b);
+        return a + b;
+    endfunction
+endmodule
+module B (x, {y, z});
+    input x, y, z;
+    function g;
+        input a, b;
+        g = a;
+    endfunction
+endmodule"##;
+        let (syntax_tree, _) = parse_sv_str(
+            src,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let modules: Vec<_> = syntax_tree
+            .into_iter()
+            .filter_map(|x| match x {
+                RefNode::ModuleDeclaration(x) => Some(x),
+                _ => None,
+            })
+            .collect();
+        let text = |x: Option<&PortIdentifier>| x.map(|x| x.text(&syntax_tree));
+
+        let a = modules[0];
+        assert_eq!(a.name().text(&syntax_tree), "A");
+        let params: Vec<_> = a
+            .parameters()
+            .iter()
+            .map(|x| x.name().text(&syntax_tree))
+            .collect();
+        assert_eq!(params, ["W", "D"]);
+        let ports: Vec<_> = a.ports().into_iter().map(|x| text(x.name())).collect();
+        assert_eq!(ports, [Some("clk"), Some("q[0]")]);
+        assert!(a.ports()[1].name().unwrap().nodes.0.is_escaped());
+        assert_eq!(a.items().len(), 3);
+
+        let insts: Vec<_> = a
+            .items()
+            .into_iter()
+            .filter_map(|x| unwrap_node!(x, ModuleInstantiation))
+            .collect();
+        let b = match insts[0] {
+            RefNode::ModuleInstantiation(x) => x,
+            _ => unreachable!(),
+        };
+        assert_eq!(b.module_name().text(&syntax_tree), "B");
+        let instances = b.instances();
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].name().text(&syntax_tree), "u_b0");
+        let conns: Vec<_> = instances[0]
+            .connections()
+            .into_iter()
+            .map(|x| {
+                (
+                    text(x.name()),
+                    x.expression().and_then(|x| syntax_tree.get_str_trim(x)),
+                )
+            })
+            .collect();
+        assert_eq!(
+            conns,
+            [
+                (Some("x"), Some("clk")),
+                (Some("y"), None),
+                (Some("z"), None)
+            ]
+        );
+        let conns = instances[1].connections();
+        assert!(matches!(conns[..], [PortConnection::Named(_)]));
+        assert_eq!(conns[0].name(), None);
+
+        let c = match insts[1] {
+            RefNode::ModuleInstantiation(x) => x,
+            _ => unreachable!(),
+        };
+        let conns: Vec<_> = c.instances()[0]
+            .connections()
+            .into_iter()
+            .map(|x| {
+                assert!(matches!(x, PortConnection::Ordered(_)));
+                x.expression().and_then(|x| syntax_tree.get_str_trim(x))
+            })
+            .collect();
+        assert_eq!(conns, [Some("clk"), None, Some(r"\q[0]")]);
+
+        let f = unwrap_node!(a, FunctionDeclaration);
+        let f = match f {
+            Some(RefNode::FunctionDeclaration(x)) => x,
+            _ => unreachable!(),
+        };
+        assert_eq!(f.name().text(&syntax_tree), "f");
+        assert_eq!(syntax_tree.get_str_trim(f.return_type()), Some("int"));
+        let args: Vec<_> = f.arguments().into_iter().map(|x| text(x.name())).collect();
+        assert_eq!(args, [Some("a"), Some("b")]);
+
+        let b = modules[1];
+        assert_eq!(b.name().text(&syntax_tree), "B");
+        assert!(b.parameters().is_empty());
+        let ports: Vec<_> = b.ports().into_iter().map(|x| text(x.name())).collect();
+        assert_eq!(ports, [Some("x"), None]);
+        assert_eq!(b.items().len(), 1);
+
+        let g = match unwrap_node!(b, FunctionDeclaration) {
+            Some(RefNode::FunctionDeclaration(x)) => x,
+            _ => unreachable!(),
+        };
+        assert_eq!(g.name().text(&syntax_tree), "g");
+        assert_eq!(syntax_tree.get_str_trim(g.return_type()), None);
+        let args: Vec<_> = g.arguments().into_iter().map(|x| text(x.name())).collect();
+        assert_eq!(args, [Some("a"), Some("b")]);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{DirectiveState, Locate, NodeEvent, RefNode, RefNodes, SyntaxTree};
+use std::collections::HashSet;
+use sv_parser_pp::preprocess::edit_distance;
+
+/// A net declared implicitly by its usage
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImplicitNet {
+    /// Module, interface or program where the net is declared
+    pub module: String,
+    pub identifier: String,
+    /// Net type given by `default_nettype
+    pub net_type: String,
+    /// Implicit nets are always scalar
+    pub width: usize,
+    /// The first usage which declares the net
+    pub locate: Locate,
+    /// Declared signal whose name looks like the intended spelling
+    pub similar: Option<String>,
+}
+
+impl SyntaxTree {
+    /// Get nets declared implicitly in port connections and left-hand sides
+    /// of continuous assignments.
+    ///
+    /// `initial` is the compiler directive state left by the previously
+    /// compiled file, see `get_directive_states`.
+    pub fn get_implicit_nets(&self, initial: &DirectiveState) -> Vec<ImplicitNet> {
+        let mut ret = Vec::new();
+        for element in self.get_directive_states(initial).elements {
+            // IEEE1800-2017 Clause 22.8
+            // If `default_nettype none is specified, implicit nets are errors.
+            if element.default_nettype == "none"
+                || matches!(element.node, RefNode::PackageDeclaration(_))
+            {
+                continue;
+            }
+
+            let mut declared = HashSet::new();
+            let mut usages: Vec<(String, Locate)> = Vec::new();
+            let mut nested = 0;
+            for node in element.node.clone().into_iter().event() {
+                match node {
+                    NodeEvent::Enter(x) if is_design_element(&x) && x != element.node => {
+                        nested += 1;
+                    }
+                    NodeEvent::Leave(x) if is_design_element(&x) && x != element.node => {
+                        nested -= 1;
+                    }
+                    // Nested design elements are reported separately
+                    _ if nested > 0 => (),
+                    NodeEvent::Enter(x) => {
+                        if let Some(x) = declaration(&x) {
+                            for x in x {
+                                let declared_node = matches!(
+                                    x,
+                                    RefNode::NetIdentifier(_)
+                                        | RefNode::VariableIdentifier(_)
+                                        | RefNode::PortIdentifier(_)
+                                        | RefNode::InterfaceIdentifier(_)
+                                        | RefNode::ParameterIdentifier(_)
+                                        | RefNode::SpecparamIdentifier(_)
+                                        | RefNode::GenvarIdentifier(_)
+                                );
+                                if declared_node {
+                                    if let Some(id) = self.get_str_trim(RefNodes(vec![x])) {
+                                        declared.insert(String::from(id));
+                                    }
+                                }
+                            }
+                        }
+                        for x in usages_in(&x) {
+                            if let Some(id) = self.get_str_trim(RefNodes(vec![x.clone()])) {
+                                if is_simple_identifier(id) {
+                                    let locate = crate::unwrap_locate!(x).unwrap();
+                                    usages.push((String::from(id), *locate));
+                                }
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+
+            let mut reported = HashSet::new();
+            for (id, locate) in usages {
+                if declared.contains(&id) || !reported.insert(id.clone()) {
+                    continue;
+                }
+                let similar = similar_name(&id, &declared);
+                ret.push(ImplicitNet {
+                    module: element.identifier.clone(),
+                    identifier: id,
+                    net_type: element.default_nettype.clone(),
+                    width: 1,
+                    locate,
+                    similar,
+                });
+            }
+        }
+        ret
+    }
+}
+
+fn is_design_element(x: &RefNode) -> bool {
+    matches!(
+        x,
+        RefNode::ModuleDeclaration(_)
+            | RefNode::InterfaceDeclaration(_)
+            | RefNode::ProgramDeclaration(_)
+            | RefNode::PackageDeclaration(_)
+    )
+}
+
+// Nodes declaring names in the scope
+fn declaration<'a>(x: &RefNode<'a>) -> Option<RefNode<'a>> {
+    match x {
+        RefNode::NetDeclAssignment(x) => Some((&x.nodes.0).into()),
+        RefNode::VariableDeclAssignment(_)
+        | RefNode::AnsiPortDeclaration(_)
+        | RefNode::ListOfPortIdentifiers(_)
+        | RefNode::ListOfVariableIdentifiers(_)
+        | RefNode::ListOfVariablePortIdentifiers(_)
+        | RefNode::ListOfInterfaceIdentifiers(_)
+        | RefNode::ListOfGenvarIdentifiers(_)
+        | RefNode::PortReference(_) => Some(x.clone()),
+        RefNode::ParamAssignment(x) => Some((&x.nodes.0).into()),
+        RefNode::SpecparamAssignmentMintypmax(x) => Some((&x.nodes.0).into()),
+        RefNode::GenvarInitialization(x) => Some((&x.nodes.1).into()),
+        _ => None,
+    }
+}
+
+// IEEE1800-2017 Clause 6.10
+// Nodes where an undeclared identifier declares an implicit scalar net
+fn usages_in<'a>(x: &RefNode<'a>) -> Vec<RefNode<'a>> {
+    match x {
+        RefNode::ContinuousAssign(_) => {
+            let mut ret = Vec::new();
+            for x in x.clone() {
+                match x {
+                    RefNode::NetAssignment(x) => ret.push((&x.nodes.0).into()),
+                    RefNode::VariableAssignment(x) => ret.push((&x.nodes.0).into()),
+                    _ => (),
+                }
+            }
+            ret
+        }
+        RefNode::OrderedPortConnection(x) => x.nodes.1.iter().map(|x| x.into()).collect(),
+        RefNode::NamedPortConnectionIdentifier(x) => match x.nodes.3 {
+            Some(ref x) => x.nodes.1.iter().map(|x| x.into()).collect(),
+            None => vec![],
+        },
+        RefNode::OutputTerminal(x) => vec![(&x.nodes.0).into()],
+        RefNode::InoutTerminal(x) => vec![(&x.nodes.0).into()],
+        RefNode::InputTerminal(x) => vec![(&x.nodes.0).into()],
+        _ => vec![],
+    }
+}
+
+fn is_simple_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(x) if x.is_ascii_alphabetic() || x == '_' => (),
+        _ => return false,
+    }
+    chars.all(|x| x.is_ascii_alphanumeric() || x == '_' || x == '$')
+}
+
+// Find a declared name which looks like the intended spelling of `id`.
+fn similar_name(id: &str, declared: &HashSet<String>) -> Option<String> {
+    let lower = id.to_lowercase();
+    declared
+        .iter()
+        .filter(|x| x.to_lowercase() == lower || edit_distance(x, id) <= 1)
+        .min_by_key(|x| (edit_distance(x, id), (*x).clone()))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_implicit_nets() {
+        let src = r##"module A (input clk, input [7:0] data_in, output y);
+    wire data_valid;
+    B u_b (.clk(clk), .d(data_in), .v(data_vaild), .q(q_out));
+    C u_c (q_out, n1[0], 1'b0);
+    and g (n2, clk, data_valid);
+    assign y = q_out & n2;
+    assign z = clk;
+endmodule
+`default_nettype none
+module D;
+    assign w = 1'b0;
+endmodule"##;
+        let (syntax_tree, _) =
+            parse_sv_str(src, PathBuf::from(""), &HashMap::new(), &[""], &ParseOptions::default())
+                .unwrap();
+        let nets = syntax_tree.get_implicit_nets(&DirectiveState::default());
+        let ids: Vec<_> = nets.iter().map(|x| x.identifier.as_str()).collect();
+        assert_eq!(ids, vec!["data_vaild", "q_out", "n2", "z"]);
+        assert!(nets.iter().all(|x| x.module == "A" && x.net_type == "wire"));
+        assert_eq!(nets[0].similar.as_deref(), Some("data_valid"));
+        assert_eq!(nets[1].similar, None);
+        assert_eq!(syntax_tree.get_str(&nets[3].locate), Some("z"));
+    }
+}
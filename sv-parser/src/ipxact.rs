@@ -0,0 +1,335 @@
+use crate::*;
+use std::path::Path;
+
+const IPXACT_NAMESPACE: &str = "http://www.accellera.org/XMLSchema/IPXACT/1685-2014";
+
+impl DesignUnitSummary {
+    /// Serialize to an IP-XACT (IEEE1685-2014) component description.
+    ///
+    /// The component is identified by `vendor`, `library`, `version` and the
+    /// unit name. Ports with `input`, `output` or `inout` become wire ports
+    /// whose vectors are the evaluated packed dimensions; vectors are omitted
+    /// if a dimension isn't constant. Parameters which can be overridden
+    /// become module parameters with their default values. `sources` and
+    /// `includes` are listed in a file set.
+    pub fn to_ipxact<T: AsRef<Path>, U: AsRef<Path>>(
+        &self,
+        vendor: &str,
+        library: &str,
+        version: &str,
+        sources: &[T],
+        includes: &[U],
+    ) -> String {
+        let mut xml = Xml::new();
+        xml.line("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        xml.open(&format!(
+            "ipxact:component xmlns:ipxact=\"{0}\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xsi:schemaLocation=\"{0} {0}/index.xsd\"",
+            IPXACT_NAMESPACE
+        ));
+        xml.element("ipxact:vendor", vendor);
+        xml.element("ipxact:library", library);
+        xml.element("ipxact:name", &self.name);
+        xml.element("ipxact:version", version);
+
+        xml.open("ipxact:model");
+        xml.open("ipxact:views");
+        xml.open("ipxact:view");
+        xml.element("ipxact:name", "rtl");
+        xml.element("ipxact:componentInstantiationRef", "rtl");
+        xml.close("ipxact:view");
+        xml.close("ipxact:views");
+
+        xml.open("ipxact:instantiations");
+        xml.open("ipxact:componentInstantiation");
+        xml.element("ipxact:name", "rtl");
+        xml.element("ipxact:language", "SystemVerilog");
+        xml.element("ipxact:moduleName", &self.name);
+        let parameters: Vec<_> = self
+            .parameters
+            .iter()
+            .filter(|x| !x.local && !x.is_type)
+            .collect();
+        if !parameters.is_empty() {
+            xml.open("ipxact:moduleParameters");
+            for x in parameters {
+                let mut tag = format!(
+                    "ipxact:moduleParameter parameterId=\"{}\" resolve=\"user\"",
+                    xml_str(&x.name)
+                );
+                if let Some(ref y) = x.data_type {
+                    tag.push_str(&format!(" dataType=\"{}\"", xml_str(y)));
+                }
+                xml.open(&tag);
+                xml.element("ipxact:name", &x.name);
+                let value = match (x.value, &x.default) {
+                    (Some(y), _) => y.to_string(),
+                    (None, Some(y)) => y.clone(),
+                    (None, None) => String::new(),
+                };
+                xml.element("ipxact:value", &value);
+                xml.close("ipxact:moduleParameter");
+            }
+            xml.close("ipxact:moduleParameters");
+        }
+        xml.open("ipxact:fileSetRef");
+        xml.element("ipxact:localName", "rtl");
+        xml.close("ipxact:fileSetRef");
+        xml.close("ipxact:componentInstantiation");
+        xml.close("ipxact:instantiations");
+
+        let ports: Vec<_> = self
+            .ports
+            .iter()
+            .filter_map(|x| {
+                let direction = match x.direction.as_deref() {
+                    Some("input") => "in",
+                    Some("output") => "out",
+                    Some("inout") => "inout",
+                    _ => return None,
+                };
+                Some((x, direction))
+            })
+            .collect();
+        if !ports.is_empty() {
+            xml.open("ipxact:ports");
+            for (x, direction) in ports {
+                xml.open("ipxact:port");
+                xml.element("ipxact:name", &x.name);
+                xml.open("ipxact:wire");
+                xml.element("ipxact:direction", direction);
+                let ranges: Option<Vec<_>> = x.packed_ranges.iter().cloned().collect();
+                match ranges {
+                    Some(ranges) if !ranges.is_empty() => {
+                        xml.open("ipxact:vectors");
+                        for (left, right) in ranges {
+                            xml.open("ipxact:vector");
+                            xml.element("ipxact:left", &left.to_string());
+                            xml.element("ipxact:right", &right.to_string());
+                            xml.close("ipxact:vector");
+                        }
+                        xml.close("ipxact:vectors");
+                    }
+                    _ => (),
+                }
+                if let Some(ref y) = x.data_type {
+                    xml.open("ipxact:wireTypeDefs");
+                    xml.open("ipxact:wireTypeDef");
+                    xml.element("ipxact:typeName", y);
+                    xml.element("ipxact:viewRef", "rtl");
+                    xml.close("ipxact:wireTypeDef");
+                    xml.close("ipxact:wireTypeDefs");
+                }
+                xml.close("ipxact:wire");
+                xml.close("ipxact:port");
+            }
+            xml.close("ipxact:ports");
+        }
+        xml.close("ipxact:model");
+
+        xml.open("ipxact:fileSets");
+        xml.open("ipxact:fileSet");
+        xml.element("ipxact:name", "rtl");
+        let sources = sources.iter().map(|x| (x.as_ref(), false));
+        let includes = includes.iter().map(|x| (x.as_ref(), true));
+        for (path, include) in sources.chain(includes) {
+            xml.open("ipxact:file");
+            xml.element("ipxact:name", &path.to_string_lossy());
+            let file_type = match path.extension().and_then(|x| x.to_str()) {
+                Some("v") | Some("vh") => "verilogSource",
+                _ => "systemVerilogSource",
+            };
+            xml.element("ipxact:fileType", file_type);
+            if include {
+                xml.element("ipxact:isIncludeFile", "true");
+            }
+            xml.close("ipxact:file");
+        }
+        xml.close("ipxact:fileSet");
+        xml.close("ipxact:fileSets");
+
+        xml.close("ipxact:component");
+        xml.text
+    }
+}
+
+// Indented XML text
+struct Xml {
+    text: String,
+    depth: usize,
+}
+
+impl Xml {
+    fn new() -> Self {
+        Xml {
+            text: String::new(),
+            depth: 0,
+        }
+    }
+
+    fn line(&mut self, x: &str) {
+        for _ in 0..self.depth {
+            self.text.push_str("  ");
+        }
+        self.text.push_str(x);
+        self.text.push('\n');
+    }
+
+    // `tag` may contain attributes which are already escaped
+    fn open(&mut self, tag: &str) {
+        self.line(&format!("<{}>", tag));
+        self.depth += 1;
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.line(&format!("</{}>", tag));
+    }
+
+    fn element(&mut self, tag: &str, value: &str) {
+        self.line(&format!("<{0}>{1}</{0}>", tag, xml_str(value)));
+    }
+}
+
+fn xml_str(x: &str) -> String {
+    let mut ret = String::new();
+    for c in x.chars() {
+        match c {
+            '&' => ret.push_str("&amp;"),
+            '<' => ret.push_str("&lt;"),
+            '>' => ret.push_str("&gt;"),
+            '"' => ret.push_str("&quot;"),
+            '\'' => ret.push_str("&apos;"),
+            _ => ret.push(c),
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::testfile_path;
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_ipxact() {
+        let dir = testfile_path("ipxact");
+
+        let (syntax_tree, _) = parse_sv(
+            dir.join("fifo.sv"),
+            &HashMap::new(),
+            &[&dir],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(syntax_tree.get_includes(), [&dir.join("fifo.svh")]);
+        let unit = &syntax_tree.get_design_unit_summaries()[0];
+        let xml = unit.to_ipxact(
+            "example.com",
+            "ip",
+            "1.0",
+            &[dir.join("fifo.sv")],
+            &syntax_tree.get_includes(),
+        );
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<ipxact:component xmlns:ipxact=\"http://www.accellera.org/XMLSchema/IPXACT/1685-2014\""));
+        assert!(xml.contains("\n  <ipxact:vendor>example.com</ipxact:vendor>\n  <ipxact:library>ip</ipxact:library>\n  <ipxact:name>fifo</ipxact:name>\n  <ipxact:version>1.0</ipxact:version>\n"));
+        assert!(xml.contains(
+            "<ipxact:moduleParameter parameterId=\"DEPTH\" resolve=\"user\" dataType=\"int\">"
+        ));
+        assert!(xml.ends_with("</ipxact:component>\n"));
+
+        // Read the exported description back and compare with the summary
+        let elements = |tag: &str| -> Vec<String> {
+            let open = format!("<ipxact:{}>", tag);
+            let close = format!("</ipxact:{}>", tag);
+            xml.split(&open)
+                .skip(1)
+                .map(|x| String::from(&x[..x.find(&close).unwrap()]))
+                .collect()
+        };
+        let ports: Vec<_> = xml
+            .split("<ipxact:port>")
+            .skip(1)
+            .map(|x| {
+                let x = &x[..x.find("</ipxact:port>").unwrap()];
+                let text = |tag: &str| {
+                    x.split(&format!("<ipxact:{}>", tag))
+                        .nth(1)
+                        .map(|y| String::from(&y[..y.find('<').unwrap()]))
+                };
+                (
+                    text("name").unwrap(),
+                    text("direction").unwrap(),
+                    text("left"),
+                    text("right"),
+                )
+            })
+            .collect();
+        let expected: Vec<_> = unit
+            .ports
+            .iter()
+            .filter(|x| x.direction.is_some())
+            .map(|x| {
+                let direction = match x.direction.as_deref() {
+                    Some("input") => "in",
+                    _ => "out",
+                };
+                let range = x.packed_ranges.first().map(|y| y.unwrap());
+                (
+                    x.name.clone(),
+                    String::from(direction),
+                    range.map(|y| y.0.to_string()),
+                    range.map(|y| y.1.to_string()),
+                )
+            })
+            .collect();
+        assert_eq!(ports, expected);
+        assert_eq!(
+            ports,
+            [
+                (String::from("clk"), String::from("in"), None, None),
+                (String::from("rst_n"), String::from("in"), None, None),
+                (
+                    String::from("wdata"),
+                    String::from("in"),
+                    Some(String::from("7")),
+                    Some(String::from("0"))
+                ),
+                (
+                    String::from("rdata"),
+                    String::from("out"),
+                    Some(String::from("7")),
+                    Some(String::from("0"))
+                ),
+                (
+                    String::from("count"),
+                    String::from("out"),
+                    Some(String::from("4")),
+                    Some(String::from("0"))
+                ),
+            ]
+        );
+
+        let parameters: Vec<_> = unit
+            .parameters
+            .iter()
+            .filter(|x| !x.local)
+            .map(|x| x.name.clone())
+            .collect();
+        assert_eq!(&elements("name")[3..6], parameters.as_slice());
+        assert_eq!(elements("value"), ["16", "8", "&quot;a&lt;b&gt;&quot;"]);
+        assert_eq!(
+            elements("fileType"),
+            ["systemVerilogSource", "systemVerilogSource"]
+        );
+        assert_eq!(elements("isIncludeFile"), ["true"]);
+        let files = elements("name");
+        assert_eq!(
+            &files[files.len() - 2..],
+            [
+                dir.join("fifo.sv").to_string_lossy(),
+                dir.join("fifo.svh").to_string_lossy()
+            ]
+        );
+    }
+}
@@ -1,23 +1,64 @@
 #![recursion_limit = "256"]
 
 use nom_greedyerror::error_position;
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::fmt;
 use std::hash::BuildHasher;
 use std::path::{Path, PathBuf};
-pub use sv_parser_error::Error;
+pub use sv_parser_error::{Error, Warning};
 use sv_parser_parser::{
-    lib_parser, lib_parser_incomplete, sv_parser, sv_parser_incomplete, Span, SpanInfo,
+    lib_parser, lib_parser_incomplete, sv_parser, sv_parser_incomplete,
+    Span, SpanInfo,
 };
+pub use sv_parser_parser::{KeywordOverrides, LanguageVersion};
+pub use sv_parser_pp::comment_pragma::{CommentPragma, CommentPragmaKind};
 pub use sv_parser_pp::preprocess::{
-    preprocess, preprocess_str, Define, DefineText, Defines, PreprocessedText,
+    preprocess, preprocess_str, Conditional, ConditionalBranch, ConditionalKind, Define,
+    DefineText, Defines, Expansion, MacroDefinition, MacroUsage, PreprocessOptions,
+    PreprocessedText,
 };
 pub use sv_parser_syntaxtree::*;
 
+mod builder;
+mod diagnostic;
+mod directive_state;
+mod eval;
+mod identifier;
+mod implicit_net;
+mod ipxact;
+mod node_table;
+mod pickle;
+mod protect;
+mod query;
+mod stub;
+mod summary;
+pub use builder::*;
+pub use diagnostic::*;
+pub use directive_state::*;
+pub use identifier::*;
+pub use implicit_net::*;
+pub use node_table::*;
+pub use pickle::*;
+pub use protect::*;
+pub use query::*;
+pub use summary::*;
+
 pub struct SyntaxTree {
     node: AnyNode,
     text: PreprocessedText,
 }
 
+/// A comment pragma and the syntax nodes it applies to
+#[derive(Clone, Debug)]
+pub struct CommentPragmaNode<'a> {
+    pub pragma: CommentPragma,
+    pub comment: &'a Comment,
+    /// The first syntax node after the comment
+    pub next: Option<RefNode<'a>>,
+    /// The innermost syntax node containing the comment
+    pub enclosing: Option<RefNode<'a>>,
+}
+
 impl SyntaxTree {
     /// Get `&str` from the specified node
     pub fn get_str<'a, T: Into<RefNodes<'a>>>(&self, nodes: T) -> Option<&str> {
@@ -73,6 +114,101 @@ impl SyntaxTree {
     pub fn get_origin(&self, locate: &Locate) -> Option<(&PathBuf, usize)> {
         self.text.origin(locate.offset)
     }
+
+    /// Get `include directives and text macro usages which produced the
+    /// specified `Locate`, from the innermost
+    pub fn get_expansions(&self, locate: &Locate) -> Vec<&Expansion> {
+        self.text.expansions(locate.offset)
+    }
+
+    /// Get the text of a source file which `get_origin` points into
+    pub fn get_source(&self, path: &Path) -> Option<&str> {
+        self.text.source(path)
+    }
+
+    /// Get conditional blocks evaluated during preprocess
+    pub fn get_conditionals(&self) -> &[Conditional] {
+        self.text.conditionals()
+    }
+
+    /// Get `define directives processed during preprocess
+    pub fn get_macro_definitions(&self) -> &[MacroDefinition] {
+        self.text.macro_definitions()
+    }
+
+    /// Get text macro usages expanded during preprocess
+    pub fn get_macro_usages(&self) -> &[MacroUsage] {
+        self.text.macro_usages()
+    }
+
+    /// Get files read by `include directives during preprocess
+    pub fn get_includes(&self) -> Vec<&PathBuf> {
+        self.text.includes()
+    }
+
+    /// Get non-fatal diagnostics found during preprocess
+    pub fn get_warnings(&self) -> &[Warning] {
+        self.text.warnings()
+    }
+
+    /// Get tool pragmas written in comments like `// synopsys translate_off`
+    pub fn get_comment_pragmas(&self) -> Vec<CommentPragmaNode<'_>> {
+        // Tokens and whitespace are not regarded as the target of pragmas
+        let is_syntax = |x: &RefNode| {
+            !matches!(
+                x,
+                RefNode::Locate(_)
+                    | RefNode::WhiteSpace(_)
+                    | RefNode::Comment(_)
+                    | RefNode::Symbol(_)
+                    | RefNode::Keyword(_)
+            )
+        };
+
+        let mut ret: Vec<CommentPragmaNode> = Vec::new();
+        let mut pending = Vec::new();
+        let mut stack: Vec<RefNode> = Vec::new();
+        let mut whitespace = 0;
+        for node in self.into_iter().event() {
+            match node {
+                NodeEvent::Enter(x) => {
+                    match x {
+                        RefNode::WhiteSpace(_) => {
+                            whitespace += 1;
+                        }
+                        RefNode::Comment(comment) => {
+                            if let Some(pragma) =
+                                self.get_str(comment).and_then(CommentPragma::parse)
+                            {
+                                let enclosing = stack.iter().rev().find(|x| is_syntax(x)).cloned();
+                                pending.push(ret.len());
+                                ret.push(CommentPragmaNode {
+                                    pragma,
+                                    comment,
+                                    next: None,
+                                    enclosing,
+                                });
+                            }
+                        }
+                        ref x if whitespace == 0 && is_syntax(x) => {
+                            for i in pending.drain(..) {
+                                ret[i].next = Some(x.clone());
+                            }
+                        }
+                        _ => (),
+                    }
+                    stack.push(x);
+                }
+                NodeEvent::Leave(x) => {
+                    if let RefNode::WhiteSpace(_) = x {
+                        whitespace -= 1;
+                    }
+                    stack.pop();
+                }
+            }
+        }
+        ret
+    }
 }
 
 impl fmt::Display for SyntaxTree {
@@ -181,81 +317,316 @@ impl<'a> IntoIterator for &'a SyntaxTree {
     }
 }
 
+/// Stack size recommended for threads parsing deeply nested source text
+pub const PARSER_STACK_SIZE: usize = 20 * 1024 * 1024;
+
+/// Build `Defines` of macros without arguments from pairs of an identifier and
+/// a text. The text is `None` for macros defined without text, e.g. by
+/// `+define+NAME`.
+pub fn defines_from<I, K, V>(defines: I) -> Defines
+where
+    I: IntoIterator<Item = (K, Option<V>)>,
+    K: Into<String>,
+    V: Into<String>,
+{
+    let mut ret = Defines::new();
+    for (ident, text) in defines {
+        let ident = ident.into();
+        let text = text.map(|x| DefineText::new(x.into(), None));
+        let define = Define::new(ident.clone(), vec![], text);
+        ret.insert(ident, Some(define));
+    }
+    ret
+}
+
+/// Options of `parse_sv`, `parse_sv_str` and their `_branches` variants
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Skip `include directives instead of reading the included files
+    pub ignore_include: bool,
+    /// Accept source text which ends in the middle of a description
+    pub allow_incomplete: bool,
+    /// Keep usages of undefined macros in the text with a warning instead of
+    /// failing, see `PreprocessOptions::allow_undefined`
+    pub allow_undefined: bool,
+    /// Remove the text between `translate_off` and `translate_on` pragma
+    /// comments before parsing
+    pub strip_translate_off: bool,
+    /// If given, keywords and constructs which are not legal in that version
+    /// are rejected except in `begin_keywords` regions.
+    pub language_version: Option<LanguageVersion>,
+    /// Words reserved or un-reserved on top of the keywords of the version
+    pub keyword_overrides: Option<KeywordOverrides>,
+    /// Accept the analog and mixed-signal extensions of Verilog-AMS 2.4 on
+    /// top of `language_version`
+    pub verilog_ams: bool,
+}
+
+impl ParseOptions {
+    fn preprocess_options(&self) -> PreprocessOptions {
+        PreprocessOptions {
+            ignore_include: self.ignore_include,
+            allow_undefined: self.allow_undefined,
+            strip_translate_off: self.strip_translate_off,
+            ..PreprocessOptions::default()
+        }
+    }
+}
+
 pub fn parse_sv<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
     path: T,
     pre_defines: &Defines<V>,
     include_paths: &[U],
-    ignore_include: bool,
-    allow_incomplete: bool,
+    options: &ParseOptions,
 ) -> Result<(SyntaxTree, Defines), Error> {
     let (text, defines) = preprocess(
         path,
         pre_defines,
         include_paths,
-        false, // strip_comments
-        ignore_include,
+        &options.preprocess_options(),
     )?;
-    parse_sv_pp(text, defines, allow_incomplete)
+    parse_sv_pp(text, defines, options)
 }
 
+/// Parse preprocessed text.
+///
+/// `options.ignore_include`, `options.allow_undefined` and
+/// `options.strip_translate_off` are not used because the text is already
+/// preprocessed.
 pub fn parse_sv_pp(
     text: PreprocessedText,
     defines: Defines,
-    allow_incomplete: bool,
+    options: &ParseOptions,
 ) -> Result<(SyntaxTree, Defines), Error> {
-    let span = Span::new_extra(text.text(), SpanInfo::default());
-    let result = if allow_incomplete {
-        sv_parser_incomplete(span)
-    } else {
-        sv_parser(span)
-    };
-    match result {
-        Ok((_, x)) => Ok((
+    match parse_text(&text, options) {
+        Ok(x) => Ok((
             SyntaxTree {
                 node: x.into(),
                 text,
             },
             defines,
         )),
-        Err(x) => {
-            let pos = match x {
-                nom::Err::Incomplete(_) => None,
-                nom::Err::Error(e) => error_position(&e),
-                nom::Err::Failure(e) => error_position(&e),
-            };
-            let origin = if let Some(pos) = pos {
-                if let Some(origin) = text.origin(pos) {
-                    Some((origin.0.clone(), origin.1))
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
-            Err(Error::Parse(origin))
+        Err(pos) => Err(parse_error(&text, pos)),
+    }
+}
+
+/// Parse preprocessed text like `parse_sv_pp`, but return a parse error as a
+/// `Diagnostic` with line and column numbers, and the `include directives
+/// and text macro usages which produced the failed position as related
+/// locations.
+pub fn parse_sv_pp_diagnostic(
+    text: PreprocessedText,
+    defines: Defines,
+    options: &ParseOptions,
+) -> Result<(SyntaxTree, Defines), Box<Diagnostic>> {
+    match parse_text(&text, options) {
+        Ok(x) => Ok((
+            SyntaxTree {
+                node: x.into(),
+                text,
+            },
+            defines,
+        )),
+        Err(pos) => {
+            let mut ret = Diagnostic::from(&parse_error(&text, pos));
+            if let Some(pos) = pos {
+                ret.related = text
+                    .expansions(pos)
+                    .into_iter()
+                    .map(RelatedLocation::from)
+                    .collect();
+            }
+            ret.resolve(|x| text.source(x));
+            Err(Box::new(ret))
         }
     }
 }
 
+// Returns the failed position in preprocessed text on error
+fn parse_text(
+    text: &PreprocessedText,
+    options: &ParseOptions,
+) -> Result<SourceText, Option<usize>> {
+    // Text macro usages remain in preprocessed text only when preprocess
+    // left undefined macros as placeholders.
+    let info = SpanInfo {
+        macro_placeholder: true,
+        language_version: options.language_version,
+        keyword_overrides: options.keyword_overrides.as_ref(),
+        verilog_ams: options.verilog_ams,
+        ..SpanInfo::default()
+    };
+    let span = Span::new_extra(text.text(), info);
+    let result = if options.allow_incomplete {
+        sv_parser_incomplete(span)
+    } else {
+        sv_parser(span)
+    };
+    match result {
+        Ok((_, x)) => Ok(x),
+        Err(x) => Err(match x {
+            nom::Err::Incomplete(_) => None,
+            nom::Err::Error(e) => error_position(&e),
+            nom::Err::Failure(e) => error_position(&e),
+        }),
+    }
+}
+
 pub fn parse_sv_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
     s: &str,
     path: T,
     pre_defines: &Defines<V>,
     include_paths: &[U],
-    ignore_include: bool,
-    allow_incomplete: bool,
+    options: &ParseOptions,
 ) -> Result<(SyntaxTree, Defines), Error> {
     let (text, defines) = preprocess_str(
         s,
         path,
         pre_defines,
         include_paths,
-        ignore_include,
-        false, // strip_comments
+        &options.preprocess_options(),
         0, // resolve_depth
         0, // include_depth
     )?;
-    parse_sv_pp(text, defines, allow_incomplete)
+    parse_sv_pp(text, defines, options)
+}
+
+/// A define configuration explored by `parse_sv_branches` and its parse result
+pub struct BranchConfiguration {
+    /// Macros defined (`true`) or left undefined (`false`) on top of `pre_defines`
+    pub defines: BTreeMap<String, bool>,
+    pub result: Result<SyntaxTree, Error>,
+}
+
+/// Parse every reachable `ifdef`/`ifndef` branch configuration.
+///
+/// Macro identifiers which control a conditional block and are neither given in
+/// `pre_defines` nor defined by the source itself are toggled one at a time,
+/// breadth-first, until all combinations are visited or `max_configurations`
+/// is reached.
+pub fn parse_sv_branches<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
+    path: T,
+    pre_defines: &Defines<V>,
+    include_paths: &[U],
+    options: &ParseOptions,
+    max_configurations: usize,
+) -> Vec<BranchConfiguration> {
+    explore_branches(pre_defines, options, max_configurations, |defines| {
+        preprocess(
+            path.as_ref(),
+            defines,
+            include_paths,
+            &options.preprocess_options(),
+        )
+    })
+}
+
+/// Parse every reachable `ifdef`/`ifndef` branch configuration of the source
+/// text `s`.
+///
+/// `path` is used for the origins of `s` like `parse_sv_str`. Configurations
+/// are explored as `parse_sv_branches`.
+pub fn parse_sv_str_branches<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
+    s: &str,
+    path: T,
+    pre_defines: &Defines<V>,
+    include_paths: &[U],
+    options: &ParseOptions,
+    max_configurations: usize,
+) -> Vec<BranchConfiguration> {
+    explore_branches(pre_defines, options, max_configurations, |defines| {
+        preprocess_str(
+            s,
+            path.as_ref(),
+            defines,
+            include_paths,
+            &options.preprocess_options(),
+            0, // resolve_depth
+            0, // include_depth
+        )
+    })
+}
+
+fn explore_branches<V: BuildHasher, F>(
+    pre_defines: &Defines<V>,
+    options: &ParseOptions,
+    max_configurations: usize,
+    mut pp: F,
+) -> Vec<BranchConfiguration>
+where
+    F: FnMut(&Defines) -> Result<(PreprocessedText, Defines), Error>,
+{
+    let mut ret = Vec::new();
+    // Configurations preprocessed, and configurations reported
+    let mut tried: HashSet<BTreeMap<String, bool>> = HashSet::new();
+    let mut visited: HashSet<BTreeMap<String, bool>> = HashSet::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(BTreeMap::new());
+
+    while let Some(mut config) = queue.pop_front() {
+        if ret.len() >= max_configurations {
+            break;
+        }
+        if !tried.insert(config.clone()) {
+            continue;
+        }
+
+        let mut defines: Defines = pre_defines
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        for (k, v) in &config {
+            if *v {
+                defines.insert(k.clone(), None);
+            } else {
+                defines.remove(k);
+            }
+        }
+
+        let result = match pp(&defines) {
+            Ok((text, new_defines)) => {
+                let identifiers: HashSet<&String> = text
+                    .conditionals()
+                    .iter()
+                    .flat_map(|x| &x.branches)
+                    .filter_map(|x| x.identifier.as_ref())
+                    .collect();
+                // Macros which no evaluated conditional refers to, e.g. in a
+                // skipped branch, don't make a different configuration.
+                config.retain(|k, _| identifiers.contains(k));
+                for id in identifiers {
+                    // Macros given by the caller are fixed, and macros
+                    // defined by the source (e.g. include guards) are
+                    // not free to choose.
+                    if pre_defines.contains_key(id)
+                        || config.contains_key(id)
+                        || new_defines.contains_key(id)
+                    {
+                        continue;
+                    }
+                    config.insert(id.clone(), false);
+                }
+                parse_sv_pp(text, new_defines, options).map(|(x, _)| x)
+            }
+            Err(x) => Err(x),
+        };
+
+        if !visited.insert(config.clone()) {
+            continue;
+        }
+        for (k, v) in &config {
+            let mut next = config.clone();
+            next.insert(k.clone(), !v);
+            queue.push_back(next);
+        }
+
+        ret.push(BranchConfiguration {
+            defines: config,
+            result,
+        });
+    }
+
+    ret
 }
 
 pub fn parse_lib<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
@@ -269,8 +640,10 @@ pub fn parse_lib<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
         path,
         pre_defines,
         include_paths,
-        false, // strip_comments
-        ignore_include,
+        &PreprocessOptions {
+            ignore_include,
+            ..PreprocessOptions::default()
+        },
     )?;
     parse_lib_pp(text, defines, allow_incomplete)
 }
@@ -288,8 +661,10 @@ pub fn parse_lib_str<T: AsRef<Path>, U: AsRef<Path>, V: BuildHasher>(
         path,
         pre_defines,
         include_paths,
-        ignore_include,
-        false, // strip_comments
+        &PreprocessOptions {
+            ignore_include,
+            ..PreprocessOptions::default()
+        },
         0, // resolve_depth
         0, // include_depth
     )?;
@@ -321,20 +696,18 @@ pub fn parse_lib_pp(
                 nom::Err::Error(e) => error_position(&e),
                 nom::Err::Failure(e) => error_position(&e),
             };
-            let origin = if let Some(pos) = pos {
-                if let Some(origin) = text.origin(pos) {
-                    Some((origin.0.clone(), origin.1))
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
-            Err(Error::Parse(origin))
+            Err(parse_error(&text, pos))
         }
     }
 }
 
+fn parse_error(text: &PreprocessedText, pos: Option<usize>) -> Error {
+    let origin = pos
+        .and_then(|x| text.origin(x))
+        .map(|(path, pos)| (path.clone(), pos));
+    Error::Parse(origin)
+}
+
 #[macro_export]
 macro_rules! unwrap_node {
     ($n:expr, $( $ty:tt ),+) => {{
@@ -372,11 +745,16 @@ mod test {
     use super::*;
     use std::collections::HashMap;
 
+    pub(crate) fn testfile_path(s: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("testcases").join(s)
+    }
+
     #[test]
     fn test() {
         let src = "/* comment */";
         let (syntax_tree, _) =
-            parse_sv_str(src, PathBuf::from(""), &HashMap::new(), &[""], false, false).unwrap();
+            parse_sv_str(src, PathBuf::from(""), &HashMap::new(), &[""], &ParseOptions::default())
+                .unwrap();
         let comment = unwrap_node!(&syntax_tree, Comment);
         assert!(comment.is_some());
     }
@@ -411,15 +789,260 @@ endmodule"##;
 
         let path = PathBuf::from("");
         let defines = HashMap::new();
-        let ret = parse_sv_str(src, &path, &defines, &[""], false, false);
+        let ret = parse_sv_str(src, &path, &defines, &[""], &ParseOptions::default());
         assert!(ret.is_ok());
-        let ret = parse_sv_str(src_broken, &path, &defines, &[""], false, false);
+        let ret = parse_sv_str(src_broken, &path, &defines, &[""], &ParseOptions::default());
         assert!(ret.is_err());
-        let ret = parse_sv_str(src, &path, &defines, &[""], false, false);
+        let ret = parse_sv_str(src, &path, &defines, &[""], &ParseOptions::default());
         assert!(ret.is_ok());
-        let ret = parse_sv_str(src_broken, &path, &defines, &[""], false, false);
+        let ret = parse_sv_str(src_broken, &path, &defines, &[""], &ParseOptions::default());
         assert!(ret.is_err());
-        let ret = parse_sv_str(src, &path, &defines, &[""], false, false);
+        let ret = parse_sv_str(src, &path, &defines, &[""], &ParseOptions::default());
         assert!(ret.is_ok());
     }
+
+    #[test]
+    fn test_allow_undefined() {
+        let src = r##"module A;
+    `DECLARE_REGS(a, b)
+    assign a = `DEFAULT_VALUE;
+    always_comb begin
+        `LOG("comb");
+    end
+endmodule"##;
+
+        let path = PathBuf::from("");
+        let defines = HashMap::new();
+        let ret = parse_sv_str(src, &path, &defines, &[""], &ParseOptions::default());
+        assert!(matches!(ret, Err(Error::DefineNotFound(ref x)) if x == "DECLARE_REGS"));
+
+        let options = ParseOptions {
+            allow_undefined: true,
+            ..ParseOptions::default()
+        };
+        let (syntax_tree, _) = parse_sv_str(src, &path, &defines, &[""], &options).unwrap();
+        assert_eq!(syntax_tree.get_warnings().len(), 3);
+        let placeholders = (&syntax_tree)
+            .into_iter()
+            .filter(|x| matches!(x, RefNode::TextMacroUsage(_)))
+            .count();
+        assert_eq!(placeholders, 3);
+    }
+
+    #[test]
+    fn test_branches() {
+        let src = r##"module A;
+`ifdef SYNTHESIS
+    wire a
+`else
+    wire b;
+`endif
+endmodule"##;
+
+        let path = PathBuf::from("");
+        let defines = HashMap::new();
+        let ret = parse_sv_str_branches(src, &path, &defines, &[""], &ParseOptions::default(), 16);
+        assert_eq!(ret.len(), 2);
+        assert_eq!(ret[0].defines.get("SYNTHESIS"), Some(&false));
+        assert!(ret[0].result.is_ok());
+        assert_eq!(ret[1].defines.get("SYNTHESIS"), Some(&true));
+        assert!(ret[1].result.is_err());
+
+        // B is evaluated only if A is defined
+        let src = r##"module A;
+`ifdef A
+`ifdef B
+    wire b;
+`endif
+`endif
+`ifndef A
+    wire c;
+`endif
+endmodule"##;
+        let ret = parse_sv_str_branches(src, &path, &defines, &[""], &ParseOptions::default(), 16);
+        let configs: Vec<_> = ret.iter().map(|x| x.defines.clone()).collect();
+        let config = |x: &[(&str, bool)]| -> BTreeMap<String, bool> {
+            x.iter().map(|(k, v)| (String::from(*k), *v)).collect()
+        };
+        assert_eq!(
+            configs,
+            [
+                config(&[("A", false)]),
+                config(&[("A", true), ("B", false)]),
+                config(&[("A", true), ("B", true)]),
+            ]
+        );
+        assert!(ret.iter().all(|x| x.result.is_ok()));
+    }
+
+    #[test]
+    fn test_protected_envelope() {
+        let src = r##"module A (input a, output b);
+`pragma protect begin_protected
+`pragma protect data_keyowner="Example", data_method="aes128-cbc"
+`pragma protect data_block
+//8AZ3N0dWZmIHRoYXQgY2Fubm90IGJlIHBhcnNlZA==
+`pragma protect end_protected
+endmodule"##;
+        let (syntax_tree, _) =
+            parse_sv_str(src, PathBuf::from(""), &HashMap::new(), &[""], &ParseOptions::default())
+                .unwrap();
+        let envelope = unwrap_node!(&syntax_tree, ProtectedEnvelope);
+        assert!(envelope.is_some());
+        let block = unwrap_node!(&syntax_tree, ProtectedBlock);
+        if let Some(RefNode::ProtectedBlock(x)) = block {
+            let text = syntax_tree.get_str(&x.nodes.0).unwrap();
+            assert_eq!(text, "//8AZ3N0dWZmIHRoYXQgY2Fubm90IGJlIHBhcnNlZA==\n");
+        } else {
+            unreachable!();
+        }
+        let ports = unwrap_node!(&syntax_tree, ListOfPortDeclarations);
+        assert!(ports.is_some());
+    }
+
+    #[test]
+    fn test_comment_pragmas() {
+        let src = r##"module A;
+    // synopsys translate_off
+    initial $display("simulation only");
+    // synopsys translate_on
+    always_comb begin
+        case (a) // synopsys full_case
+            1'b0: b = 0;
+            1'b1: b = 1;
+        endcase
+    end
+endmodule"##;
+        let (syntax_tree, _) =
+            parse_sv_str(src, PathBuf::from(""), &HashMap::new(), &[""], &ParseOptions::default())
+                .unwrap();
+        let pragmas = syntax_tree.get_comment_pragmas();
+        assert_eq!(pragmas.len(), 3);
+        assert_eq!(pragmas[0].pragma.kind, CommentPragmaKind::TranslateOff);
+        assert!(matches!(pragmas[0].next, Some(RefNode::NonPortModuleItem(_))));
+        assert_eq!(pragmas[1].pragma.kind, CommentPragmaKind::TranslateOn);
+        assert_eq!(pragmas[2].pragma.kind, CommentPragmaKind::FullCase);
+        assert!(matches!(
+            pragmas[2].enclosing,
+            Some(RefNode::CaseStatementNormal(_))
+        ));
+        assert!(matches!(pragmas[2].next, Some(RefNode::CaseItem(_))));
+
+        let options = ParseOptions {
+            strip_translate_off: true,
+            ..ParseOptions::default()
+        };
+        let (syntax_tree, _) =
+            parse_sv_str(src, PathBuf::from(""), &HashMap::new(), &[""], &options).unwrap();
+        let tasks = (&syntax_tree)
+            .into_iter()
+            .filter(|x| matches!(x, RefNode::SystemTfCall(_)))
+            .count();
+        assert_eq!(tasks, 0);
+        assert_eq!(syntax_tree.get_comment_pragmas().len(), 1);
+    }
+
+    #[test]
+    fn test_language_version() {
+        let verilog = r##"module A (input clk, input [7:0] d, output reg [7:0] q);
+    wire logic;
+    always @(posedge clk) q <= d;
+endmodule"##;
+
+        let system_verilog = r##"module A (input logic clk, input logic [7:0] d, output logic [7:0] q);
+    always_ff @(posedge clk) q <= d;
+endmodule"##;
+
+        let keywords = r##"`begin_keywords "1800-2017"
+module A (input logic clk, input logic [7:0] d, output logic [7:0] q);
+    always_ff @(posedge clk) q <= d;
+endmodule
+`end_keywords"##;
+
+        let path = PathBuf::from("");
+        let defines = HashMap::new();
+        let v2001 = ParseOptions {
+            language_version: Some(LanguageVersion::Ieee1364_2001),
+            ..ParseOptions::default()
+        };
+        let v2017 = ParseOptions {
+            language_version: Some(LanguageVersion::Ieee1800_2017),
+            ..ParseOptions::default()
+        };
+        let ret = parse_sv_str(verilog, &path, &defines, &[""], &v2001);
+        assert!(ret.is_ok());
+        let ret = parse_sv_str(verilog, &path, &defines, &[""], &v2017);
+        assert!(ret.is_err());
+        let ret = parse_sv_str(system_verilog, &path, &defines, &[""], &v2001);
+        assert!(ret.is_err());
+        let ret = parse_sv_str(system_verilog, &path, &defines, &[""], &v2017);
+        assert!(ret.is_ok());
+        let ret = parse_sv_str(system_verilog, &path, &defines, &[""], &ParseOptions::default());
+        assert!(ret.is_ok());
+        let ret = parse_sv_str(keywords, &path, &defines, &[""], &v2001);
+        assert!(ret.is_ok());
+    }
+
+    #[test]
+    fn test_verilog_ams() {
+        let src = r##"module A (input logic clk, inout p, inout n);
+    electrical p, n;
+    always_ff @(posedge clk) q <= d;
+    analog I(p, n) <+ V(p, n) / 1.5k;
+endmodule"##;
+
+        let path = PathBuf::from("");
+        let defines = HashMap::new();
+        let ams = ParseOptions {
+            verilog_ams: true,
+            ..ParseOptions::default()
+        };
+        let ams2017 = ParseOptions {
+            language_version: Some(LanguageVersion::Ieee1800_2017),
+            verilog_ams: true,
+            ..ParseOptions::default()
+        };
+        let ams2005 = ParseOptions {
+            language_version: Some(LanguageVersion::Ieee1364_2005),
+            verilog_ams: true,
+            ..ParseOptions::default()
+        };
+        let ret = parse_sv_str(src, &path, &defines, &[""], &ParseOptions::default());
+        assert!(ret.is_err());
+        let ret = parse_sv_str(src, &path, &defines, &[""], &ams);
+        assert!(ret.is_ok());
+        let ret = parse_sv_str(src, &path, &defines, &[""], &ams2017);
+        assert!(ret.is_ok());
+        let ret = parse_sv_str(src, &path, &defines, &[""], &ams2005);
+        assert!(ret.is_err());
+    }
+
+    #[test]
+    fn test_keyword_overrides() {
+        let src = r##"module A;
+    logic soft, restrict;
+    assign soft = restrict;
+    vendor_net x;
+endmodule"##;
+
+        let path = PathBuf::from("");
+        let defines = HashMap::new();
+        let overrides = ParseOptions {
+            keyword_overrides: Some(KeywordOverrides {
+                reserved: vec![String::from("vendor_net")],
+                unreserved: vec![String::from("soft"), String::from("restrict")],
+            }),
+            ..ParseOptions::default()
+        };
+        let ret = parse_sv_str(src, &path, &defines, &[""], &ParseOptions::default());
+        assert!(ret.is_err());
+        let ret = parse_sv_str(src, &path, &defines, &[""], &overrides);
+        assert!(ret.is_err());
+
+        let src = src.replace("    vendor_net x;\n", "");
+        let ret = parse_sv_str(&src, &path, &defines, &[""], &overrides);
+        assert!(ret.is_ok());
+        let ret = parse_sv_str(&src, &path, &defines, &[""], &ParseOptions::default());
+        assert!(ret.is_err());
+    }
 }
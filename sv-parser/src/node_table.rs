@@ -0,0 +1,150 @@
+use crate::*;
+use std::collections::HashMap;
+
+/// Nodes of a syntax tree numbered in pre-order
+///
+/// The root node is `0`, and the descendants of a node are numbered
+/// contiguously after it. Bindings to other languages refer to nodes by these
+/// numbers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeTable {
+    /// Kinds of nodes, which are the names of `RefNode` variants
+    pub kinds: Vec<String>,
+    pub nodes: Vec<NodeEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeEntry {
+    /// Index of `NodeTable::kinds`
+    pub kind: usize,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// Last descendant, or the node itself if it has no children
+    pub last: usize,
+    /// Preprocessed text of the node, with and without trailing white space.
+    /// `None` if the node has no token.
+    pub locate: Option<Locate>,
+    pub locate_trim: Option<Locate>,
+}
+
+impl NodeTable {
+    pub fn new(tree: &SyntaxTree) -> Self {
+        let mut kinds: Vec<String> = Vec::new();
+        let mut kind_ids: HashMap<String, usize> = HashMap::new();
+        let mut nodes: Vec<NodeEntry> = Vec::new();
+        let mut stack: Vec<usize> = Vec::new();
+
+        for event in tree.into_iter().event() {
+            match event {
+                NodeEvent::Enter(x) => {
+                    let name = format!("{}", x);
+                    let kind = match kind_ids.get(&name) {
+                        Some(x) => *x,
+                        None => {
+                            kinds.push(name.clone());
+                            kind_ids.insert(name, kinds.len() - 1);
+                            kinds.len() - 1
+                        }
+                    };
+                    let locate = match x {
+                        RefNode::Locate(x) => Some(*x),
+                        _ => None,
+                    };
+                    let id = nodes.len();
+                    let parent = stack.last().copied();
+                    if let Some(parent) = parent {
+                        nodes[parent].children.push(id);
+                    }
+                    stack.push(id);
+                    nodes.push(NodeEntry {
+                        kind,
+                        parent,
+                        children: Vec::new(),
+                        last: id,
+                        locate,
+                        locate_trim: locate,
+                    });
+                }
+                NodeEvent::Leave(x) => {
+                    let id = stack.pop().unwrap();
+                    nodes[id].last = nodes.len() - 1;
+                    if let RefNode::WhiteSpace(_) = x {
+                        nodes[id].locate_trim = None;
+                    }
+                    if let Some(parent) = stack.last() {
+                        let (locate, locate_trim) = (nodes[id].locate, nodes[id].locate_trim);
+                        let parent = &mut nodes[*parent];
+                        parent.locate = merge(parent.locate, locate);
+                        parent.locate_trim = merge(parent.locate_trim, locate_trim);
+                    }
+                }
+            }
+        }
+
+        NodeTable { kinds, nodes }
+    }
+}
+
+// Extend `x` to the end of the following `y`
+fn merge(x: Option<Locate>, y: Option<Locate>) -> Option<Locate> {
+    match (x, y) {
+        (Some(x), Some(y)) => Some(Locate {
+            offset: x.offset,
+            line: x.line,
+            len: y.offset + y.len - x.offset,
+        }),
+        (x, None) => x,
+        (None, y) => y,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_node_table() {
+        let src = "module A;\n  wire b;\nendmodule\n";
+        let (syntax_tree, _) = parse_sv_str(
+            src,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let table = NodeTable::new(&syntax_tree);
+        let root = &table.nodes[0];
+        assert_eq!(table.kinds[root.kind], "SourceText");
+        assert_eq!(root.parent, None);
+        assert_eq!(root.last, table.nodes.len() - 1);
+        assert_eq!(syntax_tree.get_str(&root.locate.unwrap()), Some(src));
+
+        let kind = table
+            .kinds
+            .iter()
+            .position(|x| x == "NetDeclaration")
+            .unwrap();
+        let (id, item) = table
+            .nodes
+            .iter()
+            .enumerate()
+            .find(|(_, x)| x.kind == kind)
+            .unwrap();
+        assert_eq!(
+            syntax_tree.get_str(&item.locate.unwrap()),
+            Some("wire b;\n")
+        );
+        assert_eq!(
+            syntax_tree.get_str(&item.locate_trim.unwrap()),
+            Some("wire b;")
+        );
+        for x in &item.children {
+            assert_eq!(table.nodes[*x].parent, Some(id));
+        }
+        assert!(table.nodes[id + 1..=item.last]
+            .iter()
+            .all(|x| x.parent.unwrap() >= id));
+    }
+}
@@ -0,0 +1,742 @@
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Options of `pickle`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PickleOptions {
+    /// Replace text macro usages by their expansions
+    pub expand_macros: bool,
+    /// Replace `include directives by the included files
+    pub inline_includes: bool,
+    /// Added to the names of design units
+    pub prefix: String,
+    pub suffix: String,
+    /// Emit `line directives mapping the text to the original files
+    pub line_directive: bool,
+}
+
+/// Error of `pickle`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PickleError {
+    /// A top design unit isn't defined
+    TopNotFound(String),
+    /// A design unit to be renamed is referred from a text macro usage or an
+    /// included file which is emitted as is, with the location of the
+    /// reference
+    NotRenamed(String, Option<(PathBuf, usize)>),
+}
+
+impl fmt::Display for PickleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PickleError::TopNotFound(x) => write!(f, "Top design unit not found: {}", x),
+            PickleError::NotRenamed(x, origin) => write!(
+                f,
+                "Reference to {} in unexpanded macro or include can't be renamed: {:?}",
+                x, origin
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PickleError {}
+
+/// Merge the design units of `trees` into a single source text.
+///
+/// The units reachable from `tops` through instantiations, interface ports,
+/// `bind` and package references are emitted in dependency order, or all
+/// units if `tops` is empty. Text outside of design units in a file, such as
+/// compiler directives and compilation-unit declarations, is emitted once
+/// before the first unit of the file. If a name is defined more than once,
+/// the first definition is used.
+///
+/// With `options.inline_includes`, the preprocessed text is emitted, in
+/// which `ifdef blocks are resolved. Otherwise the text of the original files
+/// is emitted; units produced by included files remain in the text around
+/// the units in that case. With `options.expand_macros`, text macro usages
+/// are replaced by their expansions; units produced by macros remain in the
+/// text around the units otherwise.
+///
+/// The names of modules, interfaces, programs, packages, primitives and
+/// checkers defined in `trees` are renamed to `prefix` + name + `suffix`
+/// with all references to them. `PickleError::NotRenamed` is returned if a
+/// reference is in a macro usage or an included file emitted as is.
+pub fn pickle(
+    trees: &[SyntaxTree],
+    tops: &[&str],
+    options: &PickleOptions,
+) -> Result<String, PickleError> {
+    let mut files = Files::default();
+    let sources: Vec<_> = trees.iter().map(|x| scan(x, options, &mut files)).collect();
+
+    let mut units: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (i, x) in sources.iter().enumerate() {
+        for (j, y) in x.units.iter().enumerate() {
+            units.entry(&y.name).or_insert((i, j));
+        }
+    }
+    let renames: HashMap<&str, String> = if options.prefix.is_empty() && options.suffix.is_empty() {
+        HashMap::new()
+    } else {
+        units
+            .keys()
+            .map(|x| (*x, rename(x, &options.prefix, &options.suffix)))
+            .collect()
+    };
+
+    let mut order = Order {
+        sources: &sources,
+        units: &units,
+        visited: HashSet::new(),
+        ret: Vec::new(),
+    };
+    if tops.is_empty() {
+        for (i, x) in sources.iter().enumerate() {
+            order.visit(i, None);
+            for (j, y) in x.units.iter().enumerate() {
+                if units.get(y.name.as_str()) == Some(&(i, j)) {
+                    order.visit(i, Some(j));
+                }
+            }
+        }
+    } else {
+        for x in tops {
+            let (i, j) = units
+                .get(x)
+                .ok_or_else(|| PickleError::TopNotFound(String::from(*x)))?;
+            order.visit(*i, Some(*j));
+        }
+    }
+
+    for (i, x) in sources.iter().enumerate() {
+        for y in &x.unplaced {
+            if !renames.contains_key(y.name) {
+                continue;
+            }
+            let j = y
+                .unit
+                .as_ref()
+                .and_then(|z| x.units.iter().position(|w| &w.name == z));
+            if order.visited.contains(&(i, j)) {
+                return Err(PickleError::NotRenamed(
+                    String::from(y.name),
+                    y.origin.clone(),
+                ));
+            }
+        }
+    }
+
+    let mut writer = Writer {
+        text: String::new(),
+        next: None,
+        line_directive: options.line_directive,
+    };
+    for (i, j) in order.ret {
+        let x = &sources[i];
+        match j {
+            Some(j) => {
+                let (begin, end) = x.units[j].range;
+                writer.write(x, begin, end, &renames, &mut files);
+            }
+            None => {
+                for &(begin, end) in &x.gaps() {
+                    writer.write(x, begin, end, &renames, &mut files);
+                }
+            }
+        }
+    }
+    if !writer.text.is_empty() && !writer.text.ends_with('\n') {
+        writer.text.push('\n');
+    }
+    Ok(writer.text)
+}
+
+// Text of a syntax tree which design units are cut out of
+struct Source<'a> {
+    tree: &'a SyntaxTree,
+    // The original file if includes are not inlined
+    file: Option<PathBuf>,
+    text: String,
+    // Parts of `text` in order, copied from the original files or the
+    // preprocessed text
+    segments: Vec<Segment>,
+    // Offsets, positions in `text` and lengths of the parts copied from
+    // `file` and the preprocessed text
+    file_parts: Vec<(usize, usize, usize)>,
+    pp_parts: Vec<(usize, usize, usize)>,
+    units: Vec<Unit>,
+    // Dependencies of the text outside of design units
+    deps: Vec<String>,
+    // Identifiers of design units with the position in `text`
+    identifiers: Vec<(usize, &'a str)>,
+    // Identifiers of design units which are not in `text`
+    unplaced: Vec<Unplaced<'a>>,
+}
+
+struct Unplaced<'a> {
+    // The enclosing design unit
+    unit: Option<String>,
+    name: &'a str,
+    origin: Option<(PathBuf, usize)>,
+}
+
+struct Segment {
+    begin: usize,
+    copied: Copied,
+}
+
+enum Copied {
+    // Original file and the offset
+    File(PathBuf, usize),
+    // Offset in the preprocessed text
+    Preprocessed(usize),
+}
+
+struct Unit {
+    name: String,
+    range: (usize, usize),
+    deps: Vec<String>,
+}
+
+impl<'a> Source<'a> {
+    // Build `text` from the original file or the preprocessed text
+    fn copy(&mut self, options: &PickleOptions, files: &mut Files) {
+        let tree = self.tree;
+        let usages: HashMap<(&Path, usize), usize> = tree
+            .get_macro_usages()
+            .iter()
+            .map(|x| ((x.path.as_path(), x.range.begin), x.range.end))
+            .collect();
+        let mut includes = Vec::new();
+        let mut macros = Vec::new();
+        for (range, x) in tree.text.expansion_ranges() {
+            match x {
+                Expansion::Include(_, _) => includes.push(range),
+                Expansion::Macro(_, (path, begin)) => {
+                    if let Some(end) = usages.get(&(path.as_path(), *begin)) {
+                        macros.push((range, path, *begin, *end));
+                    }
+                }
+            }
+        }
+        macros.sort_by_key(|x| x.0.begin);
+
+        // The original file is the origin of the first token written in it
+        let file = if options.inline_includes {
+            None
+        } else {
+            tree.into_iter()
+                .filter_map(|x| match x {
+                    RefNode::Locate(x) => Some(x),
+                    _ => None,
+                })
+                .find(|x| tree.get_expansions(x).is_empty())
+                .and_then(|x| tree.get_origin(x))
+                .map(|(path, _)| path.clone())
+                .filter(|x| files.get(x).is_some())
+        };
+
+        let mut pos = 0;
+        match file {
+            Some(file) => {
+                let text = &files.get(&file).unwrap().0;
+                if options.expand_macros {
+                    // Usages written in the file, not in included files
+                    for (range, path, begin, end) in macros {
+                        let included = includes.iter().any(|x| {
+                            x.begin < x.end && x.begin <= range.begin && range.end <= x.end
+                        });
+                        if *path != file || included || begin < pos {
+                            continue;
+                        }
+                        self.copy_file(&file, text, pos, begin, true);
+                        self.copy_preprocessed(range.begin, range.end);
+                        pos = end;
+                    }
+                }
+                self.copy_file(&file, text, pos, text.len(), true);
+                self.file = Some(file);
+            }
+            None => {
+                if !options.expand_macros {
+                    for (range, path, begin, end) in macros {
+                        let text = match files.get(path) {
+                            Some(x) if range.begin >= pos => &x.0,
+                            _ => continue,
+                        };
+                        self.copy_preprocessed(pos, range.begin);
+                        self.copy_file(path, text, begin, end, false);
+                        pos = range.end;
+                    }
+                }
+                self.copy_preprocessed(pos, tree.text.text().len());
+            }
+        }
+    }
+
+    fn copy_file(&mut self, path: &Path, text: &str, begin: usize, end: usize, base: bool) {
+        if begin >= end {
+            return;
+        }
+        if base {
+            self.file_parts.push((begin, self.text.len(), end - begin));
+        }
+        self.segments.push(Segment {
+            begin: self.text.len(),
+            copied: Copied::File(path.to_path_buf(), begin),
+        });
+        self.text.push_str(&text[begin..end]);
+    }
+
+    fn copy_preprocessed(&mut self, begin: usize, end: usize) {
+        if begin >= end {
+            return;
+        }
+        self.pp_parts.push((begin, self.text.len(), end - begin));
+        self.segments.push(Segment {
+            begin: self.text.len(),
+            copied: Copied::Preprocessed(begin),
+        });
+        self.text.push_str(&self.tree.text.text()[begin..end]);
+    }
+
+    // Position of a token in `text`
+    fn position(&self, x: &Locate) -> Option<usize> {
+        match self.file {
+            Some(ref file) if self.tree.get_expansions(x).is_empty() => {
+                let (path, pos) = self.tree.get_origin(x)?;
+                if path != file {
+                    return None;
+                }
+                locate(&self.file_parts, pos, x.len)
+            }
+            _ => locate(&self.pp_parts, x.offset, x.len),
+        }
+    }
+
+    // Ranges of `text` outside of design units
+    fn gaps(&self) -> Vec<(usize, usize)> {
+        let mut ranges: Vec<_> = self.units.iter().map(|x| x.range).collect();
+        ranges.sort_unstable();
+        let mut ret = Vec::new();
+        let mut pos = 0;
+        for (begin, end) in ranges {
+            ret.push((pos, begin));
+            pos = end;
+        }
+        ret.push((pos, self.text.len()));
+        ret
+    }
+
+    // File and line where `pos` of `text` came from
+    fn origin(&self, pos: usize, files: &mut Files) -> Option<(PathBuf, usize)> {
+        let i = self
+            .segments
+            .partition_point(|x| x.begin <= pos)
+            .checked_sub(1)?;
+        let x = &self.segments[i];
+        let (path, pos) = match x.copied {
+            Copied::File(ref path, begin) => (path, begin + pos - x.begin),
+            Copied::Preprocessed(begin) => self.tree.text.origin(begin + pos - x.begin)?,
+        };
+        files.line(path, pos).map(|x| (path.clone(), x))
+    }
+}
+
+// Position in the text of `len` bytes at `pos` of the copied text
+fn locate(parts: &[(usize, usize, usize)], pos: usize, len: usize) -> Option<usize> {
+    let i = parts.partition_point(|x| x.0 <= pos).checked_sub(1)?;
+    let (from, begin, n) = parts[i];
+    if pos + len <= from + n {
+        Some(begin + pos - from)
+    } else {
+        None
+    }
+}
+
+fn scan<'a>(tree: &'a SyntaxTree, options: &PickleOptions, files: &mut Files) -> Source<'a> {
+    let mut ret = Source {
+        tree,
+        file: None,
+        text: String::new(),
+        segments: Vec::new(),
+        file_parts: Vec::new(),
+        pp_parts: Vec::new(),
+        units: Vec::new(),
+        deps: Vec::new(),
+        identifiers: Vec::new(),
+        unplaced: Vec::new(),
+    };
+    ret.copy(options, files);
+
+    // The outermost design element and its dependencies
+    let mut unit: Option<(String, Vec<String>)> = None;
+    // The first and last tokens of the design element
+    let mut first = None;
+    let mut last = None;
+    let mut depth = 0;
+    let mut whitespace = 0;
+    for event in tree.into_iter().event() {
+        match event {
+            NodeEvent::Enter(RefNode::WhiteSpace(_)) => whitespace += 1,
+            NodeEvent::Leave(RefNode::WhiteSpace(_)) => whitespace -= 1,
+            NodeEvent::Enter(x) => {
+                if is_design_element(&x) {
+                    if depth == 0 {
+                        let name = unwrap_node!(
+                            x.clone(),
+                            ModuleIdentifier,
+                            InterfaceIdentifier,
+                            ProgramIdentifier,
+                            PackageIdentifier,
+                            UdpIdentifier,
+                            CheckerIdentifier
+                        )
+                        .and_then(|y| unwrap_locate!(y))
+                        .and_then(|y| tree.get_str(y))
+                        .unwrap_or("");
+                        unit = Some((String::from(name), Vec::new()));
+                        first = None;
+                    }
+                    depth += 1;
+                }
+                match x {
+                    RefNode::Locate(y) if whitespace == 0 && unit.is_some() => {
+                        first.get_or_insert(y);
+                        last = Some(y);
+                    }
+                    RefNode::ModuleIdentifier(_)
+                    | RefNode::InterfaceIdentifier(_)
+                    | RefNode::ProgramIdentifier(_)
+                    | RefNode::PackageIdentifier(_)
+                    | RefNode::UdpIdentifier(_)
+                    | RefNode::CheckerIdentifier(_) => {
+                        let y = match unwrap_locate!(x) {
+                            Some(y) => y,
+                            None => continue,
+                        };
+                        let name = match tree.get_str(y) {
+                            Some(z) => z,
+                            None => continue,
+                        };
+                        match ret.position(y) {
+                            Some(pos) => ret.identifiers.push((pos, name)),
+                            None => ret.unplaced.push(Unplaced {
+                                unit: unit.as_ref().map(|z| z.0.clone()),
+                                name,
+                                origin: tree.get_origin(y).map(|(path, pos)| (path.clone(), pos)),
+                            }),
+                        }
+                        let deps = match unit {
+                            Some((ref z, _)) if z == name => continue,
+                            Some((_, ref mut z)) => z,
+                            None => &mut ret.deps,
+                        };
+                        if !deps.iter().any(|z| z == name) {
+                            deps.push(String::from(name));
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            NodeEvent::Leave(x) => {
+                if is_design_element(&x) {
+                    depth -= 1;
+                    if depth != 0 {
+                        continue;
+                    }
+                    let (name, deps) = unit.take().unwrap();
+                    let begin = first.and_then(|y| ret.position(y));
+                    let end = last.and_then(|y| ret.position(y).map(|z| z + y.len));
+                    match (begin, end, matches!(x, RefNode::ConfigDeclaration(_))) {
+                        (Some(begin), Some(end), false) if begin < end => {
+                            ret.units.push(Unit {
+                                name,
+                                range: (begin, end),
+                                deps,
+                            });
+                        }
+                        // Left in the text outside of design units
+                        _ => {
+                            for y in deps {
+                                if !ret.deps.contains(&y) {
+                                    ret.deps.push(y);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    ret.identifiers.sort_unstable();
+    ret
+}
+
+fn is_design_element(x: &RefNode) -> bool {
+    match x {
+        RefNode::ModuleDeclaration(x) => !matches!(
+            x,
+            ModuleDeclaration::ExternNonansi(_) | ModuleDeclaration::ExternAnsi(_)
+        ),
+        RefNode::InterfaceDeclaration(x) => !matches!(
+            x,
+            InterfaceDeclaration::ExternNonansi(_) | InterfaceDeclaration::ExternAnsi(_)
+        ),
+        RefNode::ProgramDeclaration(x) => !matches!(
+            x,
+            ProgramDeclaration::ExternNonansi(_) | ProgramDeclaration::ExternAnsi(_)
+        ),
+        RefNode::PackageDeclaration(_)
+        | RefNode::UdpDeclaration(_)
+        | RefNode::ConfigDeclaration(_)
+        | RefNode::CheckerDeclaration(_) => true,
+        _ => false,
+    }
+}
+
+// An escaped identifier keeps the leading backslash
+fn rename(name: &str, prefix: &str, suffix: &str) -> String {
+    match name.strip_prefix('\\') {
+        Some(x) => format!("\\{}{}{}", prefix, x, suffix),
+        None => format!("{}{}{}", prefix, name, suffix),
+    }
+}
+
+// Depth-first order of units and texts outside of units after what they
+// depend on
+struct Order<'a> {
+    sources: &'a [Source<'a>],
+    units: &'a HashMap<&'a str, (usize, usize)>,
+    visited: HashSet<(usize, Option<usize>)>,
+    ret: Vec<(usize, Option<usize>)>,
+}
+
+impl<'a> Order<'a> {
+    fn visit(&mut self, source: usize, unit: Option<usize>) {
+        if !self.visited.insert((source, unit)) {
+            return;
+        }
+        // Directives of the file may be required by the unit
+        if unit.is_some() {
+            self.visit(source, None);
+        }
+        let x = &self.sources[source];
+        let deps = match unit {
+            Some(i) => &x.units[i].deps,
+            None => &x.deps,
+        };
+        for y in deps {
+            if let Some(&(i, j)) = self.units.get(y.as_str()) {
+                self.visit(i, Some(j));
+            }
+        }
+        self.ret.push((source, unit));
+    }
+}
+
+struct Writer {
+    text: String,
+    // Origin expected for the next line without `line directive
+    next: Option<(PathBuf, usize)>,
+    line_directive: bool,
+}
+
+impl Writer {
+    fn write(
+        &mut self,
+        source: &Source,
+        begin: usize,
+        end: usize,
+        renames: &HashMap<&str, String>,
+        files: &mut Files,
+    ) {
+        // Blank lines at the beginning are dropped
+        let text = &source.text[begin..end];
+        let blank = text.len() - text.trim_start().len();
+        if blank == text.len() {
+            return;
+        }
+        let begin = match text[..blank].rfind('\n') {
+            Some(x) => begin + x + 1,
+            None => begin,
+        };
+        if !self.text.is_empty() && !self.text.ends_with('\n') {
+            self.text.push('\n');
+            self.next = None;
+        }
+
+        let start = source.identifiers.partition_point(|x| x.0 < begin);
+        let mut identifiers = source.identifiers[start..].iter().peekable();
+        let mut pos = begin;
+        for line in source.text[begin..end].split_inclusive('\n') {
+            let origin = source.origin(pos, files);
+            if self.line_directive {
+                // IEEE1800-2017 Clause 22.12
+                if let Some((ref path, line)) = origin {
+                    if self.next.as_ref() != Some(&(path.clone(), line)) {
+                        self.text
+                            .push_str(&format!("`line {} \"{}\" 0\n", line, path.display()));
+                    }
+                }
+            }
+            let line_end = pos + line.len();
+            let mut copied = pos;
+            while let Some(&&(x, name)) = identifiers.peek() {
+                if x >= line_end {
+                    break;
+                }
+                identifiers.next();
+                if let Some(y) = renames.get(name) {
+                    self.text.push_str(&source.text[copied..x]);
+                    self.text.push_str(y);
+                    copied = x + name.len();
+                }
+            }
+            self.text.push_str(&source.text[copied..line_end]);
+            self.next = origin.map(|(path, line)| (path, line + 1));
+            pos = line_end;
+        }
+    }
+}
+
+// Original files with the beginning positions of the lines
+#[derive(Default)]
+struct Files {
+    texts: HashMap<PathBuf, Option<(String, Vec<usize>)>>,
+}
+
+impl Files {
+    fn get(&mut self, path: &Path) -> Option<&(String, Vec<usize>)> {
+        self.texts
+            .entry(path.to_path_buf())
+            .or_insert_with(|| {
+                let text = fs::read_to_string(path).ok()?;
+                let mut lines = vec![0];
+                lines.extend(text.match_indices('\n').map(|(x, _)| x + 1));
+                Some((text, lines))
+            })
+            .as_ref()
+    }
+
+    fn line(&mut self, path: &Path, pos: usize) -> Option<usize> {
+        let (_, lines) = self.get(path)?;
+        Some(lines.partition_point(|x| *x <= pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::testfile_path;
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_pickle() {
+        let dir = testfile_path("pickle");
+        let mut trees = Vec::new();
+        let mut defines = HashMap::new();
+        for x in &["top.sv", "pkg.sv"] {
+            let (tree, new_defines) =
+                parse_sv(dir.join(x), &defines, &[&dir], &ParseOptions::default()).unwrap();
+            trees.push(tree);
+            defines = new_defines;
+        }
+
+        // `sub` in the definition of `SUB is emitted as is without expanding macros
+        let options = PickleOptions {
+            suffix: String::from("_v2"),
+            line_directive: true,
+            ..PickleOptions::default()
+        };
+        let err = pickle(&trees, &["top"], &options).unwrap_err();
+        let top = dir.join("top.sv");
+        assert!(
+            matches!(err, PickleError::NotRenamed(ref x, Some((ref y, _))) if x == "sub" && y == &top)
+        );
+
+        let options = PickleOptions {
+            expand_macros: true,
+            ..options
+        };
+        let text = pickle(&trees, &["top"], &options).unwrap();
+        assert_eq!(
+            text,
+            format!(
+                r##"`line 1 "{0}" 0
+`include "defs.svh"
+`define SUB(name) sub name ()
+`line 1 "{1}" 0
+package pkg_v2;
+  typedef logic [8-1:0] t;
+endpackage
+`line 10 "{0}" 0
+module sub_v2 (output pkg_v2::t q);
+endmodule
+`line 3 "{0}" 0
+module top_v2;
+  import pkg_v2::*;
+  sub_v2 u0 ();
+  sub_v2 u1 ();
+endmodule : top_v2
+"##,
+                top.display(),
+                dir.join("pkg.sv").display()
+            )
+        );
+
+        let options = PickleOptions {
+            inline_includes: true,
+            ..PickleOptions::default()
+        };
+        let text = pickle(&trees, &["top"], &options).unwrap();
+        assert_eq!(
+            text,
+            r##"`define W 8
+
+`define SUB(name) sub name ()
+package pkg;
+  typedef logic [`W-1:0] t;
+endpackage
+module sub (output pkg::t q);
+endmodule
+module top;
+  import pkg::*;
+  `SUB(u0);
+  sub u1 ();
+endmodule : top
+"##
+        );
+
+        let options = PickleOptions {
+            expand_macros: true,
+            inline_includes: true,
+            prefix: String::from("p_"),
+            ..PickleOptions::default()
+        };
+        let text = pickle(&trees, &["top"], &options).unwrap();
+        assert!(text.contains("module p_top;\n  import p_pkg::*;\n  p_sub u0 ();\n  p_sub u1 ();\nendmodule : p_top\n"));
+        assert!(!text.contains("unused"));
+        let (tree, _) = parse_sv_str(
+            &text,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let names: Vec<_> = tree
+            .get_design_unit_summaries()
+            .into_iter()
+            .map(|x| x.name)
+            .collect();
+        assert_eq!(names, ["p_sub", "p_top"]);
+        assert!(text.find("package p_pkg;").unwrap() < text.find("module p_sub").unwrap());
+
+        let err = pickle(&trees, &["none"], &options).unwrap_err();
+        assert_eq!(err, PickleError::TopNotFound(String::from("none")));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{
+    Locate, Pragma, PragmaExpression, PragmaValue, ProtectedEnvelope, ProtectedEnvelopeItem,
+    RefNode, SyntaxTree,
+};
+use std::convert::TryInto;
+use std::mem;
+
+/// `encoding` of protected blocks, IEEE1800-2017 Clause 34.5.9
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProtectEncoding {
+    /// `uuencode`, `base64`, `quoted-printable` or `raw`
+    pub enctype: Option<String>,
+    pub line_length: Option<usize>,
+    /// Size of the block before encoding
+    pub bytes: Option<usize>,
+}
+
+/// A `key_block` with the key keywords preceding it
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProtectKeyBlock {
+    pub key_keyowner: Option<String>,
+    pub key_method: Option<String>,
+    pub key_keyname: Option<String>,
+    pub encoding: Option<ProtectEncoding>,
+    /// Encoded text of the block
+    pub block: String,
+}
+
+/// Keywords of a `` `pragma protect begin_protected `` envelope
+#[derive(Clone, Debug)]
+pub struct Protection<'a> {
+    pub envelope: &'a ProtectedEnvelope,
+    /// Encoding of the data block
+    pub encoding: Option<ProtectEncoding>,
+    pub data_keyowner: Option<String>,
+    pub data_method: Option<String>,
+    pub key_blocks: Vec<ProtectKeyBlock>,
+    /// Encoded text of the data block
+    pub data_block: Option<String>,
+    /// Range of the envelope from `begin_protected` to `end_protected`
+    pub locate: Locate,
+}
+
+enum Token<'a> {
+    Keyword(&'a str, Option<&'a PragmaValue>),
+    Block(&'a Locate),
+}
+
+enum Block {
+    Key,
+    Data,
+}
+
+impl SyntaxTree {
+    /// Get keywords and encoded blocks of protect envelopes
+    pub fn get_protections(&self) -> Vec<Protection<'_>> {
+        let mut ret = Vec::new();
+        for node in self {
+            if let RefNode::ProtectedEnvelope(x) = node {
+                ret.push(self.protection(x));
+            }
+        }
+        ret
+    }
+
+    fn protection<'a>(&self, envelope: &'a ProtectedEnvelope) -> Protection<'a> {
+        let mut ret = Protection {
+            envelope,
+            encoding: None,
+            data_keyowner: None,
+            data_method: None,
+            key_blocks: Vec::new(),
+            data_block: None,
+            locate: envelope.try_into().unwrap(),
+        };
+
+        // IEEE1800-2017 Clause 34
+        // Keywords apply to the following blocks until they are redefined.
+        let mut encoding = None;
+        let mut key = ProtectKeyBlock::default();
+        let mut block = None;
+        let (ref begin, ref items, _) = envelope.nodes;
+        let mut tokens: Vec<_> = self.protect_expressions(begin);
+        for item in items {
+            match item {
+                ProtectedEnvelopeItem::Pragma(x) => tokens.extend(self.protect_expressions(x)),
+                ProtectedEnvelopeItem::ProtectedBlock(x) => tokens.push(Token::Block(&x.nodes.0)),
+            }
+        }
+        for token in tokens {
+            match token {
+                Token::Keyword("encoding", Some(PragmaValue::Paren(x))) => {
+                    encoding = Some(self.encoding(&x.nodes.0.nodes.1.contents()));
+                }
+                Token::Keyword("data_keyowner", Some(x)) => ret.data_keyowner = Some(self.value(x)),
+                Token::Keyword("data_method", Some(x)) => ret.data_method = Some(self.value(x)),
+                Token::Keyword("key_keyowner", Some(x)) => key.key_keyowner = Some(self.value(x)),
+                Token::Keyword("key_method", Some(x)) => key.key_method = Some(self.value(x)),
+                Token::Keyword("key_keyname", Some(x)) => key.key_keyname = Some(self.value(x)),
+                Token::Keyword("key_block", None) => block = Some(Block::Key),
+                Token::Keyword("data_block", None) => block = Some(Block::Data),
+                Token::Keyword(_, _) => (),
+                Token::Block(x) => {
+                    let text = String::from(self.get_str(x).unwrap());
+                    match block.take() {
+                        Some(Block::Key) => ret.key_blocks.push(ProtectKeyBlock {
+                            encoding: encoding.clone(),
+                            block: text,
+                            ..mem::take(&mut key)
+                        }),
+                        Some(Block::Data) => {
+                            ret.encoding = encoding.clone();
+                            ret.data_block = Some(text);
+                        }
+                        None => (),
+                    }
+                }
+            }
+        }
+        if ret.data_block.is_none() {
+            ret.encoding = encoding;
+        }
+        ret
+    }
+
+    /// Keywords and values of `` `pragma protect ``
+    fn protect_expressions<'a>(&'a self, pragma: &'a Pragma) -> Vec<Token<'a>> {
+        let (_, _, ref name, ref expressions) = pragma.nodes;
+        if self.get_str(&name.nodes.0.nodes.0) != Some("protect") {
+            return Vec::new();
+        }
+        expressions
+            .iter()
+            .flat_map(|x| x.contents())
+            .filter_map(|x| self.expression(x))
+            .map(|(k, v)| Token::Keyword(k, v))
+            .collect()
+    }
+
+    fn expression<'a>(
+        &'a self,
+        x: &'a PragmaExpression,
+    ) -> Option<(&'a str, Option<&'a PragmaValue>)> {
+        match x {
+            PragmaExpression::PragmaKeyword(x) => Some((self.get_str(&x.nodes.0.nodes.0)?, None)),
+            PragmaExpression::Assignment(x) => {
+                let (ref k, _, ref v) = x.nodes;
+                Some((self.get_str(&k.nodes.0.nodes.0)?, Some(v)))
+            }
+            PragmaExpression::PragmaValue(_) => None,
+        }
+    }
+
+    fn encoding(&self, expressions: &[&PragmaExpression]) -> ProtectEncoding {
+        let mut ret = ProtectEncoding::default();
+        for (name, value) in expressions.iter().filter_map(|x| self.expression(x)) {
+            let value = match value {
+                Some(x) => self.value(x),
+                None => continue,
+            };
+            match name {
+                "enctype" => ret.enctype = Some(value),
+                "line_length" => ret.line_length = value.parse().ok(),
+                "bytes" => ret.bytes = value.parse().ok(),
+                _ => (),
+            }
+        }
+        ret
+    }
+
+    /// Text of a value where quotes of string literals are removed
+    fn value(&self, x: &PragmaValue) -> String {
+        let text = self.get_str_trim(x).unwrap_or("");
+        let text = match x {
+            PragmaValue::StringLiteral(_) => text
+                .strip_prefix('"')
+                .and_then(|x| x.strip_suffix('"'))
+                .unwrap_or(text),
+            _ => text,
+        };
+        String::from(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_protections() {
+        let src = r##"module A (input a, output b);
+`pragma protect begin_protected
+`pragma protect encrypt_agent="Example Agent"
+`pragma protect key_keyowner="Vendor A", key_method="rsa", key_keyname="KEY-A"
+`pragma protect encoding=(enctype="base64", line_length=76, bytes=256)
+`pragma protect key_block
+a2V5IGZvciB2ZW5kb3IgYQ==
+`pragma protect key_keyowner="Vendor B", key_method="rsa"
+`pragma protect key_block
+a2V5IGZvciB2ZW5kb3IgYg==
+`pragma protect data_keyowner="Example", data_method="aes128-cbc"
+`pragma protect encoding=(enctype="base64", line_length=76, bytes=48)
+`pragma protect data_block
+//8AZ3N0dWZmIHRoYXQgY2Fubm90IGJlIHBhcnNlZA==
+`pragma protect end_protected
+endmodule"##;
+        let (syntax_tree, _) = parse_sv_str(
+            src,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let protections = syntax_tree.get_protections();
+        assert_eq!(protections.len(), 1);
+        let x = &protections[0];
+        assert_eq!(x.data_keyowner.as_deref(), Some("Example"));
+        assert_eq!(x.data_method.as_deref(), Some("aes128-cbc"));
+        assert_eq!(
+            x.encoding,
+            Some(ProtectEncoding {
+                enctype: Some(String::from("base64")),
+                line_length: Some(76),
+                bytes: Some(48),
+            })
+        );
+        assert_eq!(
+            x.data_block.as_deref(),
+            Some("//8AZ3N0dWZmIHRoYXQgY2Fubm90IGJlIHBhcnNlZA==\n")
+        );
+
+        assert_eq!(x.key_blocks.len(), 2);
+        assert_eq!(x.key_blocks[0].key_keyowner.as_deref(), Some("Vendor A"));
+        assert_eq!(x.key_blocks[0].key_method.as_deref(), Some("rsa"));
+        assert_eq!(x.key_blocks[0].key_keyname.as_deref(), Some("KEY-A"));
+        assert_eq!(x.key_blocks[0].block, "a2V5IGZvciB2ZW5kb3IgYQ==\n");
+        assert_eq!(x.key_blocks[1].key_keyowner.as_deref(), Some("Vendor B"));
+        assert_eq!(x.key_blocks[1].key_keyname, None);
+        assert_eq!(
+            x.key_blocks[1].encoding.as_ref().and_then(|x| x.bytes),
+            Some(256)
+        );
+
+        let text = syntax_tree.get_str(&x.locate).unwrap();
+        assert!(text.starts_with("`pragma protect begin_protected"));
+        assert!(text.trim_end().ends_with("`pragma protect end_protected"));
+    }
+}
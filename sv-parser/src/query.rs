@@ -0,0 +1,479 @@
+use crate::{NodeTable, RefNode, SyntaxTree, REF_NODE_KINDS};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A compiled query selecting nodes of syntax trees
+///
+/// The syntax is similar to CSS selectors over variant names of `RefNode`.
+///
+/// ```text
+/// query    = selector ("," selector)*
+/// selector = compound ((">" | whitespace) compound)*
+/// compound = (kind | "*")? filter* ("@" name)?
+/// filter   = "[text" ("=" | "*=" | "^=" | "$=") string "]"
+///          | ":has(" query ")"
+///          | ":not(" compound ")"
+/// ```
+///
+/// `A B` selects `B` under `A`, and `A > B` selects `B` whose parent is `A`.
+/// `[text...]` compares the source text of the node without trailing white
+/// space, as equal, containing, starting with and ending with the string.
+/// `:has(...)` requires a descendant matched by the query, and `@name`
+/// captures the node.
+///
+/// For example, `AlwaysConstruct[text^="always_ff"]:not(:has(EventExpression[text*="rst"]))`
+/// selects `always_ff` blocks whose sensitivity list doesn't refer to reset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    selectors: Vec<Selector>,
+}
+
+/// A node selected by a query
+#[derive(Clone, Debug)]
+pub struct QueryMatch<'a> {
+    pub node: RefNode<'a>,
+    /// Nodes captured by `@name`
+    pub captures: BTreeMap<String, RefNode<'a>>,
+}
+
+/// Error of `Query::new`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueryError {
+    pub message: String,
+    /// Byte offset in the query text
+    pub position: usize,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Query error at {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Selector {
+    /// Compounds with the combinators to the previous compounds
+    steps: Vec<(Combinator, Compound)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Compound {
+    kind: Option<String>,
+    filters: Vec<Filter>,
+    capture: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Filter {
+    Text(TextOp, String),
+    Has(Vec<Selector>),
+    Not(Box<Compound>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TextOp {
+    Equal,
+    Contain,
+    Prefix,
+    Suffix,
+}
+
+impl Query {
+    /// Compile a query
+    pub fn new(s: &str) -> Result<Self, QueryError> {
+        let mut parser = Parser { s, pos: 0 };
+        let selectors = parser.query()?;
+        parser.skip_space();
+        if parser.pos < s.len() {
+            return Err(parser.error("unexpected character"));
+        }
+        Ok(Query { selectors })
+    }
+}
+
+impl SyntaxTree {
+    /// Get nodes selected by a query in pre-order
+    pub fn query(&self, query: &Query) -> Vec<QueryMatch<'_>> {
+        let nodes = Nodes::new(self);
+        let mut ret = Vec::new();
+        for i in 0..nodes.nodes.len() {
+            for selector in &query.selectors {
+                let mut captures = Vec::new();
+                if nodes.select(selector, i, None, &mut captures) {
+                    ret.push(QueryMatch {
+                        node: nodes.nodes[i].clone(),
+                        captures: captures
+                            .into_iter()
+                            .map(|(name, x)| (name, nodes.nodes[x].clone()))
+                            .collect(),
+                    });
+                    break;
+                }
+            }
+        }
+        ret
+    }
+}
+
+// Nodes of a syntax tree numbered as `NodeTable`
+struct Nodes<'a> {
+    tree: &'a SyntaxTree,
+    table: NodeTable,
+    nodes: Vec<RefNode<'a>>,
+}
+
+impl<'a> Nodes<'a> {
+    fn new(tree: &'a SyntaxTree) -> Self {
+        Nodes {
+            tree,
+            table: NodeTable::new(tree),
+            nodes: tree.into_iter().collect(),
+        }
+    }
+
+    // Whether node `i` is selected, where ancestors are searched below `root`
+    fn select(
+        &self,
+        selector: &Selector,
+        i: usize,
+        root: Option<usize>,
+        captures: &mut Vec<(String, usize)>,
+    ) -> bool {
+        self.select_step(&selector.steps, selector.steps.len() - 1, i, root, captures)
+    }
+
+    fn select_step(
+        &self,
+        steps: &[(Combinator, Compound)],
+        k: usize,
+        i: usize,
+        root: Option<usize>,
+        captures: &mut Vec<(String, usize)>,
+    ) -> bool {
+        let len = captures.len();
+        if !self.matches(&steps[k].1, i, captures) {
+            return false;
+        }
+        if k == 0 {
+            return true;
+        }
+        let mut parent = self.table.nodes[i].parent;
+        while let Some(j) = parent {
+            if Some(j) == root {
+                break;
+            }
+            if self.select_step(steps, k - 1, j, root, captures) {
+                return true;
+            }
+            if steps[k].0 == Combinator::Child {
+                break;
+            }
+            parent = self.table.nodes[j].parent;
+        }
+        captures.truncate(len);
+        false
+    }
+
+    fn matches(&self, compound: &Compound, i: usize, captures: &mut Vec<(String, usize)>) -> bool {
+        let len = captures.len();
+        let entry = &self.table.nodes[i];
+        if let Some(ref kind) = compound.kind {
+            if &self.table.kinds[entry.kind] != kind {
+                return false;
+            }
+        }
+        let text = entry.locate_trim.and_then(|x| self.tree.get_str(&x));
+        for filter in &compound.filters {
+            let ret = match filter {
+                Filter::Text(op, s) => match text {
+                    Some(text) => match op {
+                        TextOp::Equal => text == s,
+                        TextOp::Contain => text.contains(s.as_str()),
+                        TextOp::Prefix => text.starts_with(s.as_str()),
+                        TextOp::Suffix => text.ends_with(s.as_str()),
+                    },
+                    None => false,
+                },
+                Filter::Has(selectors) => (i + 1..=entry.last).any(|j| {
+                    selectors
+                        .iter()
+                        .any(|x| self.select(x, j, Some(i), captures))
+                }),
+                Filter::Not(x) => !self.matches(x, i, &mut Vec::new()),
+            };
+            if !ret {
+                captures.truncate(len);
+                return false;
+            }
+        }
+        if let Some(ref name) = compound.capture {
+            captures.push((name.clone(), i));
+        }
+        true
+    }
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> QueryError {
+        QueryError {
+            message: String::from(message),
+            position: self.pos,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), QueryError> {
+        if self.eat(s) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", s)))
+        }
+    }
+
+    fn skip_space(&mut self) -> bool {
+        let len = self.rest().len() - self.rest().trim_start().len();
+        self.pos += len;
+        len > 0
+    }
+
+    fn name(&mut self) -> &'a str {
+        let rest = self.rest();
+        let len = rest
+            .find(|x: char| !x.is_ascii_alphanumeric() && x != '_')
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn query(&mut self) -> Result<Vec<Selector>, QueryError> {
+        let mut ret = vec![self.selector()?];
+        loop {
+            self.skip_space();
+            if !self.eat(",") {
+                return Ok(ret);
+            }
+            ret.push(self.selector()?);
+        }
+    }
+
+    fn selector(&mut self) -> Result<Selector, QueryError> {
+        self.skip_space();
+        let mut steps = vec![(Combinator::Descendant, self.compound()?)];
+        loop {
+            let space = self.skip_space();
+            let combinator = if self.eat(">") {
+                self.skip_space();
+                Combinator::Child
+            } else if space && !matches!(self.peek(), None | Some(',') | Some(')')) {
+                Combinator::Descendant
+            } else {
+                return Ok(Selector { steps });
+            };
+            steps.push((combinator, self.compound()?));
+        }
+    }
+
+    fn compound(&mut self) -> Result<Compound, QueryError> {
+        let start = self.pos;
+        let kind = if self.eat("*") {
+            None
+        } else {
+            let name = self.name();
+            if name.is_empty() {
+                None
+            } else if REF_NODE_KINDS.contains(&name) {
+                Some(String::from(name))
+            } else {
+                self.pos = start;
+                return Err(self.error(&format!("unknown node kind `{}`", name)));
+            }
+        };
+
+        let mut filters = Vec::new();
+        loop {
+            if self.eat("[") {
+                self.skip_space();
+                self.expect("text")?;
+                self.skip_space();
+                let op = if self.eat("=") {
+                    TextOp::Equal
+                } else if self.eat("*=") {
+                    TextOp::Contain
+                } else if self.eat("^=") {
+                    TextOp::Prefix
+                } else if self.eat("$=") {
+                    TextOp::Suffix
+                } else {
+                    return Err(self.error("expected `=`, `*=`, `^=` or `$=`"));
+                };
+                self.skip_space();
+                let s = self.string()?;
+                self.skip_space();
+                self.expect("]")?;
+                filters.push(Filter::Text(op, s));
+            } else if self.eat(":has(") {
+                let x = self.query()?;
+                self.skip_space();
+                self.expect(")")?;
+                filters.push(Filter::Has(x));
+            } else if self.eat(":not(") {
+                self.skip_space();
+                let x = self.compound()?;
+                self.skip_space();
+                self.expect(")")?;
+                filters.push(Filter::Not(Box::new(x)));
+            } else {
+                break;
+            }
+        }
+
+        let capture = if self.eat("@") {
+            let name = self.name();
+            if name.is_empty() {
+                return Err(self.error("expected capture name"));
+            }
+            Some(String::from(name))
+        } else {
+            None
+        };
+
+        if self.pos == start {
+            return Err(self.error("expected node kind"));
+        }
+        Ok(Compound {
+            kind,
+            filters,
+            capture,
+        })
+    }
+
+    fn string(&mut self) -> Result<String, QueryError> {
+        self.expect("\"")?;
+        let mut ret = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, x)) = chars.next() {
+            match x {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(ret);
+                }
+                '\\' => match chars.next() {
+                    Some((_, x)) => ret.push(x),
+                    None => break,
+                },
+                _ => ret.push(x),
+            }
+        }
+        self.pos = self.s.len();
+        Err(self.error("unterminated string"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_query() {
+        let src = r##"module A (input clk, input rst_n, input [7:0] d, output logic [7:0] q, r);
+    always_ff @(posedge clk or negedge rst_n) if (!rst_n) q <= 0; else q <= d;
+    always_ff @(posedge clk) r <= d;
+    always_comb begin end
+    B u_b (.x(q));
+endmodule
+
+module B (input x);
+endmodule"##;
+        let (syntax_tree, _) = parse_sv_str(
+            src,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let query = |s| {
+            let query = Query::new(s).unwrap();
+            syntax_tree
+                .query(&query)
+                .iter()
+                .map(|x| String::from(syntax_tree.get_str_trim(vec![x.node.clone()]).unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            query(r#"AlwaysConstruct[text^="always_ff"]:not(:has(EventExpression[text*="rst"]))"#),
+            vec!["always_ff @(posedge clk) r <= d;"]
+        );
+        assert_eq!(
+            query("ModuleDeclarationAnsi > ModuleAnsiHeader ModuleIdentifier"),
+            vec!["A", "B"]
+        );
+        assert_eq!(
+            query("ModuleDeclarationAnsi > ModuleIdentifier"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            query(r#"ModuleInstantiation ModuleIdentifier, PortIdentifier[text$="_n"]"#),
+            vec!["rst_n", "B"]
+        );
+        assert_eq!(query(r#"Keyword[text = "begin"]"#), vec!["begin"]);
+
+        let query = Query::new(r#"ModuleDeclarationAnsi:has(ModuleIdentifier@name) AlwaysConstruct@always > Statement:has(ConditionalStatement)"#).unwrap();
+        let matches = syntax_tree.query(&query);
+        assert_eq!(matches.len(), 1);
+        let captures: Vec<_> = matches[0]
+            .captures
+            .iter()
+            .map(|(k, v)| {
+                (
+                    k.as_str(),
+                    syntax_tree.get_str_trim(vec![v.clone()]).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(captures[0].0, "always");
+        assert!(captures[0].1.starts_with("always_ff @(posedge clk or"));
+        assert_eq!(captures[1], ("name", "A"));
+
+        let err = Query::new("ModuleDeclaration >").unwrap_err();
+        assert_eq!(err.position, 19);
+        let err = Query::new("Modul").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Query error at 0: unknown node kind `Modul`"
+        );
+        assert!(Query::new(r#"*[text="a]"#).is_err());
+        assert!(Query::new("A:has(B").is_err());
+    }
+}
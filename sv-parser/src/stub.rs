@@ -0,0 +1,323 @@
+use crate::*;
+
+impl SyntaxTree {
+    /// Generate black-box stubs of the modules in the tree.
+    ///
+    /// A stub has the header of the module with attributes, parameters and
+    /// ports, and keeps timeunits, port, parameter, typedef and import
+    /// declarations of the body. The `timescale in effect for the module is
+    /// placed before it. `initial` is the state left by the previously
+    /// compiled file as `get_directive_states`.
+    pub fn generate_stubs(&self, initial: &DirectiveState) -> String {
+        let builder = Builder::from_syntax_tree(self);
+        let mut ret = Vec::new();
+        for x in self.get_directive_states(initial).elements {
+            if let RefNode::ModuleDeclaration(y) = x.node {
+                if let Some(stub) = stub(y) {
+                    ret.push(format!("{}{}", timescale(&x), builder.print(&stub)));
+                }
+            }
+        }
+        ret.join("\n")
+    }
+
+    /// Generate a module `wrapper` which has the same parameters and ports as
+    /// `module` and instantiates it as `instance` with all parameters and
+    /// ports connected by name. `None` is returned if `module` isn't found.
+    pub fn generate_wrapper(
+        &self,
+        module: &str,
+        wrapper: &str,
+        instance: &str,
+        initial: &DirectiveState,
+    ) -> Option<String> {
+        let states = self.get_directive_states(initial);
+        let (state, x) = states.elements.iter().find_map(|x| match x.node {
+            RefNode::ModuleDeclaration(y) if y.name().text(self) == module => Some((x, y)),
+            _ => None,
+        })?;
+        let summary = self
+            .get_design_unit_summaries()
+            .into_iter()
+            .find(|x| x.kind == "module" && x.name == module)?;
+
+        let mut b = Builder::from_syntax_tree(self);
+        let mut parameters = Vec::new();
+        for y in summary.parameters.iter().filter(|y| !y.local) {
+            parameters.push((y.name.as_str(), b.expression_identifier(&y.name)));
+        }
+        let mut ports = Vec::new();
+        for y in x.ports() {
+            let name = match y.name() {
+                Some(z) => z.text(self),
+                None => continue,
+            };
+            // The internal name of `.a(b)` is `b`
+            let internal = match y {
+                ModulePort::Nonansi(Port::Named(z)) => match (z.nodes.2).nodes.1 {
+                    Some(PortExpression::PortReference(ref w))
+                        if self.get_str(&w.nodes.1).is_none() =>
+                    {
+                        Some(w.nodes.0.text(self))
+                    }
+                    _ => None,
+                },
+                _ => Some(name),
+            };
+            ports.push((name, internal.map(|z| b.expression_identifier(z))));
+        }
+        let instantiation = b.module_instantiation(module, parameters, instance, ports);
+
+        let name = ModuleIdentifier {
+            nodes: (b.identifier(wrapper),),
+        };
+        let ret = match stub(x)? {
+            ModuleDeclaration::Nonansi(mut y) => {
+                (y.nodes.0).nodes.0.clear();
+                (y.nodes.0).nodes.3 = name;
+                let item = instantiation.into_module_item();
+                y.nodes
+                    .2
+                    .push(ModuleItem::NonPortModuleItem(Box::new(item)));
+                ModuleDeclaration::Nonansi(y)
+            }
+            ModuleDeclaration::Ansi(mut y) => {
+                (y.nodes.0).nodes.0.clear();
+                (y.nodes.0).nodes.3 = name;
+                y.nodes.2.push(instantiation.into_module_item());
+                ModuleDeclaration::Ansi(y)
+            }
+            ModuleDeclaration::Wildcard(mut y) => {
+                y.nodes.0.clear();
+                y.nodes.3 = name;
+                let item = instantiation.into_module_item();
+                y.nodes
+                    .7
+                    .push(ModuleItem::NonPortModuleItem(Box::new(item)));
+                ModuleDeclaration::Wildcard(y)
+            }
+            _ => return None,
+        };
+        Some(format!("{}{}", timescale(state), b.print(&ret)))
+    }
+}
+
+// Copy of a module without the body except the declarations which the header
+// and port declarations may depend on. The label after `endmodule` is removed.
+fn stub(x: &ModuleDeclaration) -> Option<ModuleDeclaration> {
+    let ret = match x {
+        ModuleDeclaration::Nonansi(x) => {
+            let (ref header, ref timeunits, ref items, ref end, _) = x.nodes;
+            let items = items.iter().filter(|y| keep_item(y)).cloned().collect();
+            ModuleDeclaration::Nonansi(Box::new(ModuleDeclarationNonansi {
+                nodes: (header.clone(), timeunits.clone(), items, end.clone(), None),
+            }))
+        }
+        ModuleDeclaration::Ansi(x) => {
+            let (ref header, ref timeunits, ref items, ref end, _) = x.nodes;
+            let items = items
+                .iter()
+                .filter(|y| keep_non_port_item(y))
+                .cloned()
+                .collect();
+            ModuleDeclaration::Ansi(Box::new(ModuleDeclarationAnsi {
+                nodes: (header.clone(), timeunits.clone(), items, end.clone(), None),
+            }))
+        }
+        ModuleDeclaration::Wildcard(x) => {
+            let mut x = x.as_ref().clone();
+            x.nodes.7.retain(keep_item);
+            x.nodes.9 = None;
+            ModuleDeclaration::Wildcard(Box::new(x))
+        }
+        ModuleDeclaration::ExternNonansi(_) | ModuleDeclaration::ExternAnsi(_) => return None,
+    };
+    Some(ret)
+}
+
+fn keep_item(x: &ModuleItem) -> bool {
+    match x {
+        ModuleItem::PortDeclaration(_) => true,
+        ModuleItem::NonPortModuleItem(x) => keep_non_port_item(x),
+    }
+}
+
+fn keep_non_port_item(x: &NonPortModuleItem) -> bool {
+    let x = match x {
+        NonPortModuleItem::TimeunitsDeclaration(_) => return true,
+        NonPortModuleItem::ModuleOrGenerateItem(x) => match x.as_ref() {
+            ModuleOrGenerateItem::ModuleItem(x) => &x.nodes.1,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    let x = match x {
+        ModuleCommonItem::ModuleOrGenerateItemDeclaration(x) => match x.as_ref() {
+            ModuleOrGenerateItemDeclaration::PackageOrGenerateItemDeclaration(x) => x,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    match x.as_ref() {
+        PackageOrGenerateItemDeclaration::ParameterDeclaration(_)
+        | PackageOrGenerateItemDeclaration::LocalParameterDeclaration(_) => true,
+        PackageOrGenerateItemDeclaration::DataDeclaration(x) => matches!(
+            x.as_ref(),
+            DataDeclaration::TypeDeclaration(_) | DataDeclaration::PackageImportDeclaration(_)
+        ),
+        _ => false,
+    }
+}
+
+// `timescale directive line in effect for the design element
+fn timescale(x: &DesignElementState) -> String {
+    match (x.timescale_source, &x.time_unit, &x.time_precision) {
+        (TimescaleSource::Directive, Some(unit), Some(precision))
+        | (TimescaleSource::Inherited, Some(unit), Some(precision)) => {
+            format!("`timescale {}/{}\n", unit, precision)
+        }
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_stubs() {
+        let src = r##"`timescale 1ns/1ps
+(* black_box = "yes" *)
+module fifo #(parameter int W = 8, parameter type T = logic) (
+    input  logic clk, // clock
+    input  T [W-1:0] d,
+    output logic [W-1:0] q
+);
+    localparam D = W * 2;
+    logic [D-1:0] mem;
+    always_ff @(posedge clk) q <= d;
+endmodule : fifo
+module old (a, .b(c), y);
+    parameter N = 4;
+    input [N-1:0] a;
+    input c;
+    output reg y;
+    always @* y = a[0] & c;
+endmodule
+"##;
+        let (syntax_tree, _) = parse_sv_str(
+            src,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let state = DirectiveState::default();
+
+        let stubs = syntax_tree.generate_stubs(&state);
+        assert_eq!(
+            stubs,
+            r##"`timescale 1ns/1ps
+(* black_box = "yes" *) module fifo #(
+  parameter int W = 8,
+  parameter type T = logic
+) (
+  input logic clk,
+  input T [W - 1:0] d,
+  output logic [W - 1:0] q
+);
+  localparam D = W * 2;
+endmodule
+
+`timescale 1ns/1ps
+module old (
+  a,
+  .b(c),
+  y
+);
+  parameter N = 4;
+  input [N - 1:0] a;
+  input c;
+  output reg y;
+endmodule
+"##
+        );
+        assert!(parse_sv_str(
+            &stubs,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default()
+        )
+        .is_ok());
+
+        let wrapper = syntax_tree
+            .generate_wrapper("old", "old_wrapper", "u_old", &state)
+            .unwrap();
+        assert_eq!(
+            wrapper,
+            r##"`timescale 1ns/1ps
+module old_wrapper (
+  a,
+  .b(c),
+  y
+);
+  parameter N = 4;
+  input [N - 1:0] a;
+  input c;
+  output reg y;
+  old #(
+    .N(N)
+  ) u_old (
+    .a(a),
+    .b(c),
+    .y(y)
+  );
+endmodule
+"##
+        );
+
+        let wrapper = syntax_tree
+            .generate_wrapper("fifo", "fifo_wrapper", "u_fifo", &state)
+            .unwrap();
+        let (wrapper_tree, _) = parse_sv_str(
+            &wrapper,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let units = wrapper_tree.get_design_unit_summaries();
+        let fifo = &syntax_tree.get_design_unit_summaries()[0];
+        assert_eq!(units[0].name, "fifo_wrapper");
+        assert_eq!(units[0].parameters, fifo.parameters);
+        // Dimensions are compared by values since the printer adds spaces
+        let ports = |x: &DesignUnitSummary| -> Vec<_> {
+            x.ports
+                .iter()
+                .map(|y| {
+                    (
+                        y.name.clone(),
+                        y.direction.clone(),
+                        y.data_type.clone(),
+                        y.packed_ranges.clone(),
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(ports(&units[0]), ports(fifo));
+        let instances: Vec<_> = units[0]
+            .instances
+            .iter()
+            .map(|x| (x.module.as_str(), x.name.as_str()))
+            .collect();
+        assert_eq!(instances, [("fifo", "u_fifo")]);
+        assert!(!wrapper.contains("black_box"));
+        assert!(syntax_tree
+            .generate_wrapper("none", "w", "u", &state)
+            .is_none());
+    }
+}
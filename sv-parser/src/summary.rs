@@ -0,0 +1,1021 @@
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Interface of a module, an interface or a program
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DesignUnitSummary {
+    /// `module`, `interface` or `program`
+    pub kind: &'static str,
+    pub name: String,
+    /// Location of the identifier
+    pub location: Option<Location>,
+    pub parameters: Vec<ParameterSummary>,
+    pub ports: Vec<PortSummary>,
+    /// Modports of interfaces
+    pub modports: Vec<ModportSummary>,
+    /// Instances in the body including generate blocks
+    pub instances: Vec<InstanceSummary>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ParameterSummary {
+    pub name: String,
+    /// `localparam`, or `parameter` in the body of a unit with a parameter
+    /// port list, which can't be overridden
+    pub local: bool,
+    /// Type parameter declared by `parameter type`
+    pub is_type: bool,
+    /// Data type of a value parameter, `None` if implicit
+    pub data_type: Option<String>,
+    /// Default value, or default type of a type parameter
+    pub default: Option<String>,
+    /// Default value evaluated with the default values of the preceding
+    /// parameters, `None` if it isn't an integer constant
+    pub value: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PortSummary {
+    /// Port name, or the port expression of an unnamed port like `{a, b}`
+    pub name: String,
+    /// `input`, `output`, `inout` or `ref`, `None` for interface ports and
+    /// non-ANSI ports without declarations
+    pub direction: Option<String>,
+    /// Data type, net type or interface without packed dimensions, `None`
+    /// if implicit
+    pub data_type: Option<String>,
+    pub packed_dimensions: Vec<String>,
+    /// Packed dimensions evaluated to `(left, right)` with the default values
+    /// of parameters, `None` if not constant
+    pub packed_ranges: Vec<Option<(i64, i64)>>,
+    pub unpacked_dimensions: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ModportSummary {
+    pub name: String,
+    pub ports: Vec<ModportPortSummary>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ModportPortSummary {
+    pub name: String,
+    /// Port direction, `import`, `export` or `clocking`
+    pub direction: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct InstanceSummary {
+    /// Name of the instantiated module, interface or program
+    pub module: String,
+    pub name: String,
+}
+
+impl SyntaxTree {
+    /// Get summaries of modules, interfaces and programs in the tree.
+    ///
+    /// `extern` declarations are not included.
+    pub fn get_design_unit_summaries(&self) -> Vec<DesignUnitSummary> {
+        let mut ret = Vec::new();
+        for node in self {
+            let (kind, header) = match node {
+                RefNode::ModuleDeclarationNonansi(x) => ("module", Header::from(&x.nodes.0)),
+                RefNode::ModuleDeclarationAnsi(x) => ("module", Header::from(&x.nodes.0)),
+                RefNode::ModuleDeclarationWildcard(x) => ("module", Header::wildcard(&x.nodes.3)),
+                RefNode::InterfaceDeclarationNonansi(x) => ("interface", Header::from(&x.nodes.0)),
+                RefNode::InterfaceDeclarationAnsi(x) => ("interface", Header::from(&x.nodes.0)),
+                RefNode::InterfaceDeclarationWildcard(x) => {
+                    ("interface", Header::wildcard(&x.nodes.3))
+                }
+                RefNode::ProgramDeclarationNonansi(x) => ("program", Header::from(&x.nodes.0)),
+                RefNode::ProgramDeclarationAnsi(x) => ("program", Header::from(&x.nodes.0)),
+                RefNode::ProgramDeclarationWildcard(x) => ("program", Header::wildcard(&x.nodes.2)),
+                _ => continue,
+            };
+            ret.push(self.summary(kind, header, node.clone()));
+        }
+        ret
+    }
+
+    fn summary(&self, kind: &'static str, header: Header, node: RefNode) -> DesignUnitSummary {
+        let body = Body::new(node);
+
+        let mut parameters = Vec::new();
+        if let Some(x) = header.parameters {
+            self.header_parameters(x, &mut parameters);
+        }
+        for x in &body.parameters {
+            let local = header.parameters.is_some();
+            self.parameter_declaration(x, local, &mut parameters);
+        }
+
+        let values = parameter_values(&parameters);
+        let ports = match header.ports {
+            HeaderPorts::Nonansi(x) => self.nonansi_ports(x, &body.port_declarations, &values),
+            HeaderPorts::Ansi(Some(x)) => self.ansi_ports(x, &values),
+            HeaderPorts::Ansi(None) | HeaderPorts::Wildcard => vec![],
+        };
+
+        let modports = body
+            .modports
+            .iter()
+            .flat_map(|x| x.nodes.1.contents())
+            .map(|x| self.modport(x))
+            .collect();
+
+        let mut instances = Vec::new();
+        for (module, x) in &body.instances {
+            for x in x.contents() {
+                instances.push(InstanceSummary {
+                    module: String::from(module.text(self)),
+                    name: String::from(x.name().text(self)),
+                });
+            }
+        }
+
+        let locate = header.name.locate();
+        let location = self.get_origin(locate).map(|(path, offset)| {
+            let mut ret = Location::new(path, Some(offset));
+            if let Some(source) = self.get_source(path) {
+                ret.resolve(source);
+            }
+            ret
+        });
+
+        DesignUnitSummary {
+            kind,
+            name: String::from(header.name.text(self)),
+            location,
+            parameters,
+            ports,
+            modports,
+            instances,
+        }
+    }
+
+    fn header_parameters(&self, list: &ParameterPortList, ret: &mut Vec<ParameterSummary>) {
+        let decls = match list {
+            ParameterPortList::Assignment(x) => {
+                let (ref list, ref decls) = (x.nodes.1).nodes.1;
+                for x in list.nodes.0.contents() {
+                    let x = self.param_assignment(x, false, None, ret);
+                    ret.push(x);
+                }
+                decls.iter().map(|(_, x)| x).collect()
+            }
+            ParameterPortList::Declaration(x) => (x.nodes.1).nodes.1.contents(),
+            ParameterPortList::Empty(_) => vec![],
+        };
+
+        // Declarations without `parameter` or `localparam` inherit it from the
+        // previous declaration
+        let mut local = false;
+        for x in decls {
+            match x {
+                ParameterPortDeclaration::ParameterDeclaration(x) => {
+                    local = false;
+                    self.parameter_declaration(
+                        &RefNode::ParameterDeclaration(x.as_ref()),
+                        local,
+                        ret,
+                    );
+                }
+                ParameterPortDeclaration::LocalParameterDeclaration(x) => {
+                    local = true;
+                    self.parameter_declaration(
+                        &RefNode::LocalParameterDeclaration(x.as_ref()),
+                        local,
+                        ret,
+                    );
+                }
+                ParameterPortDeclaration::ParamList(x) => {
+                    let data_type = self.compact_str(&x.nodes.0);
+                    for y in x.nodes.1.nodes.0.contents() {
+                        let y = self.param_assignment(y, local, data_type.clone(), ret);
+                        ret.push(y);
+                    }
+                }
+                ParameterPortDeclaration::TypeList(x) => {
+                    for y in x.nodes.1.nodes.0.contents() {
+                        ret.push(self.type_assignment(y, local));
+                    }
+                }
+            }
+        }
+    }
+
+    // `local` is forced for `parameter` in the body of units with parameter port lists
+    fn parameter_declaration(&self, node: &RefNode, local: bool, ret: &mut Vec<ParameterSummary>) {
+        let (local, data_type, list) = match node {
+            RefNode::ParameterDeclaration(ParameterDeclaration::Param(x)) => {
+                (local, &x.nodes.1, &x.nodes.2)
+            }
+            RefNode::LocalParameterDeclaration(LocalParameterDeclaration::Param(x)) => {
+                (true, &x.nodes.1, &x.nodes.2)
+            }
+            RefNode::ParameterDeclaration(ParameterDeclaration::Type(x)) => {
+                for y in x.nodes.2.nodes.0.contents() {
+                    ret.push(self.type_assignment(y, local));
+                }
+                return;
+            }
+            RefNode::LocalParameterDeclaration(LocalParameterDeclaration::Type(x)) => {
+                for y in x.nodes.2.nodes.0.contents() {
+                    ret.push(self.type_assignment(y, true));
+                }
+                return;
+            }
+            _ => return,
+        };
+        let data_type = self.compact_str(data_type);
+        for x in list.nodes.0.contents() {
+            let x = self.param_assignment(x, local, data_type.clone(), ret);
+            ret.push(x);
+        }
+    }
+
+    fn param_assignment(
+        &self,
+        x: &ParamAssignment,
+        local: bool,
+        data_type: Option<String>,
+        previous: &[ParameterSummary],
+    ) -> ParameterSummary {
+        let value = match x.nodes.2 {
+            Some((_, ConstantParamExpression::ConstantMintypmaxExpression(ref y))) => match **y {
+                ConstantMintypmaxExpression::Unary(ref z) => {
+                    self.eval_constant_expression(z, &parameter_values(previous))
+                }
+                ConstantMintypmaxExpression::Ternary(_) => None,
+            },
+            _ => None,
+        };
+        ParameterSummary {
+            name: String::from(x.name().text(self)),
+            local,
+            is_type: false,
+            data_type,
+            default: x.nodes.2.as_ref().and_then(|(_, x)| self.compact_str(x)),
+            value,
+        }
+    }
+
+    fn type_assignment(&self, x: &TypeAssignment, local: bool) -> ParameterSummary {
+        ParameterSummary {
+            name: String::from(x.nodes.0.text(self)),
+            local,
+            is_type: true,
+            data_type: None,
+            default: x.nodes.1.as_ref().and_then(|(_, x)| self.compact_str(x)),
+            value: None,
+        }
+    }
+
+    fn ansi_ports(
+        &self,
+        list: &ListOfPortDeclarations,
+        values: &HashMap<String, i64>,
+    ) -> Vec<PortSummary> {
+        let mut ret: Vec<PortSummary> = Vec::new();
+        for x in list.ports() {
+            // IEEE1800-2017 Clause 23.2.2.3
+            // Ports without direction and type inherit them from the previous port.
+            let prev = ret.last();
+            let prev_direction = prev
+                .and_then(|x| x.direction.clone())
+                .unwrap_or_else(|| String::from("inout"));
+            let inherit = || match prev {
+                Some(x) => (
+                    x.direction.clone(),
+                    x.data_type.clone(),
+                    (x.packed_dimensions.clone(), x.packed_ranges.clone()),
+                ),
+                None => (Some(prev_direction.clone()), None, (vec![], vec![])),
+            };
+
+            let (direction, (data_type, packed), unpacked) = match x {
+                AnsiPortDeclaration::Net(x) => {
+                    let (direction, data_type, packed) = match x.nodes.0 {
+                        Some(NetPortHeaderOrInterfacePortHeader::NetPortHeader(ref y)) => {
+                            let direction = match y.nodes.0 {
+                                Some(ref z) => self.compact_str(z),
+                                None => Some(prev_direction.clone()),
+                            };
+                            let (data_type, packed) = self.split_dimensions(&y.nodes.1, values);
+                            (direction, data_type, packed)
+                        }
+                        Some(NetPortHeaderOrInterfacePortHeader::InterfacePortHeader(ref y)) => {
+                            (None, self.compact_str(y.as_ref()), (vec![], vec![]))
+                        }
+                        None => inherit(),
+                    };
+                    (
+                        direction,
+                        (data_type, packed),
+                        x.nodes
+                            .2
+                            .iter()
+                            .filter_map(|y| self.compact_str(y))
+                            .collect(),
+                    )
+                }
+                AnsiPortDeclaration::Variable(x) => {
+                    let (direction, data_type, packed) = match x.nodes.0 {
+                        Some(ref y) => {
+                            let direction = match y.nodes.0 {
+                                Some(ref z) => self.compact_str(z),
+                                None => Some(prev_direction.clone()),
+                            };
+                            let (data_type, packed) = self.split_dimensions(&y.nodes.1, values);
+                            (direction, data_type, packed)
+                        }
+                        None => inherit(),
+                    };
+                    (
+                        direction,
+                        (data_type, packed),
+                        x.nodes
+                            .2
+                            .iter()
+                            .filter_map(|y| self.compact_str(y))
+                            .collect(),
+                    )
+                }
+                AnsiPortDeclaration::Paren(x) => {
+                    let direction = match x.nodes.0 {
+                        Some(ref y) => self.compact_str(y),
+                        None => Some(prev_direction.clone()),
+                    };
+                    (direction, (None, (vec![], vec![])), vec![])
+                }
+            };
+            ret.push(PortSummary {
+                name: String::from(x.name().text(self)),
+                direction,
+                data_type,
+                packed_dimensions: packed.0,
+                packed_ranges: packed.1,
+                unpacked_dimensions: unpacked,
+            });
+        }
+        ret
+    }
+
+    fn nonansi_ports(
+        &self,
+        list: &ListOfPorts,
+        decls: &[&PortDeclaration],
+        values: &HashMap<String, i64>,
+    ) -> Vec<PortSummary> {
+        let mut declared = Vec::new();
+        for x in decls {
+            self.port_declaration(x, values, &mut declared);
+        }
+
+        let mut ret = Vec::new();
+        for x in list.ports() {
+            // The internal name of `.a(b)` is `b`
+            let (name, internal) = match x {
+                Port::Named(y) => {
+                    let internal = match (y.nodes.2).nodes.1 {
+                        Some(PortExpression::PortReference(ref z)) => Some(&z.nodes.0),
+                        _ => None,
+                    };
+                    (Some(&y.nodes.1), internal)
+                }
+                Port::NonNamed(_) => (x.name(), x.name()),
+            };
+            let name = match name {
+                Some(x) => String::from(x.text(self)),
+                None => match self.compact_str(x) {
+                    Some(x) => x,
+                    None => continue,
+                },
+            };
+            let declared = internal.and_then(|y| {
+                let y = y.text(self);
+                declared.iter().find(|z: &&PortSummary| z.name == y)
+            });
+            match declared {
+                Some(x) => ret.push(PortSummary { name, ..x.clone() }),
+                None => ret.push(PortSummary {
+                    name,
+                    direction: None,
+                    data_type: None,
+                    packed_dimensions: vec![],
+                    packed_ranges: vec![],
+                    unpacked_dimensions: vec![],
+                }),
+            }
+        }
+        ret
+    }
+
+    fn port_declaration(
+        &self,
+        x: &PortDeclaration,
+        values: &HashMap<String, i64>,
+        ret: &mut Vec<PortSummary>,
+    ) {
+        let (direction, data_type, names) = match x {
+            PortDeclaration::Inout(x) => {
+                let x = &x.nodes.1;
+                (
+                    &x.nodes.0,
+                    RefNode::NetPortType(&x.nodes.1),
+                    self.port_identifiers(&x.nodes.2),
+                )
+            }
+            PortDeclaration::Input(x) => match x.nodes.1 {
+                InputDeclaration::Net(ref x) => (
+                    &x.nodes.0,
+                    RefNode::NetPortType(&x.nodes.1),
+                    self.port_identifiers(&x.nodes.2),
+                ),
+                InputDeclaration::Variable(ref x) => (
+                    &x.nodes.0,
+                    RefNode::VariablePortType(&x.nodes.1),
+                    self.variable_identifiers(&x.nodes.2),
+                ),
+            },
+            PortDeclaration::Output(x) => match x.nodes.1 {
+                OutputDeclaration::Net(ref x) => (
+                    &x.nodes.0,
+                    RefNode::NetPortType(&x.nodes.1),
+                    self.port_identifiers(&x.nodes.2),
+                ),
+                OutputDeclaration::Variable(ref x) => {
+                    let names = (x.nodes.2)
+                        .nodes
+                        .0
+                        .contents()
+                        .into_iter()
+                        .map(|(x, y, _)| {
+                            (
+                                x.as_ref(),
+                                y.iter().filter_map(|y| self.compact_str(y)).collect(),
+                            )
+                        })
+                        .collect();
+                    (&x.nodes.0, RefNode::VariablePortType(&x.nodes.1), names)
+                }
+            },
+            PortDeclaration::Ref(x) => {
+                let x = &x.nodes.1;
+                (
+                    &x.nodes.0,
+                    RefNode::VariablePortType(&x.nodes.1),
+                    self.variable_identifiers(&x.nodes.2),
+                )
+            }
+            PortDeclaration::Interface(x) => {
+                let x = &x.nodes.1;
+                let data_type = match x.nodes.1 {
+                    Some((_, ref y)) => format!("{}.{}", x.nodes.0.text(self), y.text(self)),
+                    None => String::from(x.nodes.0.text(self)),
+                };
+                for (name, unpacked) in (x.nodes.2).nodes.0.contents() {
+                    ret.push(PortSummary {
+                        name: String::from(name.text(self)),
+                        direction: None,
+                        data_type: Some(data_type.clone()),
+                        packed_dimensions: vec![],
+                        packed_ranges: vec![],
+                        unpacked_dimensions: unpacked
+                            .iter()
+                            .filter_map(|x| self.compact_str(x))
+                            .collect(),
+                    });
+                }
+                return;
+            }
+        };
+        let direction = self.compact_str(direction);
+        let (data_type, (packed, ranges)) = self.split_dimensions(vec![data_type], values);
+        for (name, unpacked) in names {
+            ret.push(PortSummary {
+                name: String::from(name.text(self)),
+                direction: direction.clone(),
+                data_type: data_type.clone(),
+                packed_dimensions: packed.clone(),
+                packed_ranges: ranges.clone(),
+                unpacked_dimensions: unpacked,
+            });
+        }
+    }
+
+    fn modport(&self, x: &ModportItem) -> ModportSummary {
+        let mut ports = Vec::new();
+        for y in (x.nodes.1).nodes.1.contents() {
+            match y {
+                ModportPortsDeclaration::Simple(y) => {
+                    let y = &y.nodes.1;
+                    let direction = self.compact_str(&y.nodes.0).unwrap_or_default();
+                    for z in y.nodes.1.contents() {
+                        let name = match z {
+                            ModportSimplePort::Ordered(z) => &z.nodes.0,
+                            ModportSimplePort::Named(z) => &z.nodes.1,
+                        };
+                        ports.push(ModportPortSummary {
+                            name: String::from(name.text(self)),
+                            direction: direction.clone(),
+                        });
+                    }
+                }
+                ModportPortsDeclaration::Tf(y) => {
+                    let y = &y.nodes.1;
+                    let direction = self.compact_str(&y.nodes.0).unwrap_or_default();
+                    for z in y.nodes.1.contents() {
+                        let name = match z {
+                            ModportTfPort::TfIdentifier(z) => Some(z.text(self)),
+                            ModportTfPort::MethodPrototype(z) => {
+                                match unwrap_node!(z.as_ref(), TaskIdentifier, FunctionIdentifier) {
+                                    Some(RefNode::TaskIdentifier(z)) => Some(z.text(self)),
+                                    Some(RefNode::FunctionIdentifier(z)) => Some(z.text(self)),
+                                    _ => None,
+                                }
+                            }
+                        };
+                        if let Some(name) = name {
+                            ports.push(ModportPortSummary {
+                                name: String::from(name),
+                                direction: direction.clone(),
+                            });
+                        }
+                    }
+                }
+                ModportPortsDeclaration::Clocking(y) => {
+                    ports.push(ModportPortSummary {
+                        name: String::from((y.nodes.1).nodes.1.text(self)),
+                        direction: String::from("clocking"),
+                    });
+                }
+            }
+        }
+        ModportSummary {
+            name: String::from(x.nodes.0.text(self)),
+            ports,
+        }
+    }
+
+    fn port_identifiers<'a>(
+        &self,
+        x: &'a ListOfPortIdentifiers,
+    ) -> Vec<(&'a Identifier, Vec<String>)> {
+        (x.nodes.0)
+            .contents()
+            .into_iter()
+            .map(|(x, y)| {
+                (
+                    x.as_ref(),
+                    y.iter().filter_map(|y| self.compact_str(y)).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn variable_identifiers<'a>(
+        &self,
+        x: &'a ListOfVariableIdentifiers,
+    ) -> Vec<(&'a Identifier, Vec<String>)> {
+        (x.nodes.0)
+            .contents()
+            .into_iter()
+            .map(|(x, y)| {
+                (
+                    x.as_ref(),
+                    y.iter().filter_map(|y| self.compact_str(y)).collect(),
+                )
+            })
+            .collect()
+    }
+
+    // Source text where white spaces and comments are replaced by a space
+    fn compact_str<'a, T: Into<RefNodes<'a>>>(&self, nodes: T) -> Option<String> {
+        let (ret, _) = self.text_without_dimensions(nodes, false);
+        ret
+    }
+
+    // Source text of a data type without packed dimensions, and the packed
+    // dimensions with their evaluated ranges. Dimensions of struct members
+    // are kept in the data type.
+    fn split_dimensions<'a, T: Into<RefNodes<'a>>>(
+        &self,
+        nodes: T,
+        values: &HashMap<String, i64>,
+    ) -> (Option<String>, Packed) {
+        let (text, dimensions) = self.text_without_dimensions(nodes, true);
+        let packed = dimensions
+            .iter()
+            .filter_map(|x| self.compact_str(*x))
+            .collect();
+        let ranges = dimensions
+            .iter()
+            .map(|x| self.eval_packed_dimension(x, values))
+            .collect();
+        (text, (packed, ranges))
+    }
+
+    fn text_without_dimensions<'a, T: Into<RefNodes<'a>>>(
+        &self,
+        nodes: T,
+        split: bool,
+    ) -> (Option<String>, Vec<&'a PackedDimension>) {
+        let mut text = String::new();
+        let mut dimensions = Vec::new();
+        let mut skip = 0;
+        let mut member = 0;
+        let mut gap = false;
+        for event in Iter::new(nodes.into()).event() {
+            match event {
+                NodeEvent::Enter(x) => {
+                    if skip > 0 {
+                        skip += 1;
+                        continue;
+                    }
+                    match x {
+                        RefNode::WhiteSpace(_) => {
+                            gap = true;
+                            skip = 1;
+                        }
+                        RefNode::PackedDimension(x) if split && member == 0 => {
+                            dimensions.push(x);
+                            gap = true;
+                            skip = 1;
+                        }
+                        RefNode::StructUnionMember(_) => member += 1,
+                        RefNode::Locate(x) => {
+                            if gap && !text.is_empty() {
+                                text.push(' ');
+                            }
+                            text.push_str(x.str(self.text.text()));
+                            gap = false;
+                        }
+                        _ => (),
+                    }
+                }
+                NodeEvent::Leave(x) => {
+                    if skip > 0 {
+                        skip -= 1;
+                    } else if let RefNode::StructUnionMember(_) = x {
+                        member -= 1;
+                    }
+                }
+            }
+        }
+        let text = if text.is_empty() { None } else { Some(text) };
+        (text, dimensions)
+    }
+}
+
+// Packed dimensions and their evaluated ranges
+type Packed = (Vec<String>, Vec<Option<(i64, i64)>>);
+
+// Evaluated values of parameters to evaluate following declarations
+fn parameter_values(parameters: &[ParameterSummary]) -> HashMap<String, i64> {
+    parameters
+        .iter()
+        .filter_map(|x| x.value.map(|y| (x.name.clone(), y)))
+        .collect()
+}
+
+struct Header<'a> {
+    name: &'a Identifier,
+    parameters: Option<&'a ParameterPortList>,
+    ports: HeaderPorts<'a>,
+}
+
+enum HeaderPorts<'a> {
+    Nonansi(&'a ListOfPorts),
+    Ansi(Option<&'a ListOfPortDeclarations>),
+    Wildcard,
+}
+
+// Headers of modules, interfaces and programs have the same layout
+macro_rules! impl_from_header {
+    ($nonansi:ident, $ansi:ident) => {
+        impl<'a> From<&'a $nonansi> for Header<'a> {
+            fn from(x: &'a $nonansi) -> Self {
+                Header {
+                    name: x.nodes.3.as_ref(),
+                    parameters: x.nodes.5.as_ref(),
+                    ports: HeaderPorts::Nonansi(&x.nodes.6),
+                }
+            }
+        }
+
+        impl<'a> From<&'a $ansi> for Header<'a> {
+            fn from(x: &'a $ansi) -> Self {
+                Header {
+                    name: x.nodes.3.as_ref(),
+                    parameters: x.nodes.5.as_ref(),
+                    ports: HeaderPorts::Ansi(x.nodes.6.as_ref()),
+                }
+            }
+        }
+    };
+}
+
+impl_from_header!(ModuleNonansiHeader, ModuleAnsiHeader);
+impl_from_header!(InterfaceNonansiHeader, InterfaceAnsiHeader);
+impl_from_header!(ProgramNonansiHeader, ProgramAnsiHeader);
+
+impl<'a> Header<'a> {
+    fn wildcard<T: AsRef<Identifier>>(name: &'a T) -> Self {
+        Header {
+            name: name.as_ref(),
+            parameters: None,
+            ports: HeaderPorts::Wildcard,
+        }
+    }
+}
+
+// Declarations in the body of a design unit
+struct Body<'a> {
+    port_declarations: Vec<&'a PortDeclaration>,
+    /// `ParameterDeclaration` and `LocalParameterDeclaration` out of local
+    /// scopes like functions and generate blocks
+    parameters: Vec<RefNode<'a>>,
+    modports: Vec<&'a ModportDeclaration>,
+    instances: Vec<(&'a Identifier, &'a List<Symbol, HierarchicalInstance>)>,
+}
+
+impl<'a> Body<'a> {
+    fn new(node: RefNode<'a>) -> Self {
+        let mut ret = Body {
+            port_declarations: Vec::new(),
+            parameters: Vec::new(),
+            modports: Vec::new(),
+            instances: Vec::new(),
+        };
+        // Depth in skipped subtrees and local scopes
+        let mut skip = 0;
+        let mut local = 0;
+        for (i, event) in node.into_iter().event().enumerate() {
+            match event {
+                NodeEvent::Enter(x) => {
+                    if skip > 0 {
+                        skip += 1;
+                        continue;
+                    }
+                    match x {
+                        // Nested design units and the header parameters
+                        RefNode::ModuleDeclaration(_)
+                        | RefNode::InterfaceDeclaration(_)
+                        | RefNode::ProgramDeclaration(_)
+                        | RefNode::ParameterPortList(_)
+                            if i > 0 =>
+                        {
+                            skip = 1;
+                        }
+                        RefNode::FunctionDeclaration(_)
+                        | RefNode::TaskDeclaration(_)
+                        | RefNode::ClassDeclaration(_)
+                        | RefNode::InterfaceClassDeclaration(_)
+                        | RefNode::CovergroupDeclaration(_)
+                        | RefNode::CheckerDeclaration(_)
+                        | RefNode::GenerateBlock(_) => local += 1,
+                        RefNode::PortDeclaration(x) => ret.port_declarations.push(x),
+                        RefNode::ParameterDeclaration(_)
+                        | RefNode::LocalParameterDeclaration(_)
+                            if local == 0 =>
+                        {
+                            ret.parameters.push(x);
+                        }
+                        RefNode::ModportDeclaration(x) => ret.modports.push(x),
+                        RefNode::ModuleInstantiation(x) => {
+                            ret.instances.push((x.nodes.0.as_ref(), &x.nodes.2))
+                        }
+                        RefNode::InterfaceInstantiation(x) => {
+                            ret.instances.push((x.nodes.0.as_ref(), &x.nodes.2))
+                        }
+                        RefNode::ProgramInstantiation(x) => {
+                            ret.instances.push((x.nodes.0.as_ref(), &x.nodes.2))
+                        }
+                        _ => (),
+                    }
+                }
+                NodeEvent::Leave(x) => {
+                    if skip > 0 {
+                        skip -= 1;
+                        continue;
+                    }
+                    match x {
+                        RefNode::FunctionDeclaration(_)
+                        | RefNode::TaskDeclaration(_)
+                        | RefNode::ClassDeclaration(_)
+                        | RefNode::InterfaceClassDeclaration(_)
+                        | RefNode::CovergroupDeclaration(_)
+                        | RefNode::CheckerDeclaration(_)
+                        | RefNode::GenerateBlock(_) => local -= 1,
+                        _ => (),
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
+impl DesignUnitSummary {
+    /// Serialize to a JSON object
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+/// Serialize summaries to a JSON array
+#[cfg(feature = "serde")]
+pub fn summaries_to_json(summaries: &[DesignUnitSummary]) -> String {
+    serde_json::to_string(summaries).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_design_unit_summaries() {
+        let src = r##"module A #(parameter int W = 8, type T = logic, localparam D = W * 2) (
+    input clk, rst_n,
+    output logic [W-1:0][1:0] q [4],
+    bus_if.master bus
+);
+    parameter X = 1;
+    B #(.W(W)) u_b0 (.x(clk)), u_b1 (.x(rst_n));
+    if (W > 4) begin : g
+        localparam Y = 2;
+        C u_c ();
+    end
+    function automatic int f(input int a);
+        localparam Z = 3;
+        return a;
+    endfunction
+endmodule
+module B (x, .y(z), {p, r});
+    parameter W = 4;
+    input x;
+    output reg [W-1:0] z;
+    inout wire p, r;
+endmodule
+interface bus_if;
+    logic req, ack;
+    modport master (output req, input ack, import task send(), clocking cb);
+    modport slave (input req, output ack);
+endinterface
+program P (input clk);
+endprogram"##;
+        let (syntax_tree, _) = parse_sv_str(
+            src,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let units = syntax_tree.get_design_unit_summaries();
+        let names: Vec<_> = units.iter().map(|x| (x.kind, x.name.as_str())).collect();
+        assert_eq!(
+            names,
+            [
+                ("module", "A"),
+                ("module", "B"),
+                ("interface", "bus_if"),
+                ("program", "P")
+            ]
+        );
+
+        let a = &units[0];
+        let params: Vec<_> = a
+            .parameters
+            .iter()
+            .map(|x| {
+                (
+                    x.name.as_str(),
+                    x.local,
+                    x.is_type,
+                    x.data_type.as_deref(),
+                    x.default.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            params,
+            [
+                ("W", false, false, Some("int"), Some("8")),
+                ("T", false, true, None, Some("logic")),
+                ("D", true, false, None, Some("W * 2")),
+                ("X", true, false, None, Some("1")),
+            ]
+        );
+        let values: Vec<_> = a.parameters.iter().map(|x| x.value).collect();
+        assert_eq!(values, [Some(8), None, Some(16), Some(1)]);
+        assert_eq!(
+            a.ports[0],
+            PortSummary {
+                name: String::from("clk"),
+                direction: Some(String::from("input")),
+                data_type: None,
+                packed_dimensions: vec![],
+                packed_ranges: vec![],
+                unpacked_dimensions: vec![],
+            }
+        );
+        assert_eq!(a.ports[1].name, "rst_n");
+        assert_eq!(a.ports[1].direction.as_deref(), Some("input"));
+        assert_eq!(
+            a.ports[2],
+            PortSummary {
+                name: String::from("q"),
+                direction: Some(String::from("output")),
+                data_type: Some(String::from("logic")),
+                packed_dimensions: vec![String::from("[W-1:0]"), String::from("[1:0]")],
+                packed_ranges: vec![Some((7, 0)), Some((1, 0))],
+                unpacked_dimensions: vec![String::from("[4]")],
+            }
+        );
+        assert_eq!(a.ports[3].direction, None);
+        assert_eq!(a.ports[3].data_type.as_deref(), Some("bus_if.master"));
+        let instances: Vec<_> = a
+            .instances
+            .iter()
+            .map(|x| (x.module.as_str(), x.name.as_str()))
+            .collect();
+        assert_eq!(instances, [("B", "u_b0"), ("B", "u_b1"), ("C", "u_c")]);
+
+        let b = &units[1];
+        assert!(!b.parameters[0].local);
+        let ports: Vec<_> = b
+            .ports
+            .iter()
+            .map(|x| {
+                (
+                    x.name.as_str(),
+                    x.direction.as_deref(),
+                    x.data_type.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            ports,
+            [
+                ("x", Some("input"), None),
+                ("y", Some("output"), Some("reg")),
+                ("{p, r}", None, None)
+            ]
+        );
+        assert_eq!(b.ports[1].packed_dimensions, ["[W-1:0]"]);
+        assert_eq!(b.ports[1].packed_ranges, [Some((3, 0))]);
+
+        let bus = &units[2];
+        let modports: Vec<_> = bus.modports[0]
+            .ports
+            .iter()
+            .map(|x| (x.name.as_str(), x.direction.as_str()))
+            .collect();
+        assert_eq!(
+            modports,
+            [
+                ("req", "output"),
+                ("ack", "input"),
+                ("send", "import"),
+                ("cb", "clocking")
+            ]
+        );
+        assert_eq!(bus.modports[1].name, "slave");
+
+        let location = units[3].location.as_ref().unwrap();
+        assert_eq!(location.offset, src.find("P (input"));
+        assert_eq!((location.line, location.column), (Some(28), Some(9)));
+        assert_eq!(location.end_column, Some(10));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_design_unit_summaries_json() {
+        let src = "program P (input clk);\nendprogram\n";
+        let (syntax_tree, _) = parse_sv_str(
+            src,
+            PathBuf::from(""),
+            &HashMap::new(),
+            &[""],
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let json = summaries_to_json(&syntax_tree.get_design_unit_summaries());
+        assert_eq!(
+            json,
+            r#"[{"kind":"program","name":"P","location":{"path":"","offset":8,"line":1,"column":9,"end_offset":9,"end_line":1,"end_column":10},"parameters":[],"ports":[{"name":"clk","direction":"input","data_type":null,"packed_dimensions":[],"packed_ranges":[],"unpacked_dimensions":[]}],"modports":[],"instances":[]}]"#
+        );
+    }
+}
@@ -0,0 +1,4 @@
+`define BAD assign = b;
+module A;
+  `BAD
+endmodule
@@ -0,0 +1,3 @@
+module A;
+`include "inc.svh"
+endmodule
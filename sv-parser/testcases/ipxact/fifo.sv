@@ -0,0 +1,15 @@
+`include "fifo.svh"
+module fifo #(
+    parameter int DEPTH = `FIFO_DEPTH,
+    parameter int W = 8,
+    parameter NAME = "a<b>",
+    localparam int AW = $clog2(DEPTH)
+) (
+    input  logic         clk,
+    input  logic         rst_n,
+    input  logic [W-1:0] wdata,
+    output logic [W-1:0] rdata,
+    output logic [AW:0]  count,
+    bus_if.slave         bus
+);
+endmodule
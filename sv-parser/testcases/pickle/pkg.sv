@@ -0,0 +1,3 @@
+package pkg;
+  typedef logic [`W-1:0] t;
+endpackage
@@ -0,0 +1,11 @@
+`include "defs.svh"
+`define SUB(name) sub name ()
+module top;
+  import pkg::*;
+  `SUB(u0);
+  sub u1 ();
+endmodule : top
+module unused;
+endmodule
+module sub (output pkg::t q);
+endmodule